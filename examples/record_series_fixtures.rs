@@ -0,0 +1,30 @@
+//! Record series fixtures from the site.
+//!
+//! `cargo run --example record_series_fixtures --features test-util -- cassettes/series`
+//!
+//! Body of the `search2` request replaces `tests/series-search-page.html`,
+//! body of the first `ssearch` request replaces `tests/series-page.html`.
+use std::env;
+use std::path::PathBuf;
+
+use opensubs_rs::{BASE_URL, get_series_by_mask};
+use opensubs_rs::cassette::cassette::{record, ScrubOptions};
+use opensubs_rs::types::types::OperationResult;
+
+const SERIES_MASK: &str = "adventure time";
+const LANGUAGES: &str = "rus,eng";
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> OperationResult<()> {
+    let directory = env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("cassettes/series"));
+
+    let recorder = record(BASE_URL, &directory, ScrubOptions::default())?;
+    let client = reqwest::Client::new();
+
+    match get_series_by_mask(&client, &recorder.url(), "en", SERIES_MASK, LANGUAGES).await? {
+        Some(series) => println!("'{}': {} seasons recorded to '{}'", series.title, series.seasons.len(), directory.display()),
+        None => println!("series '{}' not found", SERIES_MASK)
+    }
+
+    Ok(())
+}
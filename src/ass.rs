@@ -0,0 +1,124 @@
+pub mod ass {
+    use std::sync::OnceLock;
+
+    use regex::Regex;
+
+    const MARKUP_TAG_PATTERN: &str = "<(/?)([a-zA-Z]+)[^>]*>";
+
+    static MARKUP_TAG_REGEX: OnceLock<Regex> = OnceLock::new();
+
+    const DEFAULT_FONT_NAME: &str = "Arial";
+    const DEFAULT_FONT_SIZE: u32 = 20;
+    const DEFAULT_PRIMARY_COLOUR: &str = "&H00FFFFFF";
+    const DEFAULT_MARGIN_V: u32 = 10;
+
+    /// Bottom center (numpad layout)
+    pub const ALIGNMENT_BOTTOM: u8 = 2;
+
+    /// Top center (numpad layout)
+    pub const ALIGNMENT_TOP: u8 = 8;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct AssStyle {
+        pub name: String,
+        pub font_name: String,
+        pub font_size: u32,
+
+        /// Colour in ASS format, `&HAABBGGRR`
+        pub primary_colour: String,
+
+        /// Numpad layout: 2 - bottom center, 8 - top center
+        pub alignment: u8,
+
+        pub margin_v: u32
+    }
+
+    impl AssStyle {
+        pub fn new(name: &str, alignment: u8) -> AssStyle {
+            AssStyle {
+                name: name.to_string(),
+                font_name: DEFAULT_FONT_NAME.to_string(),
+                font_size: DEFAULT_FONT_SIZE,
+                primary_colour: DEFAULT_PRIMARY_COLOUR.to_string(),
+                alignment,
+                margin_v: DEFAULT_MARGIN_V
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct AssEvent {
+        /// Start time in milliseconds
+        pub start: u64,
+
+        /// End time in milliseconds
+        pub end: u64,
+
+        /// Style name
+        pub style: String,
+
+        /// Text in cue markup (`\n` line breaks, `<i>`, `<b>`, `<u>` tags)
+        pub text: String
+    }
+
+    /// Write Advanced SubStation Alpha (v4.00+) document
+    pub fn write_ass(styles: &[AssStyle], events: &[AssEvent]) -> String {
+        let mut result = String::new();
+
+        result.push_str("[Script Info]\n");
+        result.push_str("ScriptType: v4.00+\n");
+        result.push_str("WrapStyle: 0\n");
+        result.push_str("ScaledBorderAndShadow: yes\n\n");
+
+        result.push_str("[V4+ Styles]\n");
+        result.push_str("Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, \
+                         BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, \
+                         BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n");
+
+        for style in styles {
+            result.push_str(&format!(
+                "Style: {},{},{},{},&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,0,{},10,10,{},1\n",
+                style.name, style.font_name, style.font_size, style.primary_colour,
+                style.alignment, style.margin_v
+            ));
+        }
+
+        result.push_str("\n[Events]\n");
+        result.push_str("Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n");
+
+        for event in events {
+            result.push_str(&format!(
+                "Dialogue: 0,{},{},{},,0,0,0,,{}\n",
+                format_ass_time(event.start), format_ass_time(event.end),
+                event.style, get_ass_text(&event.text)
+            ));
+        }
+
+        result
+    }
+
+    /// Format milliseconds as ASS time `H:MM:SS.cc`
+    pub fn format_ass_time(millis: u64) -> String {
+        let centis = (millis + 5) / 10;
+
+        format!(
+            "{}:{:02}:{:02}.{:02}",
+            centis / 360_000, (centis / 6000) % 60, (centis / 100) % 60, centis % 100
+        )
+    }
+
+    fn get_ass_text(text: &str) -> String {
+        let markup_tag_pattern = MARKUP_TAG_REGEX.get_or_init(|| Regex::new(MARKUP_TAG_PATTERN).unwrap());
+
+        let converted = markup_tag_pattern.replace_all(text, |groups: &regex::Captures| {
+            let state = if groups[1].is_empty() { "1" } else { "0" };
+
+            match groups[2].to_lowercase().as_str() {
+                tag @ ("i" | "b" | "u") => format!("{{\\{}{}}}", tag, state),
+                _ => String::new()
+            }
+        });
+
+        converted.replace("\r\n", "\n").replace('\n', "\\N")
+    }
+}
@@ -0,0 +1,349 @@
+pub mod batch {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use futures::stream::{self, StreamExt};
+    use serde::{Deserialize, Serialize};
+
+    use crate::{download_subtitles, find_best_subtitles, get_download_url_from_page};
+    use crate::config::config::{get_preference_rank, HearingImpairedPreference};
+    use crate::error::error::OperationError;
+    use crate::format::format::SubtitleFormat;
+    use crate::language::language::{get_language_code, LanguageCodeStyle};
+    use crate::ranking::ranking::RankingOptions;
+    use crate::save::save::{save_subtitle, SaveOptions, SaveStatus, SubtitleTag};
+    use crate::transport::transport::HttpTransport;
+    use crate::types::types::OperationResult;
+
+    pub const VIDEO_EXTENSIONS: [&str; 12] = [
+        "mkv", "mp4", "m4v", "avi", "mov", "wmv", "mpg", "mpeg", "ts", "m2ts", "webm", "flv"
+    ];
+
+    pub const SUBTITLE_EXTENSIONS: [&str; 8] = ["srt", "ass", "ssa", "vtt", "smi", "sub", "ttml", "dfxp"];
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct BatchOptions {
+        /// Wanted subtitle languages: `eng`, `ru`, ...
+        pub languages: Vec<String>,
+
+        /// Find best candidates only, don't download
+        pub dry_run: bool,
+
+        /// Max videos processed at the same time
+        pub concurrency: usize,
+
+        pub ranking: RankingOptions,
+
+        /// Profile preference, applied over ranking
+        pub hearing_impaired: HearingImpairedPreference,
+
+        /// Preferred formats, best first, applied over ranking
+        pub formats: Vec<SubtitleFormat>,
+
+        pub save: SaveOptions
+    }
+
+    impl Default for BatchOptions {
+        fn default() -> Self {
+            BatchOptions {
+                languages: vec![String::from("eng")],
+                dry_run: false,
+                concurrency: 2,
+                ranking: RankingOptions::default(),
+                hearing_impaired: HearingImpairedPreference::Any,
+                formats: Vec::new(),
+                save: SaveOptions::default()
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub enum BatchStatus {
+        Downloaded { path: PathBuf },
+
+        /// Subtitle file appeared meanwhile, kept as is (`CollisionPolicy::Keep`)
+        Skipped { path: PathBuf },
+
+        /// Dry run: subtitles that would be downloaded
+        WouldDownload { details_url: String, score: f32 },
+
+        NotFound,
+
+        Failed { error: String }
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct BatchItem {
+        pub video: PathBuf,
+        pub language: String,
+        pub status: BatchStatus
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+    pub struct BatchSummary {
+        pub videos: usize,
+
+        /// Videos with subtitles in all wanted languages
+        pub skipped: usize,
+
+        pub downloaded: usize,
+
+        /// Subtitle files appeared meanwhile and kept
+        pub kept: usize,
+
+        pub would_download: usize,
+        pub not_found: usize,
+        pub failed: usize
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct BatchReport {
+        pub summary: BatchSummary,
+        pub items: Vec<BatchItem>
+    }
+
+    /// Video files (by extension) in directory tree, sorted
+    pub fn find_video_files(directory: &Path) -> OperationResult<Vec<PathBuf>> {
+        let mut results: Vec<PathBuf> = Vec::new();
+
+        collect_video_files(directory, &mut results)?;
+        results.sort();
+
+        Ok(results)
+    }
+
+    /**
+    Wanted languages without subtitles next to the video.
+
+    Subtitles are files like `<video-stem>.<lang>[.tags][.number].<ext>`, language as ISO 639-1 or ISO 639-2 code.
+    */
+    pub fn get_missing_languages(video_path: &Path, languages: &[String]) -> OperationResult<Vec<String>> {
+        let stem = video_path.file_stem().map(|stem| stem.to_string_lossy().to_string()).ok_or_else(|| {
+            OperationError::InvalidArgument(format!("invalid video path '{}'", video_path.display()))
+        })?;
+
+        let directory = video_path.parent().filter(|parent| !parent.as_os_str().is_empty())
+                                  .unwrap_or_else(|| Path::new("."));
+
+        let prefix = format!("{}.", stem);
+
+        let mut existing_languages: Vec<&str> = Vec::new();
+
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+
+            if !has_extension(&path, &SUBTITLE_EXTENSIONS) {
+                continue;
+            }
+
+            let filename = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+
+            let language = filename.strip_prefix(&prefix).and_then(get_subtitle_language);
+
+            if let Some(language) = language {
+                existing_languages.push(language);
+            }
+        }
+
+        Ok(languages.iter()
+                    .filter(|language| match get_language_code(language, LanguageCodeStyle::Iso639_2) {
+                        Some(code) => !existing_languages.contains(&code),
+                        None => true
+                    })
+                    .cloned()
+                    .collect())
+    }
+
+    /**
+    Walk directory tree and fetch missing subtitles for every video file.
+
+    Each video/language pair goes through `find_best_subtitles`, the best candidate is
+    downloaded and saved next to the video. Errors are reported per item, they don't stop the batch.
+    */
+    pub async fn fetch_missing_subtitles(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                                         directory: &Path, options: &BatchOptions) -> OperationResult<BatchReport> {
+        info!("fetch missing subtitles in '{}'", directory.display());
+        info!("- languages: {:?}, dry run: {}", options.languages, options.dry_run);
+
+        let videos = find_video_files(directory)?;
+        info!("video files found: {}", videos.len());
+
+        let mut summary = BatchSummary { videos: videos.len(), ..BatchSummary::default() };
+        let mut tasks: Vec<(PathBuf, String)> = Vec::new();
+        let mut items: Vec<BatchItem> = Vec::new();
+
+        for video in &videos {
+            let missing_languages = match get_missing_languages(video, &options.languages) {
+                Ok(missing_languages) => missing_languages,
+                Err(e) => {
+                    error!("unable to check subtitles of video '{}': {}", video.display(), e);
+
+                    items.extend(options.languages.iter().map(|language| BatchItem {
+                        video: video.clone(),
+                        language: language.clone(),
+                        status: BatchStatus::Failed { error: e.to_string() }
+                    }));
+
+                    continue;
+                }
+            };
+
+            if missing_languages.is_empty() {
+                debug!("video '{}' has all subtitles", video.display());
+                summary.skipped += 1;
+            }
+
+            tasks.extend(missing_languages.into_iter().map(|language| (video.clone(), language)));
+        }
+
+        let fetched_items: Vec<BatchItem> = stream::iter(tasks)
+            .map(|(video, language)| async move {
+                let status = match fetch_video_subtitles(client, base_url, locale, &video, &language, options).await {
+                    Ok(status) => status,
+                    Err(e) => {
+                        error!("unable to process video '{}': {}", video.display(), e);
+                        BatchStatus::Failed { error: e.to_string() }
+                    }
+                };
+
+                BatchItem { video, language, status }
+            })
+            .buffer_unordered(options.concurrency.max(1))
+            .collect()
+            .await;
+
+        items.extend(fetched_items);
+
+        items.sort_by(|first, second| (&first.video, &first.language).cmp(&(&second.video, &second.language)));
+
+        for item in &items {
+            match item.status {
+                BatchStatus::Downloaded { .. } => summary.downloaded += 1,
+                BatchStatus::Skipped { .. } => summary.kept += 1,
+                BatchStatus::WouldDownload { .. } => summary.would_download += 1,
+                BatchStatus::NotFound => summary.not_found += 1,
+                BatchStatus::Failed { .. } => summary.failed += 1
+            }
+        }
+
+        info!("batch summary: {:?}", summary);
+
+        Ok(BatchReport { summary, items })
+    }
+
+    /// Find, download and save the best subtitles for one video and language
+    #[instrument(name = "batch_video", skip_all, fields(video = %video.display(), language = %language))]
+    pub async fn fetch_video_subtitles(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                                       video: &Path, language: &str, options: &BatchOptions) -> OperationResult<BatchStatus> {
+        let site_language = get_language_code(language, LanguageCodeStyle::Iso639_2).ok_or_else(|| {
+            OperationError::InvalidArgument(format!("unsupported language '{}'", language))
+        })?;
+
+        // whole ranking, preferences may exclude shortlisted candidates
+        let ranking = RankingOptions { max_results: usize::MAX, ..options.ranking.clone() };
+
+        let ranked = find_best_subtitles(client, base_url, locale, video, site_language, &ranking).await?;
+
+        // stable: score order is kept within the same preference rank
+        let best = ranked.iter()
+                         .filter_map(|ranked| {
+                             get_preference_rank(&ranked.item, options.hearing_impaired, &options.formats)
+                                 .map(|rank| (rank, ranked))
+                         })
+                         .min_by_key(|(rank, _)| *rank)
+                         .map(|(_, ranked)| ranked);
+
+        let best = match best {
+            Some(best) => best,
+            None => return Ok(BatchStatus::NotFound)
+        };
+
+        if options.dry_run {
+            return Ok(BatchStatus::WouldDownload {
+                details_url: best.item.details_url.clone(),
+                score: best.score
+            });
+        }
+
+        let download_url = match get_download_url_from_page(client, &best.item.details_url).await? {
+            Some(url) => url,
+            None => return Ok(BatchStatus::NotFound)
+        };
+
+        let files = download_subtitles(client, base_url, &download_url).await?;
+
+        let file = files.iter().find(|file| file.format.format != SubtitleFormat::Unknown)
+                        .or_else(|| files.first());
+
+        match file {
+            Some(file) => {
+                let result = save_subtitle(video, file, language, &options.save)?;
+
+                if result.status == SaveStatus::Skipped {
+                    warn!("subtitle file '{}' appeared meanwhile, kept", result.path.display());
+                    return Ok(BatchStatus::Skipped { path: result.path });
+                }
+
+                Ok(BatchStatus::Downloaded { path: result.path })
+            }
+            None => Ok(BatchStatus::NotFound)
+        }
+    }
+
+    /// Symlinked directories are not followed (loops), unreadable subdirectories are skipped
+    fn collect_video_files(directory: &Path, results: &mut Vec<PathBuf>) -> OperationResult<()> {
+        for entry in fs::read_dir(directory)? {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    error!("unable to read entry in '{}': {}", directory.display(), e);
+                    continue;
+                }
+            };
+
+            let path = entry.path();
+            let is_dir = entry.file_type().map(|file_type| file_type.is_dir()).unwrap_or(false);
+
+            if is_dir {
+                if let Err(e) = collect_video_files(&path, results) {
+                    error!("unable to read directory '{}': {}", path.display(), e);
+                }
+
+            } else if has_extension(&path, &VIDEO_EXTENSIONS) {
+                results.push(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+    Language code of subtitle file name suffix `<lang>[.tags][.number].<ext>`.
+
+    `None` for other suffixes, e.g. `part2.it.srt` of another video with the same stem prefix.
+    Language goes first, so `hi` after it is the hearing-impaired tag, not Hindi.
+    */
+    fn get_subtitle_language(suffix: &str) -> Option<&'static str> {
+        let mut parts: Vec<&str> = suffix.split('.').collect();
+
+        // extension
+        parts.pop()?;
+
+        let (language, rest) = parts.split_first()?;
+
+        let tags_only = rest.iter().all(|part| {
+            SubtitleTag::from_name(part).is_some() || part.parse::<u32>().is_ok()
+        });
+
+        match tags_only {
+            true => get_language_code(language, LanguageCodeStyle::Iso639_2),
+            false => None
+        }
+    }
+
+    fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+        path.extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .is_some_and(|extension| extensions.contains(&extension.as_str()))
+    }
+}
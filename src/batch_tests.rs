@@ -0,0 +1,226 @@
+#[cfg(test)]
+mod batch_tests {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use crate::BASE_URL;
+    use crate::batch::batch::{BatchOptions, BatchStatus, fetch_missing_subtitles, fetch_video_subtitles,
+                              find_video_files, get_missing_languages};
+    use crate::mock_server::mock_server::{FIXTURES_DIRECTORY, MockResponse, MockServer};
+
+    const EPISODE_VIDEO: &str = "The.Midnight.Gospel.S01E01.mkv";
+    const EPISODE_SUBTITLE: &str = "The.Midnight.Gospel.S01E01.ru.srt";
+
+    const SUBTITLE_CONTENT: &[u8] = b"1\n00:00:01,000 --> 00:00:02,000\nHello\n";
+
+    #[test]
+    fn video_files_should_be_found_recursively() {
+        let directory = TempDir::new().unwrap();
+        let season_directory = directory.path().join("Show").join("Season 01");
+        fs::create_dir_all(&season_directory).unwrap();
+
+        fs::write(directory.path().join("movie.MKV"), "").unwrap();
+        fs::write(directory.path().join("movie.en.srt"), "").unwrap();
+        fs::write(season_directory.join("show.s01e02.mp4"), "").unwrap();
+        fs::write(season_directory.join("show.s01e01.avi"), "").unwrap();
+        fs::write(season_directory.join("notes.txt"), "").unwrap();
+
+        let videos = find_video_files(directory.path()).unwrap();
+
+        assert_eq!(vec![
+            directory.path().join("Show/Season 01/show.s01e01.avi"),
+            directory.path().join("Show/Season 01/show.s01e02.mp4"),
+            directory.path().join("movie.MKV")
+        ], videos);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_directories_should_not_be_followed() {
+        let directory = TempDir::new().unwrap();
+        let show_directory = directory.path().join("Show");
+        fs::create_dir(&show_directory).unwrap();
+        fs::write(show_directory.join("show.s01e01.mkv"), "").unwrap();
+
+        // loop: Show/again -> root
+        std::os::unix::fs::symlink(directory.path(), show_directory.join("again")).unwrap();
+
+        let videos = find_video_files(directory.path()).unwrap();
+
+        assert_eq!(vec![show_directory.join("show.s01e01.mkv")], videos);
+    }
+
+    #[test]
+    fn languages_with_subtitles_should_be_skipped() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("movie.mkv");
+
+        fs::write(&video_path, "").unwrap();
+        fs::write(directory.path().join("movie.en.srt"), "").unwrap();
+        fs::write(directory.path().join("movie.fre.forced.ass"), "").unwrap();
+        fs::write(directory.path().join("movie.2.ru.txt"), "").unwrap();
+        fs::write(directory.path().join("other.de.srt"), "").unwrap();
+
+        let languages: Vec<String> = ["eng", "fr", "rus", "ger"].iter().map(|language| language.to_string()).collect();
+
+        let missing = get_missing_languages(&video_path, &languages).unwrap();
+
+        assert_eq!(vec![String::from("rus"), String::from("ger")], missing);
+    }
+
+    #[test]
+    fn hearing_impaired_tag_should_not_be_read_as_language() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("movie.mkv");
+
+        fs::write(&video_path, "").unwrap();
+        fs::write(directory.path().join("movie.en.hi.srt"), "").unwrap();
+
+        let languages = vec![String::from("hin"), String::from("eng")];
+
+        let missing = get_missing_languages(&video_path, &languages).unwrap();
+
+        assert_eq!(vec![String::from("hin")], missing);
+    }
+
+    #[test]
+    fn subtitles_of_other_videos_with_same_prefix_should_be_ignored() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("show.s01e01.mkv");
+
+        fs::write(&video_path, "").unwrap();
+        fs::write(directory.path().join("show.s01e01.part2.mkv"), "").unwrap();
+        fs::write(directory.path().join("show.s01e01.part2.it.srt"), "").unwrap();
+        fs::write(directory.path().join("show.s01e01.de.forced.1.srt"), "").unwrap();
+
+        let languages = vec![String::from("ita"), String::from("ger")];
+
+        let missing = get_missing_languages(&video_path, &languages).unwrap();
+
+        assert_eq!(vec![String::from("ita")], missing);
+    }
+
+    #[tokio::test]
+    async fn missing_subtitles_should_be_downloaded_next_to_videos() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join(EPISODE_VIDEO);
+
+        fs::write(&video_path, "sample").unwrap();
+        fs::write(directory.path().join("movie.mkv"), "sample").unwrap();
+        fs::write(directory.path().join("movie.ru.srt"), "").unwrap();
+
+        let server = get_server();
+        let options = BatchOptions { languages: vec![String::from("rus")], ..BatchOptions::default() };
+
+        let report = fetch_missing_subtitles(&reqwest::Client::new(), &server.url(), "en", directory.path(), &options)
+            .await.unwrap();
+
+        let subtitle_path = directory.path().join(EPISODE_SUBTITLE);
+
+        assert_eq!(2, report.summary.videos);
+        assert_eq!(1, report.summary.skipped);
+        assert_eq!(1, report.summary.downloaded);
+        assert_eq!(0, report.summary.failed);
+
+        assert_eq!(1, report.items.len());
+        assert_eq!(video_path, report.items[0].video);
+        assert_eq!(BatchStatus::Downloaded { path: subtitle_path.clone() }, report.items[0].status);
+        assert_eq!(SUBTITLE_CONTENT, fs::read(&subtitle_path).unwrap().as_slice());
+    }
+
+    #[tokio::test]
+    async fn dry_run_should_not_download() {
+        let directory = TempDir::new().unwrap();
+        fs::write(directory.path().join(EPISODE_VIDEO), "sample").unwrap();
+
+        let server = get_server();
+        let options = BatchOptions { languages: vec![String::from("rus")], dry_run: true, ..BatchOptions::default() };
+
+        let report = fetch_missing_subtitles(&reqwest::Client::new(), &server.url(), "en", directory.path(), &options)
+            .await.unwrap();
+
+        assert_eq!(1, report.summary.would_download);
+        assert_eq!(0, report.summary.downloaded);
+
+        match &report.items[0].status {
+            BatchStatus::WouldDownload { details_url, .. } =>
+                assert!(details_url.ends_with("/season-1/episode-1/SearchOnlyTVSeries-on")),
+            status => panic!("would download status expected, got {:?}", status)
+        }
+
+        assert!(!directory.path().join(EPISODE_SUBTITLE).exists());
+        assert!(server.requests().iter().all(|request| !request.path.contains("subtitleserve")));
+    }
+
+    #[tokio::test]
+    async fn failed_language_should_not_stop_batch() {
+        let directory = TempDir::new().unwrap();
+        fs::write(directory.path().join(EPISODE_VIDEO), "sample").unwrap();
+
+        let server = get_server();
+        let options = BatchOptions {
+            languages: vec![String::from("rus"), String::from("xx")],
+            ..BatchOptions::default()
+        };
+
+        let report = fetch_missing_subtitles(&reqwest::Client::new(), &server.url(), "en", directory.path(), &options)
+            .await.unwrap();
+
+        assert_eq!(1, report.summary.downloaded);
+        assert_eq!(1, report.summary.failed);
+
+        assert_eq!("rus", report.items[0].language);
+        assert!(matches!(report.items[0].status, BatchStatus::Downloaded { .. }));
+
+        assert_eq!("xx", report.items[1].language);
+        assert!(matches!(report.items[1].status, BatchStatus::Failed { .. }));
+    }
+
+    #[tokio::test]
+    async fn existing_subtitle_file_should_be_kept() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join(EPISODE_VIDEO);
+        let subtitle_path = directory.path().join(EPISODE_SUBTITLE);
+
+        fs::write(&video_path, "sample").unwrap();
+        fs::write(&subtitle_path, "existing").unwrap();
+
+        let server = get_server();
+
+        let status = fetch_video_subtitles(&reqwest::Client::new(), &server.url(), "en", &video_path, "rus",
+                                           &BatchOptions::default()).await.unwrap();
+
+        assert_eq!(BatchStatus::Skipped { path: subtitle_path.clone() }, status);
+        assert_eq!("existing", fs::read_to_string(&subtitle_path).unwrap());
+    }
+
+    #[tokio::test]
+    async fn video_without_search_results_should_be_not_found() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join(EPISODE_VIDEO);
+        fs::write(&video_path, "sample").unwrap();
+
+        let server = MockServer::start();
+        server.mock(r"^/en/search/", MockResponse::html("<html><body>No results</body></html>"));
+
+        let status = fetch_video_subtitles(&reqwest::Client::new(), &server.url(), "en", &video_path, "rus",
+                                           &BatchOptions::default()).await.unwrap();
+
+        assert_eq!(BatchStatus::NotFound, status);
+    }
+
+    /// Episode search returns the episode page, its download link (site url replaced) serves srt file
+    fn get_server() -> MockServer {
+        let server = MockServer::start();
+
+        let episode_page = fs::read_to_string(Path::new(FIXTURES_DIRECTORY).join("episode-page.html")).unwrap()
+                              .replace(BASE_URL, &server.url());
+
+        server.mock(r"/season-\d+/episode-\d+/SearchOnlyTVSeries-on$", MockResponse::html(&episode_page))
+              .mock(r"^/en/subtitleserve/sub/8179335$", MockResponse::bytes(SUBTITLE_CONTENT, "text/plain"));
+
+        server
+    }
+}
@@ -0,0 +1,657 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+use opensubs_rs::batch::batch::{BatchOptions, BatchStatus, fetch_missing_subtitles};
+use opensubs_rs::client::client::Client;
+use opensubs_rs::config::config::Config;
+use opensubs_rs::coverage::coverage::{find_local_episodes, get_coverage_report, get_library_coverage,
+                                      get_table_headers, get_table_rows, to_csv};
+use opensubs_rs::domain::domain::{SubtitleSearchResultItem, SubtitleSearchResults};
+use opensubs_rs::error::error::OperationError;
+use opensubs_rs::hash::hash::get_movie_hash;
+use opensubs_rs::language::language::LanguageCodeStyle;
+use opensubs_rs::types::types::OperationResult;
+#[cfg(target_os = "linux")]
+use opensubs_rs::watch::watch::{watch, WatchOptions};
+
+/// Exit code for errors, clap uses 2 for invalid arguments
+const EXIT_ERROR: i32 = 1;
+
+/// Exit code when nothing was found
+const EXIT_NO_RESULTS: i32 = 3;
+
+#[cfg(target_os = "linux")]
+const QUEUE_FILENAME: &str = "opensubs-queue.json";
+
+#[derive(Parser)]
+#[command(name = "opensubs", version, about = "Search and download subtitles from opensubtitles.org")]
+struct Cli {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table, global = true)]
+    output: OutputFormat,
+
+    /// Site url [default: from config or https://www.opensubtitles.org]
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+
+    /// Site UI locale, e.g. ru for localized titles [default: from config or en]
+    #[arg(long, global = true)]
+    locale: Option<String>,
+
+    /// Config file [default: $OPENSUBS_CONFIG or $XDG_CONFIG_HOME/opensubs/config.toml]
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Config profile
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    #[command(subcommand)]
+    command: Command
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Json,
+
+    /// Table cells as CSV
+    Csv
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Search subtitles by movie or series name
+    Search(SearchArgs),
+
+    /// Show subtitle details and download url
+    Details {
+        /// Subtitle details page url
+        url: String
+    },
+
+    /// Download subtitle files (archives are unpacked)
+    Download(DownloadArgs),
+
+    /// Print movie hash and size of a video file
+    Hash {
+        file: PathBuf
+    },
+
+    /// Scan media directory and fetch missing subtitles
+    Batch(BatchArgs),
+
+    /// Subtitle coverage per episode: present locally, available on the site or missing
+    Coverage(CoverageArgs),
+
+    /// Watch directories for new video files and fetch their subtitles
+    #[cfg(target_os = "linux")]
+    Watch(WatchArgs)
+}
+
+#[derive(Args)]
+struct SearchArgs {
+    /// Search mask: movie or series name
+    mask: String,
+
+    #[arg(short, long)]
+    season: Option<u8>,
+
+    #[arg(short, long, requires = "season")]
+    episode: Option<u16>,
+
+    /// Subtitle languages, comma separated: `rus,eng` [default: from config profile]
+    #[arg(short, long, value_delimiter = ',')]
+    langs: Option<Vec<String>>,
+
+    #[arg(long)]
+    min_downloads: Option<u32>,
+
+    #[arg(long)]
+    min_rating: Option<f32>,
+
+    /// Only subtitles made for this frame rate
+    #[arg(long)]
+    fps: Option<f32>,
+
+    /// Only subtitles for hearing impaired
+    #[arg(long, conflicts_with = "no_hi")]
+    hi: bool,
+
+    /// Exclude subtitles for hearing impaired
+    #[arg(long)]
+    no_hi: bool,
+
+    /// Max results to show
+    #[arg(long)]
+    limit: Option<usize>
+}
+
+#[derive(Args)]
+struct DownloadArgs {
+    /// Subtitle id, details page url or download url
+    target: String,
+
+    /// Directory for subtitle files
+    #[arg(short, long, default_value = ".")]
+    output_dir: PathBuf,
+
+    /// Replace existing files
+    #[arg(long)]
+    force: bool
+}
+
+#[derive(Args)]
+struct BatchArgs {
+    /// Media directory
+    directory: PathBuf,
+
+    /// Wanted subtitle languages, comma separated: `rus,eng` [default: from config profile]
+    #[arg(short, long, value_delimiter = ',')]
+    langs: Option<Vec<String>>,
+
+    /// Show what would be downloaded
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Max videos processed at the same time
+    #[arg(long, default_value_t = 2)]
+    concurrency: usize,
+
+    /// Use three letter language codes in file names (`movie.eng.srt`) [default: from config profile]
+    #[arg(long)]
+    iso639_2: bool
+}
+
+#[derive(Args)]
+struct CoverageArgs {
+    /// Series name [default: every series in --directory]
+    mask: Option<String>,
+
+    /// Series IMDb id instead of name
+    #[arg(long, conflicts_with = "mask")]
+    imdb_id: Option<String>,
+
+    /// Media directory with local episodes
+    #[arg(short, long)]
+    directory: Option<PathBuf>,
+
+    /// Wanted subtitle languages, comma separated: `rus,eng` [default: from config profile]
+    #[arg(short, long, value_delimiter = ',')]
+    langs: Option<Vec<String>>
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Args)]
+struct WatchArgs {
+    /// Media directories
+    #[arg(required = true)]
+    directories: Vec<PathBuf>,
+
+    /// Wanted subtitle languages, comma separated: `rus,eng` [default: from config profile]
+    #[arg(short, long, value_delimiter = ',')]
+    langs: Option<Vec<String>>,
+
+    /// Queue state file [default: in config cache_dir or current directory]
+    #[arg(long)]
+    queue_file: Option<PathBuf>,
+
+    /// Use three letter language codes in file names (`movie.eng.srt`) [default: from config profile]
+    #[arg(long)]
+    iso639_2: bool
+}
+
+#[derive(Serialize)]
+struct Details {
+    item: SubtitleSearchResultItem,
+    download_url: Option<String>
+}
+
+#[derive(Serialize)]
+struct SavedFile {
+    filename: String,
+    path: PathBuf,
+    format: String
+}
+
+#[derive(Serialize)]
+struct HashInfo {
+    file: PathBuf,
+    hash: String,
+    size: u64
+}
+
+enum Outcome {
+    Done,
+    NoResults
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    let exit_code = match run(&cli).await {
+        Ok(Outcome::Done) => 0,
+        Ok(Outcome::NoResults) => {
+            eprintln!("no results");
+            EXIT_NO_RESULTS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            EXIT_ERROR
+        }
+    };
+
+    process::exit(exit_code);
+}
+
+async fn run(cli: &Cli) -> OperationResult<Outcome> {
+    let config = match &cli.config {
+        Some(path) => Config::load(path)?,
+        None => Config::load_default()?
+    };
+
+    let mut builder = Client::builder().config(config.clone());
+
+    if let Some(profile) = &cli.profile {
+        builder = builder.profile(profile);
+    }
+
+    if let Some(base_url) = &cli.base_url {
+        builder = builder.base_url(base_url);
+    }
+
+    if let Some(locale) = &cli.locale {
+        builder = builder.locale(locale);
+    }
+
+    let languages = match &cli.command {
+        Command::Search(args) => args.langs.as_ref(),
+        Command::Batch(args) => args.langs.as_ref(),
+        Command::Coverage(args) => args.langs.as_ref(),
+        #[cfg(target_os = "linux")]
+        Command::Watch(args) => args.langs.as_ref(),
+        _ => None
+    };
+
+    if let Some(languages) = languages {
+        let languages: Vec<&str> = languages.iter().map(|language| language.as_str()).collect();
+        builder = builder.languages(&languages);
+    }
+
+    let client = builder.build()?;
+
+    match &cli.command {
+        Command::Search(args) => search(cli, &client, args).await,
+        Command::Details { url } => details(cli, &client, url).await,
+        Command::Download(args) => download(cli, &client, args).await,
+        Command::Batch(args) => batch(cli, &client, args).await,
+        Command::Coverage(args) => coverage(cli, &client, args).await,
+        #[cfg(target_os = "linux")]
+        Command::Watch(args) => {
+            let queue_path = args.queue_file.clone().unwrap_or_else(|| match &config.cache_dir {
+                Some(cache_dir) => cache_dir.join(QUEUE_FILENAME),
+                None => PathBuf::from(QUEUE_FILENAME)
+            });
+
+            let mut options = WatchOptions {
+                directories: args.directories.clone(),
+                queue_path,
+                ..WatchOptions::default()
+            };
+
+            options.batch = get_batch_options(&client, args.iso639_2);
+
+            watch(client.transport(), client.base_url(), client.locale(), &options).await?;
+
+            Ok(Outcome::Done)
+        }
+        Command::Hash { file } => {
+            let movie_hash = get_movie_hash(file)?;
+
+            let info = HashInfo { file: file.clone(), hash: movie_hash.hash, size: movie_hash.size };
+
+            match cli.output {
+                OutputFormat::Json => print_json(&info),
+                OutputFormat::Table => println!("{}  {}  {}", info.hash, info.size, info.file.display()),
+                OutputFormat::Csv => print_csv(&["HASH", "SIZE", "FILE"], vec![
+                    vec![info.hash.clone(), info.size.to_string(), info.file.display().to_string()]
+                ])
+            }
+
+            Ok(Outcome::Done)
+        }
+    }
+}
+
+async fn search(cli: &Cli, client: &Client, args: &SearchArgs) -> OperationResult<Outcome> {
+    let results = match (args.season, args.episode) {
+        (Some(season), Some(episode)) => client.search_serial_episode(&args.mask, season, episode).await,
+        (Some(season), None) => client.search_serial_season(&args.mask, season).await,
+        _ => client.search_by_mask(&args.mask).await
+    };
+
+    // Page without results table
+    let results = match results {
+        Err(OperationError::HtmlParseError) => return Ok(Outcome::NoResults),
+        results => results?
+    };
+
+    let mut results: SubtitleSearchResults = results.into_iter().filter(|item| is_matching(item, args)).collect();
+
+    if let Some(limit) = args.limit {
+        results.truncate(limit);
+    }
+
+    if results.is_empty() {
+        return Ok(Outcome::NoResults);
+    }
+
+    match cli.output {
+        OutputFormat::Json => print_json(&results),
+        output => print_rows(
+            output, &["#", "TITLE", "S/E", "DOWNLOADS", "RATING", "FPS", "HI", "RELEASE", "URL"],
+            results.iter().map(get_item_row).collect()
+        )
+    }
+
+    Ok(Outcome::Done)
+}
+
+async fn details(cli: &Cli, client: &Client, url: &str) -> OperationResult<Outcome> {
+    let item = client.get_subtitle_details(url).await?;
+    let download_url = client.get_download_url_from_page(url).await?;
+
+    let details = Details { item, download_url };
+
+    match cli.output {
+        OutputFormat::Json => print_json(&details),
+        output => {
+            let item = &details.item;
+
+            print_rows(output, &["FIELD", "VALUE"], vec![
+                vec![String::from("title"), item.title.clone()],
+                vec![String::from("season/episode"), get_season_episode(item)],
+                vec![String::from("release"), item.release_name.clone().unwrap_or_default()],
+                vec![String::from("hearing impaired"), item.hearing_impaired.to_string()],
+                vec![String::from("imdb"), item.imdb_id.clone().unwrap_or_default()],
+                vec![String::from("download url"), details.download_url.clone().unwrap_or_default()]
+            ]);
+        }
+    }
+
+    Ok(Outcome::Done)
+}
+
+async fn download(cli: &Cli, client: &Client, args: &DownloadArgs) -> OperationResult<Outcome> {
+    let download_url = if args.target.chars().all(|character| character.is_ascii_digit()) {
+        format!("/{}/subtitleserve/sub/{}", client.locale(), args.target)
+
+    } else if args.target.contains("/subtitles/") {
+        match client.get_download_url_from_page(&args.target).await? {
+            Some(url) => url,
+            None => return Ok(Outcome::NoResults)
+        }
+
+    } else {
+        args.target.clone()
+    };
+
+    let files = client.download_subtitles(&download_url).await?;
+
+    if files.is_empty() {
+        return Ok(Outcome::NoResults);
+    }
+
+    let mut saved_files: Vec<SavedFile> = Vec::new();
+
+    for file in &files {
+        // Archive entry names are not trusted, only file name is written into output directory
+        let filename = match Path::new(&file.filename).file_name() {
+            Some(filename) => filename,
+            None => {
+                eprintln!("skip unsafe file name '{}'", file.filename);
+                continue;
+            }
+        };
+
+        let path = args.output_dir.join(filename);
+
+        let mut output = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .create_new(!args.force)
+            .open(&path)?;
+
+        output.write_all(&file.content)?;
+
+        saved_files.push(SavedFile {
+            filename: file.filename.clone(),
+            path,
+            format: format!("{:?}", file.format.format)
+        });
+    }
+
+    match cli.output {
+        OutputFormat::Json => print_json(&saved_files),
+        output => print_rows(
+            output, &["FILE", "FORMAT"],
+            saved_files.iter().map(|file| vec![file.path.display().to_string(), file.format.clone()]).collect()
+        )
+    }
+
+    Ok(Outcome::Done)
+}
+
+async fn batch(cli: &Cli, client: &Client, args: &BatchArgs) -> OperationResult<Outcome> {
+    let mut options = get_batch_options(client, args.iso639_2);
+    options.dry_run = args.dry_run;
+    options.concurrency = args.concurrency;
+
+    let report = fetch_missing_subtitles(client.transport(), client.base_url(), client.locale(), &args.directory, &options).await?;
+
+    match cli.output {
+        OutputFormat::Json => print_json(&report),
+        output => {
+            print_rows(
+                output, &["VIDEO", "LANG", "STATUS"],
+                report.items.iter().map(|item| vec![
+                    item.video.display().to_string(),
+                    item.language.clone(),
+                    get_batch_status(&item.status)
+                ]).collect()
+            );
+
+            let summary = &report.summary;
+
+            if output == OutputFormat::Table {
+                println!();
+                println!(
+                    "videos: {}, skipped: {}, downloaded: {}, kept: {}, would download: {}, not found: {}, failed: {}",
+                    summary.videos, summary.skipped, summary.downloaded, summary.kept,
+                    summary.would_download, summary.not_found, summary.failed
+                );
+            }
+        }
+    }
+
+    if report.summary.failed > 0 {
+        Err(OperationError::Error)
+
+    } else {
+        Ok(Outcome::Done)
+    }
+}
+
+async fn coverage(cli: &Cli, client: &Client, args: &CoverageArgs) -> OperationResult<Outcome> {
+    let languages = &client.settings().languages;
+
+    let reports = if args.mask.is_some() || args.imdb_id.is_some() {
+        let series = match (&args.mask, &args.imdb_id) {
+            (Some(mask), _) => match client.get_series_by_mask(mask).await? {
+                Some(series) => series,
+                None => return Ok(Outcome::NoResults)
+            },
+            (None, Some(imdb_id)) => client.get_series_by_imdb_id(imdb_id).await?,
+            _ => unreachable!()
+        };
+
+        let local_episodes = match &args.directory {
+            Some(directory) => find_local_episodes(directory, languages)?,
+            None => Vec::new()
+        };
+
+        vec![get_coverage_report(&series, languages, &local_episodes)?]
+
+    } else {
+        let directory = args.directory.as_ref().ok_or_else(|| {
+            OperationError::InvalidArgument(String::from("series name, --imdb-id or --directory expected"))
+        })?;
+
+        get_library_coverage(client.transport(), client.base_url(), client.locale(), directory, languages).await?
+    };
+
+    if reports.iter().all(|report| report.episodes.is_empty()) {
+        return Ok(Outcome::NoResults);
+    }
+
+    match cli.output {
+        OutputFormat::Json => print_json(&reports),
+        OutputFormat::Csv => print!("{}", to_csv(&reports)),
+        OutputFormat::Table => {
+            let headers = get_table_headers(&reports[0].languages);
+
+            print_table(
+                &headers.iter().map(|header| header.as_str()).collect::<Vec<&str>>(),
+                get_table_rows(&reports)
+            );
+
+            println!();
+
+            for report in &reports {
+                for (language, summary) in report.languages.iter().filter_map(|language| report.summary.get_key_value(language)) {
+                    println!(
+                        "{} {}: local: {}, available: {}, missing: {}",
+                        report.series, language, summary.local, summary.available, summary.missing
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(Outcome::Done)
+}
+
+/// Languages, preferences and naming from client settings
+fn get_batch_options(client: &Client, iso639_2: bool) -> BatchOptions {
+    let mut options = BatchOptions {
+        languages: client.settings().languages.clone(),
+        hearing_impaired: client.settings().hearing_impaired,
+        formats: client.settings().formats.clone(),
+        ..BatchOptions::default()
+    };
+
+    options.save.language_code_style = if iso639_2 {
+        LanguageCodeStyle::Iso639_2
+    } else {
+        client.settings().naming
+    };
+
+    options
+}
+
+fn get_batch_status(status: &BatchStatus) -> String {
+    match status {
+        BatchStatus::Downloaded { path } => format!("downloaded {}", path.display()),
+        BatchStatus::Skipped { path } => format!("kept existing {}", path.display()),
+        BatchStatus::WouldDownload { details_url, score } => format!("would download {} (score {:.1})", details_url, score),
+        BatchStatus::NotFound => String::from("not found"),
+        BatchStatus::Failed { error } => format!("failed: {}", error)
+    }
+}
+
+fn is_matching(item: &SubtitleSearchResultItem, args: &SearchArgs) -> bool {
+    args.min_downloads.is_none_or(|min_downloads| item.downloads >= min_downloads) &&
+    args.min_rating.is_none_or(|min_rating| item.rating >= min_rating) &&
+    args.fps.is_none_or(|fps| item.fps.is_some_and(|item_fps| (item_fps - fps).abs() < 0.01)) &&
+    (!args.hi || item.hearing_impaired) &&
+    (!args.no_hi || !item.hearing_impaired)
+}
+
+fn get_item_row(item: &SubtitleSearchResultItem) -> Vec<String> {
+    vec![
+        item.index.to_string(),
+        item.title.clone(),
+        get_season_episode(item),
+        item.downloads.to_string(),
+        format!("{:.1}", item.rating),
+        item.fps.map(|fps| format!("{:.3}", fps)).unwrap_or_default(),
+        if item.hearing_impaired { String::from("yes") } else { String::new() },
+        item.release_name.clone().unwrap_or_default(),
+        item.details_url.clone()
+    ]
+}
+
+fn get_season_episode(item: &SubtitleSearchResultItem) -> String {
+    if item.season > 0 {
+        format!("S{:02}E{:02}", item.season, item.episode)
+    } else {
+        String::new()
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    println!("{}", serde_json::to_string_pretty(value).expect("unable to serialize output"));
+}
+
+/// Table or CSV
+fn print_rows(output: OutputFormat, headers: &[&str], rows: Vec<Vec<String>>) {
+    if output == OutputFormat::Csv {
+        print_csv(headers, rows);
+    } else {
+        print_table(headers, rows);
+    }
+}
+
+fn print_csv(headers: &[&str], rows: Vec<Vec<String>>) {
+    let header_row: Vec<String> = headers.iter().map(|header| header.to_lowercase()).collect();
+
+    for row in std::iter::once(&header_row).chain(rows.iter()) {
+        let line = row.iter().map(|value| {
+            if value.contains(',') || value.contains('"') || value.contains('\n') {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            } else {
+                value.clone()
+            }
+        }).collect::<Vec<String>>().join(",");
+
+        println!("{}", line);
+    }
+}
+
+fn print_table(headers: &[&str], rows: Vec<Vec<String>>) {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.chars().count()).collect();
+
+    for row in &rows {
+        for (position, value) in row.iter().enumerate() {
+            widths[position] = widths[position].max(value.chars().count());
+        }
+    }
+
+    let header_row: Vec<String> = headers.iter().map(|header| header.to_string()).collect();
+
+    for row in std::iter::once(&header_row).chain(rows.iter()) {
+        let line = row.iter().zip(widths.iter())
+                      .map(|(value, width)| format!("{:<width$}", value, width = width))
+                      .collect::<Vec<String>>()
+                      .join("  ");
+
+        println!("{}", line.trim_end());
+    }
+}
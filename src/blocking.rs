@@ -0,0 +1,111 @@
+pub mod blocking {
+    use std::future::Future;
+    use std::path::Path;
+
+    use tokio::runtime::Runtime;
+
+    use crate::client::client::Client as AsyncClient;
+    use crate::config::config::Settings;
+    use crate::domain::domain::{Series, SubtitleFile, SubtitleSearchResultItem, SubtitleSearchResults};
+    use crate::hash::hash::MovieHash;
+    use crate::ranking::ranking::{RankedSubtitle, RankingOptions};
+    use crate::types::types::{OperationResult, OptionResult};
+    use crate::upload::upload::UploadRequest;
+
+    /**
+    Synchronous client, mirrors `client::Client` on an internal single-thread runtime.
+
+    Must not be used from inside an async runtime.
+
+    ```no_run
+    use opensubs_rs::blocking::blocking::Client;
+
+    let client = Client::new().unwrap();
+    let results = client.search_by_mask("tideland").unwrap();
+    ```
+    */
+    pub struct Client {
+        runtime: Runtime,
+        client: AsyncClient
+    }
+
+    impl Client {
+        /// Client with default config, see `Config::load_default` for configured one
+        pub fn new() -> OperationResult<Client> {
+            Client::from_client(AsyncClient::builder().build()?)
+        }
+
+        /// Blocking wrapper for client made with `client::Client::builder()`
+        pub fn from_client(client: AsyncClient) -> OperationResult<Client> {
+            let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+
+            Ok(Client { runtime, client })
+        }
+
+        pub fn inner(&self) -> &AsyncClient {
+            &self.client
+        }
+
+        pub fn base_url(&self) -> &str {
+            self.client.base_url()
+        }
+
+        pub fn settings(&self) -> &Settings {
+            self.client.settings()
+        }
+
+        pub fn search_by_mask(&self, mask: &str) -> OperationResult<SubtitleSearchResults> {
+            self.block_on(self.client.search_by_mask(mask))
+        }
+
+        pub fn search_serial_season(&self, mask: &str, season: u8) -> OperationResult<SubtitleSearchResults> {
+            self.block_on(self.client.search_serial_season(mask, season))
+        }
+
+        pub fn search_serial_episode(&self, mask: &str, season: u8,
+                                     episode: u16) -> OperationResult<SubtitleSearchResults> {
+            self.block_on(self.client.search_serial_episode(mask, season, episode))
+        }
+
+        pub fn search_by_hash(&self, movie_hash: &MovieHash) -> OperationResult<SubtitleSearchResults> {
+            self.block_on(self.client.search_by_hash(movie_hash))
+        }
+
+        pub fn search_by_imdb_id(&self, imdb_id: &str) -> OperationResult<SubtitleSearchResults> {
+            self.block_on(self.client.search_by_imdb_id(imdb_id))
+        }
+
+        pub fn get_subtitle_details(&self, page_url: &str) -> OperationResult<SubtitleSearchResultItem> {
+            self.block_on(self.client.get_subtitle_details(page_url))
+        }
+
+        pub fn get_download_url_from_page(&self, page_url: &str) -> OptionResult<String> {
+            self.block_on(self.client.get_download_url_from_page(page_url))
+        }
+
+        pub fn download_subtitles(&self, download_url: &str) -> OperationResult<Vec<SubtitleFile>> {
+            self.block_on(self.client.download_subtitles(download_url))
+        }
+
+        pub fn find_best_subtitles(&self, video_path: &Path,
+                                   options: &RankingOptions) -> OperationResult<Vec<RankedSubtitle>> {
+            self.block_on(self.client.find_best_subtitles(video_path, options))
+        }
+
+        pub fn get_series_by_mask(&self, mask: &str) -> OptionResult<Series> {
+            self.block_on(self.client.get_series_by_mask(mask))
+        }
+
+        pub fn get_series_by_imdb_id(&self, imdb_id: &str) -> OperationResult<Series> {
+            self.block_on(self.client.get_series_by_imdb_id(imdb_id))
+        }
+
+        pub fn upload_subtitles(&self, request: &UploadRequest) -> OperationResult<String> {
+            self.block_on(self.client.upload_subtitles(request))
+        }
+
+        fn block_on<R>(&self, future: impl Future<Output = R>) -> R {
+            self.runtime.block_on(future)
+        }
+    }
+}
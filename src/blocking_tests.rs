@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod blocking_tests {
+    use crate::blocking::blocking::Client;
+    use crate::client::client::Client as AsyncClient;
+    use crate::error::error::OperationError;
+    use crate::hash::hash::MovieHash;
+    use crate::mock_server::mock_server::{MockResponse, MockServer};
+
+    #[test]
+    fn search_should_return_results() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search/sublanguageid-rus/moviename-adventure\+time$",
+                    MockResponse::fixture("series-search-results.html"));
+
+        let client = get_client(&server);
+
+        let results = client.search_by_mask("adventure time").unwrap();
+
+        assert!(results.len() > 1);
+        assert_eq!("\"Adventure Time\" Bonnibel Bubblegum (2017)", results[0].title);
+    }
+
+    #[test]
+    fn episode_search_should_return_single_result() {
+        let server = MockServer::start();
+        server.mock(r"/season-1/episode-1/SearchOnlyTVSeries-on$", MockResponse::fixture("episode-page.html"));
+
+        let client = get_client(&server);
+
+        assert_eq!(1, client.search_serial_episode("Midnight Gospel", 1, 1).unwrap().len());
+    }
+
+    #[test]
+    fn download_url_should_be_parsed() {
+        let server = MockServer::start();
+        server.mock(r"^/en/subtitles/7863206/", MockResponse::fixture("season-page.html"));
+
+        let client = get_client(&server);
+
+        let url = format!("{}/en/subtitles/7863206/adventure-time-bonnibel-bubblegum-ru", server.url());
+
+        assert_eq!(Some(String::from("/en/subtitleserve/sub/7863206")),
+                   client.get_download_url_from_page(&url).unwrap());
+    }
+
+    #[test]
+    fn hash_and_imdb_searches_should_return_results() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search/sublanguageid-rus/moviebytesize-12345/moviehash-8e245d9679d31e12$",
+                    MockResponse::fixture("episode-page.html"))
+              .mock(r"^/en/search/sublanguageid-rus/imdbid-7342228$", MockResponse::fixture("episode-page.html"));
+
+        let client = get_client(&server);
+        let movie_hash = MovieHash { hash: String::from("8e245d9679d31e12"), size: 12345 };
+
+        assert_eq!(1, client.search_by_hash(&movie_hash).unwrap().len());
+        assert_eq!(1, client.search_by_imdb_id("tt7342228").unwrap().len());
+    }
+
+    #[test]
+    fn subtitle_details_should_be_parsed() {
+        let server = MockServer::start();
+        server.mock(r"^/en/subtitles/8179335/", MockResponse::fixture("episode-page.html"));
+
+        let client = get_client(&server);
+
+        let url = format!("{}/en/subtitles/8179335/the-midnight-gospel-taste-of-the-king-ru", server.url());
+        let item = client.get_subtitle_details(&url).unwrap();
+
+        assert_eq!(url, item.details_url);
+    }
+
+    #[test]
+    fn errors_should_be_the_same_as_async_ones() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search/", MockResponse::captcha());
+
+        let client = get_client(&server);
+
+        match client.search_by_mask("adventure time") {
+            Err(OperationError::HtmlParseError) => {}
+            _ => panic!("parse error expected")
+        }
+    }
+
+    fn get_client(server: &MockServer) -> Client {
+        let client = AsyncClient::builder().base_url(&server.url()).languages(&["rus"]).build().unwrap();
+        Client::from_client(client).unwrap()
+    }
+}
@@ -0,0 +1,320 @@
+pub mod cassette {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::error::error::OperationError;
+    use crate::mock_server::mock_server::{MockRequest, MockResponse, MockServer};
+    use crate::types::types::OperationResult;
+
+    /// Cassette index file inside cassette directory
+    pub const CASSETTE_FILENAME: &str = "cassette.json";
+
+    /// Headers not stored: set by server on replay or meaningless for a saved body
+    const SKIPPED_HEADERS: [&str; 4] = ["content-length", "transfer-encoding", "connection", "content-encoding"];
+
+    /// Request headers not forwarded to upstream
+    const SKIPPED_REQUEST_HEADERS: [&str; 3] = ["host", "connection", "content-length"];
+
+    /// Recorded request with response, body is kept in a separate file
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct CassetteEntry {
+        pub method: String,
+
+        /// Path with query: `/en/search/sublanguageid-rus/moviename-tideland`
+        pub path: String,
+
+        pub status: u16,
+        pub headers: Vec<(String, String)>,
+
+        /// Body filename in cassette directory: `001.html`
+        pub body_file: String
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+    pub struct Cassette {
+        pub entries: Vec<CassetteEntry>
+    }
+
+    impl Cassette {
+        /// Load cassette index from directory
+        pub fn load(directory: &Path) -> OperationResult<Cassette> {
+            let content = fs::read_to_string(directory.join(CASSETTE_FILENAME))?;
+
+            serde_json::from_str(&content).map_err(|e| {
+                error!("unable to parse cassette '{}': {}", directory.display(), e);
+                OperationError::Error
+            })
+        }
+
+        /// Write cassette index to directory
+        pub fn save(&self, directory: &Path) -> OperationResult<()> {
+            let content = serde_json::to_string_pretty(self).map_err(|e| {
+                error!("unable to serialize cassette: {}", e);
+                OperationError::Error
+            })?;
+
+            let path = directory.join(CASSETTE_FILENAME);
+            let temp_path = path.with_extension("tmp");
+
+            fs::write(&temp_path, content)?;
+            fs::rename(&temp_path, &path)?;
+
+            Ok(())
+        }
+    }
+
+    /// What is removed from recorded responses
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct ScrubOptions {
+        /// Response headers dropped from cassette, case insensitive
+        pub headers: Vec<String>,
+
+        /// Text replaced in bodies and header values: `("borek8000", "user")`
+        pub replacements: Vec<(String, String)>
+    }
+
+    impl Default for ScrubOptions {
+        fn default() -> Self {
+            ScrubOptions {
+                headers: vec![String::from("set-cookie"), String::from("cookie"), String::from("authorization")],
+                replacements: Vec::new()
+            }
+        }
+    }
+
+    impl ScrubOptions {
+        /// Replace logged-in user name in recorded pages
+        pub fn with_replacement(mut self, text: &str, replacement: &str) -> ScrubOptions {
+            self.replacements.push((text.to_string(), replacement.to_string()));
+            self
+        }
+
+        pub fn with_header(mut self, name: &str) -> ScrubOptions {
+            self.headers.push(name.to_lowercase());
+            self
+        }
+
+        fn scrub_headers(&self, headers: &[(String, String)]) -> Vec<(String, String)> {
+            headers.iter()
+                   .filter(|(name, _)| !self.headers.iter().any(|scrubbed| scrubbed.eq_ignore_ascii_case(name)))
+                   .map(|(name, value)| (name.clone(), self.scrub_text(value)))
+                   .collect()
+        }
+
+        fn scrub_text(&self, text: &str) -> String {
+            self.replacements.iter()
+                .fold(text.to_string(), |text, (from, to)| text.replace(from, to))
+        }
+
+        fn scrub_body(&self, body: &[u8]) -> Vec<u8> {
+            self.replacements.iter()
+                .fold(body.to_vec(), |body, (from, to)| replace_bytes(&body, from.as_bytes(), to.as_bytes()))
+        }
+    }
+
+    /**
+    Start recording proxy to `upstream_url` (`https://www.opensubtitles.org`), use server `url()` as `base_url`.
+
+    Every request is forwarded as is (redirects are not followed, absolute upstream `Location` becomes relative),
+    response goes back unchanged and is written scrubbed to `directory`: index `cassette.json`
+    plus body files, which are usable as fixtures in `tests` directory.
+    */
+    pub fn record(upstream_url: &str, directory: &Path, scrub: ScrubOptions) -> OperationResult<MockServer> {
+        info!("record cassette from '{}' to '{}'", upstream_url, directory.display());
+
+        fs::create_dir_all(directory)?;
+
+        let upstream_url = upstream_url.trim_end_matches('/').to_string();
+        let directory = directory.to_path_buf();
+        let cassette = Arc::new(Mutex::new(Cassette::default()));
+
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .pool_max_idle_per_host(0)
+            .build()
+            .map_err(|e| {
+                error!("unable to create http client: {}", e);
+                OperationError::Error
+            })?;
+
+        Ok(MockServer::start_with_handler(move |request| {
+            match forward_request(&client, &upstream_url, request) {
+                Ok(response) => {
+                    if let Err(e) = write_entry(&cassette, &directory, request, &response, &scrub) {
+                        error!("unable to write cassette entry for '{}': {}", request.path, e);
+                    }
+
+                    response
+                }
+                Err(e) => {
+                    error!("unable to forward request '{}': {}", request.path, e);
+                    MockResponse::status(502)
+                }
+            }
+        }))
+    }
+
+    /**
+    Start server with responses from cassette `directory`.
+
+    Requests are matched by method and exact path. Repeated requests get
+    recorded responses in order, the last one is reused when they run out.
+    Unknown requests get `404`.
+    */
+    pub fn replay(directory: &Path) -> OperationResult<MockServer> {
+        info!("replay cassette '{}'", directory.display());
+
+        let cassette = Cassette::load(directory)?;
+        let mut entries = Vec::new();
+
+        for entry in cassette.entries {
+            let body = fs::read(directory.join(&entry.body_file))?;
+
+            let response = MockResponse {
+                status: entry.status,
+                headers: entry.headers,
+                body,
+                delay: None
+            };
+
+            entries.push((entry.method, entry.path, response, false));
+        }
+
+        let entries = Mutex::new(entries);
+
+        Ok(MockServer::start_with_handler(move |request| {
+            let mut entries = entries.lock().unwrap();
+
+            let mut matching = entries.iter_mut()
+                .filter(|(method, path, _, _)| method == &request.method && path == &request.path)
+                .peekable();
+
+            let mut last = None;
+
+            while let Some((_, _, response, replayed)) = matching.next() {
+                if !*replayed || matching.peek().is_none() {
+                    *replayed = true;
+                    last = Some(response.clone());
+                    break;
+                }
+            }
+
+            last.unwrap_or_else(|| {
+                warn!("no recorded response for {} '{}'", request.method, request.path);
+                MockResponse::status(404)
+            })
+        }))
+    }
+
+    fn forward_request(client: &reqwest::Client, upstream_url: &str,
+                       request: &MockRequest) -> OperationResult<MockResponse> {
+        let method = reqwest::Method::from_bytes(request.method.as_bytes()).map_err(|_| {
+            OperationError::InvalidArgument(format!("invalid method '{}'", request.method))
+        })?;
+
+        let mut upstream_request = client.request(method, format!("{}{}", upstream_url, request.path))
+                                         .body(request.body.clone());
+
+        for (name, value) in &request.headers {
+            if !SKIPPED_REQUEST_HEADERS.iter().any(|skipped| skipped.eq_ignore_ascii_case(name)) {
+                upstream_request = upstream_request.header(name.as_str(), value.as_str());
+            }
+        }
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+
+        runtime.block_on(async {
+            let resp = upstream_request.send().await.map_err(|e| {
+                error!("upstream request error: {}", e);
+                OperationError::Error
+            })?;
+
+            let status = resp.status().as_u16();
+
+            let headers: Vec<(String, String)> = resp.headers().iter()
+                .filter(|(name, _)| !SKIPPED_HEADERS.contains(&name.as_str()))
+                .filter_map(|(name, value)| {
+                    let value = value.to_str().ok()?;
+
+                    let value = match name == reqwest::header::LOCATION {
+                        true => value.strip_prefix(upstream_url).unwrap_or(value),
+                        false => value
+                    };
+
+                    Some((name.to_string(), value.to_string()))
+                })
+                .collect();
+
+            let body = resp.bytes().await.map_err(|e| {
+                error!("unable to read upstream response: {}", e);
+                OperationError::Error
+            })?;
+
+            Ok(MockResponse { status, headers, body: body.to_vec(), delay: None })
+        })
+    }
+
+    fn write_entry(cassette: &Mutex<Cassette>, directory: &Path, request: &MockRequest,
+                   response: &MockResponse, scrub: &ScrubOptions) -> OperationResult<()> {
+        let mut cassette = cassette.lock().unwrap();
+
+        let body_file = format!("{:03}.{}", cassette.entries.len() + 1, get_body_extension(response));
+        let body_path: PathBuf = directory.join(&body_file);
+
+        fs::write(&body_path, scrub.scrub_body(&response.body))?;
+
+        cassette.entries.push(CassetteEntry {
+            method: request.method.clone(),
+            path: scrub.scrub_text(&request.path),
+            status: response.status,
+            headers: scrub.scrub_headers(&response.headers),
+            body_file
+        });
+
+        debug!("recorded '{}' -> {}", request.path, response.status);
+
+        cassette.save(directory)
+    }
+
+    fn get_body_extension(response: &MockResponse) -> &'static str {
+        let content_type = response.headers.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.to_lowercase())
+            .unwrap_or_default();
+
+        if content_type.starts_with("text/html") {
+            "html"
+
+        } else if content_type.starts_with("application/zip") {
+            "zip"
+
+        } else {
+            "bin"
+        }
+    }
+
+    fn replace_bytes(content: &[u8], from: &[u8], to: &[u8]) -> Vec<u8> {
+        if from.is_empty() {
+            return content.to_vec();
+        }
+
+        let mut result = Vec::with_capacity(content.len());
+        let mut position = 0;
+
+        while position < content.len() {
+            if content[position..].starts_with(from) {
+                result.extend_from_slice(to);
+                position += from.len();
+
+            } else {
+                result.push(content[position]);
+                position += 1;
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod cassette_tests {
+    use std::fs;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tempfile::tempdir;
+
+    use crate::{search_by_mask, search_serial_episode};
+    use crate::cassette::cassette::{Cassette, record, replay, ScrubOptions};
+    use crate::mock_server::mock_server::{MockResponse, MockServer};
+
+    const SEARCH_RESULTS_PAGE: &str = "series-search-results.html";
+    const EPISODE_PAGE: &str = "episode-page.html";
+
+    #[tokio::test]
+    async fn recorded_responses_should_be_replayed() {
+        let upstream = get_upstream();
+        let directory = tempdir().unwrap();
+
+        let recorder = record(&upstream.url(), directory.path(), ScrubOptions::default()).unwrap();
+        let client = reqwest::Client::new();
+
+        let recorded_results = search_by_mask(&client, &recorder.url(), "adventure time", "rus").await.unwrap();
+
+        let player = replay(directory.path()).unwrap();
+
+        let replayed_results = search_by_mask(&client, &player.url(), "adventure time", "rus").await.unwrap();
+
+        assert_eq!(format!("{:?}", recorded_results), format!("{:?}", replayed_results));
+        assert_eq!(1, upstream.requests().len());
+    }
+
+    #[tokio::test]
+    async fn cassette_should_contain_request_and_response() {
+        let upstream = get_upstream();
+        let directory = tempdir().unwrap();
+
+        let recorder = record(&upstream.url(), directory.path(), ScrubOptions::default()).unwrap();
+
+        search_by_mask(&reqwest::Client::new(), &recorder.url(), "adventure time", "rus").await.unwrap();
+
+        let cassette = Cassette::load(directory.path()).unwrap();
+        assert_eq!(1, cassette.entries.len());
+
+        let entry = &cassette.entries[0];
+
+        assert_eq!("GET", entry.method);
+        assert_eq!("/en/search/sublanguageid-rus/moviename-adventure+time", entry.path);
+        assert_eq!(200, entry.status);
+        assert_eq!("001.html", entry.body_file);
+        assert!(entry.headers.iter().any(|(name, value)| name == "content-type" && value.starts_with("text/html")));
+
+        let expected_body = fs::read(format!("tests/{}", SEARCH_RESULTS_PAGE)).unwrap();
+        assert_eq!(expected_body, fs::read(directory.path().join("001.html")).unwrap());
+    }
+
+    #[tokio::test]
+    async fn cookies_and_user_name_should_be_scrubbed() {
+        let upstream = get_upstream();
+        let directory = tempdir().unwrap();
+
+        let scrub = ScrubOptions::default().with_replacement("borek8000", "user");
+        let recorder = record(&upstream.url(), directory.path(), scrub).unwrap();
+
+        let client = reqwest::Client::builder().cookie_store(true).build().unwrap();
+
+        search_by_mask(&client, &recorder.url(), "adventure time", "rus").await.unwrap();
+        search_by_mask(&client, &recorder.url(), "adventure time", "eng").await.unwrap();
+
+        let upstream_requests = upstream.requests();
+        assert_eq!(Some("PHPSESSID=secret"), upstream_requests[1].header("Cookie"));
+
+        let cassette = Cassette::load(directory.path()).unwrap();
+        let cassette_content = fs::read_to_string(directory.path().join("cassette.json")).unwrap();
+
+        assert!(!cassette_content.contains("secret"));
+        assert!(cassette.entries.iter().all(|entry| {
+            entry.headers.iter().all(|(name, _)| name != "set-cookie")
+        }));
+
+        let body = fs::read(directory.path().join("001.html")).unwrap();
+        let body = String::from_utf8_lossy(&body);
+
+        assert!(!body.contains("borek8000"));
+        assert!(body.contains("Logged-in as: <a href=\"https://www.opensubtitles.org/en/profile/iduser-8532398\">user"));
+    }
+
+    #[tokio::test]
+    async fn redirect_should_be_recorded_with_relative_location() {
+        let upstream = MockServer::start();
+        upstream.mock(r"^/en/search/",
+                      MockResponse::redirect(&format!("{}/en/subtitles/8179335/the-midnight-gospel", upstream.url())))
+                .mock(r"^/en/subtitles/8179335/", MockResponse::fixture(EPISODE_PAGE));
+
+        let directory = tempdir().unwrap();
+        let recorder = record(&upstream.url(), directory.path(), ScrubOptions::default()).unwrap();
+        let client = reqwest::Client::new();
+
+        let recorded_results = search_serial_episode(
+            &client, &recorder.url(), "Midnight Gospel", "eng", 1, 1
+        ).await.unwrap();
+
+        let cassette = Cassette::load(directory.path()).unwrap();
+
+        assert_eq!(2, cassette.entries.len());
+        assert_eq!(302, cassette.entries[0].status);
+        assert!(cassette.entries[0].headers.contains(
+            &(String::from("location"), String::from("/en/subtitles/8179335/the-midnight-gospel"))
+        ));
+
+        let player = replay(directory.path()).unwrap();
+
+        let replayed_results = search_serial_episode(
+            &client, &player.url(), "Midnight Gospel", "eng", 1, 1
+        ).await.unwrap();
+
+        assert_eq!(1, replayed_results.len());
+        assert_eq!(recorded_results[0].release_name, replayed_results[0].release_name);
+    }
+
+    #[tokio::test]
+    async fn unknown_request_should_not_be_replayed() {
+        let upstream = get_upstream();
+        let directory = tempdir().unwrap();
+
+        let recorder = record(&upstream.url(), directory.path(), ScrubOptions::default()).unwrap();
+        let client = reqwest::Client::new();
+
+        search_by_mask(&client, &recorder.url(), "adventure time", "rus").await.unwrap();
+
+        let player = replay(directory.path()).unwrap();
+
+        assert!(search_by_mask(&client, &player.url(), "tideland", "rus").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn repeated_requests_should_be_replayed_in_order_by_method() {
+        let counter = AtomicUsize::new(0);
+
+        let upstream = MockServer::start_with_handler(move |request| {
+            match request.method.as_str() {
+                "POST" => MockResponse::html("posted"),
+                _ => MockResponse::html(&format!("get {}", counter.fetch_add(1, Ordering::SeqCst) + 1))
+            }
+        });
+
+        let directory = tempdir().unwrap();
+        let recorder = record(&upstream.url(), directory.path(), ScrubOptions::default()).unwrap();
+        let client = reqwest::Client::new();
+        let url = format!("{}/page", recorder.url());
+
+        for _ in 0..2 {
+            client.get(&url).send().await.unwrap().text().await.unwrap();
+        }
+        client.post(&url).send().await.unwrap().text().await.unwrap();
+
+        let player = replay(directory.path()).unwrap();
+        let url = format!("{}/page", player.url());
+
+        assert_eq!("posted", client.post(&url).send().await.unwrap().text().await.unwrap());
+        assert_eq!("get 1", client.get(&url).send().await.unwrap().text().await.unwrap());
+        assert_eq!("get 2", client.get(&url).send().await.unwrap().text().await.unwrap());
+        assert_eq!("get 2", client.get(&url).send().await.unwrap().text().await.unwrap());
+
+        let response = client.put(&url).send().await.unwrap();
+        assert_eq!(404, response.status().as_u16());
+    }
+
+    #[test]
+    fn replay_without_cassette_should_return_error() {
+        let directory = tempdir().unwrap();
+        assert!(replay(directory.path()).is_err());
+    }
+
+    fn get_upstream() -> MockServer {
+        let upstream = MockServer::start();
+        upstream.mock(r"^/en/search/",
+                      MockResponse::fixture(SEARCH_RESULTS_PAGE).with_header("Set-Cookie", "PHPSESSID=secret; path=/"));
+        upstream
+    }
+}
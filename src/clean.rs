@@ -0,0 +1,139 @@
+pub mod clean {
+    use regex::Regex;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cue::cue::{Cue, Cues, renumber};
+    use crate::error::error::OperationError;
+    use crate::types::types::OperationResult;
+
+    const MARKUP_TAG_PATTERN: &str = "<[^>]*>";
+
+    /**
+    Promo and uploader credit cues commonly injected into site subtitles.
+
+    Credits must make the whole cue (`Synced and corrected by VitoSilans`), dialogue like
+    `It was encoded by the Germans` is kept.
+    */
+    pub const DEFAULT_AD_PATTERNS: [&str; 9] = [
+        "(?i)opensubtitles",
+        "(?i)advertise your product or brand here",
+        "(?i)become (a )?vip member",
+        "(?i)subtitles? (downloaded|provided|ripped) (from|by)",
+        "(?i)^\\s*(subtitles?|subs|sync(ed|hronized)?|re-?synced|correct(ed|ions)|translat(ed|ion)|ripped|encoded)\\s+(and\\s+\\w+\\s+)?by\\s*:?\\s*\\S+(?-i:\\s+[A-Z]\\S*)?\\s*$",
+        "(?i)^\\s*(перевод|синхронизация|субтитры|редактура)\\s*(и\\s+\\w+\\s*)?:",
+        "(?i)www\\.[a-z0-9-]+\\.[a-z]{2,}",
+        "(?i)https?://",
+        "(?i)@\\w+\\.(com|org|net|ru)\\b"
+    ];
+
+    /// Extensible list of ad/credit patterns (regular expressions)
+    #[derive(Clone, Debug)]
+    pub struct AdFilter {
+        patterns: Vec<Regex>
+    }
+
+    impl AdFilter {
+        /// Filter without patterns
+        pub fn empty() -> AdFilter {
+            AdFilter { patterns: Vec::new() }
+        }
+
+        /// Add pattern (regular expression) to the filter
+        pub fn with_pattern(mut self, pattern: &str) -> OperationResult<AdFilter> {
+            let regex = Regex::new(pattern).map_err(|e| {
+                error!("invalid ad pattern '{}': {}", pattern, e);
+                OperationError::InvalidArgument(format!("invalid ad pattern '{}'", pattern))
+            })?;
+
+            self.patterns.push(regex);
+
+            Ok(self)
+        }
+
+        /// First pattern matching the text
+        pub fn find_match(&self, text: &str) -> Option<&str> {
+            self.patterns.iter()
+                         .find(|pattern| pattern.is_match(text))
+                         .map(|pattern| pattern.as_str())
+        }
+
+        pub fn is_match(&self, text: &str) -> bool {
+            self.find_match(text).is_some()
+        }
+    }
+
+    impl Default for AdFilter {
+        /// Filter with built-in patterns
+        fn default() -> Self {
+            AdFilter {
+                patterns: DEFAULT_AD_PATTERNS.iter().map(|pattern| Regex::new(pattern).unwrap()).collect()
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub enum CleanMode {
+        /// Remove matched cues
+        Remove,
+
+        /// Keep matched cues timing, clear their text
+        Blank
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct RemovedCue {
+        /// Cue as it was before cleaning
+        pub cue: Cue,
+
+        /// Pattern that matched the cue
+        pub pattern: String
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct CleanReport {
+        /// Cleaned and renumbered cues
+        pub cues: Cues,
+
+        pub removed: Vec<RemovedCue>
+    }
+
+    /// Remove (or blank) cues matching the filter and renumber the rest
+    pub fn remove_ads(cues: &[Cue], filter: &AdFilter, mode: CleanMode) -> CleanReport {
+        info!("remove ad cues, mode {:?}", mode);
+
+        let mut results: Cues = Vec::new();
+        let mut removed: Vec<RemovedCue> = Vec::new();
+
+        for cue in cues {
+            match filter.find_match(&get_plain_text(&cue.text)) {
+                Some(pattern) => {
+                    debug!("ad cue {} matched '{}': '{}'", cue.index, pattern, cue.text);
+
+                    removed.push(RemovedCue { cue: cue.clone(), pattern: pattern.to_string() });
+
+                    if mode == CleanMode::Blank {
+                        results.push(Cue::new(cue.index, cue.start, cue.end, ""));
+                    }
+                }
+                None => results.push(cue.clone())
+            }
+        }
+
+        renumber(&mut results);
+
+        info!("ad cues found: {}", removed.len());
+
+        CleanReport { cues: results, removed }
+    }
+
+    /// Text without markup tags, lines joined with space
+    pub fn get_plain_text(text: &str) -> String {
+        let markup_tag_pattern = Regex::new(MARKUP_TAG_PATTERN).unwrap();
+
+        markup_tag_pattern.replace_all(text, "")
+                          .lines()
+                          .map(|line| line.trim())
+                          .collect::<Vec<&str>>()
+                          .join(" ")
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod clean_tests {
+    use crate::clean::clean::{AdFilter, CleanMode, remove_ads};
+    use crate::cue::cue::Cue;
+
+    #[test]
+    fn ad_and_credit_cues_should_be_removed_and_rest_renumbered() {
+        let cues = vec![
+            Cue::new(1, 0, 2000, "Subtitles downloaded from www.OpenSubtitles.org"),
+            Cue::new(2, 3000, 5000, "Where are we going?"),
+            Cue::new(3, 6000, 8000, "<font color=\"#ffff00\">Advertise your product or brand here</font>\ncontact us today"),
+            Cue::new(4, 9000, 11000, "To the Candy Kingdom."),
+            Cue::new(5, 12000, 14000, "Synced and corrected by VitoSilans"),
+            Cue::new(6, 15000, 17000, "Перевод и синхронизация: Notabenoid")
+        ];
+
+        let report = remove_ads(&cues, &AdFilter::default(), CleanMode::Remove);
+
+        assert_eq!(vec![
+            Cue::new(1, 3000, 5000, "Where are we going?"),
+            Cue::new(2, 9000, 11000, "To the Candy Kingdom.")
+        ], report.cues);
+
+        let removed_indexes: Vec<u32> = report.removed.iter().map(|removed| removed.cue.index).collect();
+        assert_eq!(vec![1, 3, 5, 6], removed_indexes);
+    }
+
+    #[test]
+    fn credit_words_in_dialogue_should_be_kept() {
+        let cues = vec![
+            Cue::new(1, 0, 2000, "We're lost in translation by now."),
+            Cue::new(2, 3000, 5000, "It was encoded by the Germans."),
+            Cue::new(3, 6000, 8000, "Encoded by the Germans, decoded by us.")
+        ];
+
+        let report = remove_ads(&cues, &AdFilter::default(), CleanMode::Remove);
+
+        assert_eq!(cues, report.cues);
+        assert!(report.removed.is_empty());
+    }
+
+    #[test]
+    fn ad_cues_should_be_blanked() {
+        let cues = vec![
+            Cue::new(1, 0, 2000, "Hi"),
+            Cue::new(2, 3000, 5000, "Support us and become VIP member")
+        ];
+
+        let report = remove_ads(&cues, &AdFilter::default(), CleanMode::Blank);
+
+        assert_eq!(vec![Cue::new(1, 0, 2000, "Hi"), Cue::new(2, 3000, 5000, "")], report.cues);
+        assert_eq!(1, report.removed.len());
+    }
+
+    #[test]
+    fn custom_patterns_should_be_used() {
+        let filter = AdFilter::empty().with_pattern("(?i)lostfilm").unwrap();
+
+        let cues = vec![Cue::new(1, 0, 2000, "LostFilm presents"), Cue::new(2, 3000, 5000, "www.example.com")];
+
+        let report = remove_ads(&cues, &filter, CleanMode::Remove);
+
+        assert_eq!(vec![Cue::new(1, 3000, 5000, "www.example.com")], report.cues);
+        assert_eq!("(?i)lostfilm", report.removed[0].pattern);
+    }
+
+    #[test]
+    fn invalid_pattern_should_be_rejected() {
+        assert!(AdFilter::default().with_pattern("(unclosed").is_err());
+    }
+}
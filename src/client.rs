@@ -0,0 +1,260 @@
+pub mod client {
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use crate::{BASE_URL, download_subtitles, find_best_subtitles, get_download_url_from_page,
+                get_series_by_imdb_id, get_series_by_mask, get_subtitle_details, search_by_hash,
+                search_by_imdb_id, search_by_mask_with_locale, search_serial_episode_with_locale,
+                search_serial_season_with_locale};
+    use crate::config::config::{Config, Credentials, Settings};
+    use crate::domain::domain::{Series, SubtitleFile, SubtitleSearchResultItem, SubtitleSearchResults};
+    use crate::error::error::OperationError;
+    use crate::hash::hash::MovieHash;
+    use crate::locale::locale::{DEFAULT_LOCALE, get_locale_code};
+    use crate::ranking::ranking::{RankedSubtitle, RankingOptions};
+    use crate::transport::transport::{HttpTransport, RateLimitedTransport};
+    use crate::types::types::{OperationResult, OptionResult};
+    use crate::upload::upload::{upload_subtitles, UploadRequest};
+
+    const DEFAULT_USER_AGENT: &str = "opensubs-rs";
+
+    /// Site client with settings from config profile
+    pub struct Client {
+        transport: Arc<dyn HttpTransport>,
+        base_url: String,
+
+        /// Site UI locale: `en`, `ru`
+        locale: String,
+
+        settings: Settings,
+        credentials: Option<Credentials>
+    }
+
+    #[derive(Default)]
+    pub struct ClientBuilder {
+        config: Option<Config>,
+        profile: Option<String>,
+        base_url: Option<String>,
+        locale: Option<String>,
+        user_agent: Option<String>,
+        languages: Option<Vec<String>>,
+        transport: Option<Arc<dyn HttpTransport>>
+    }
+
+    impl ClientBuilder {
+        /// Use config, see `Config::load_default`
+        pub fn config(mut self, config: Config) -> ClientBuilder {
+            self.config = Some(config);
+            self
+        }
+
+        /// Config profile name, config `profile` value is used by default
+        pub fn profile(mut self, profile: &str) -> ClientBuilder {
+            self.profile = Some(profile.to_string());
+            self
+        }
+
+        /// Overrides config value
+        pub fn base_url(mut self, base_url: &str) -> ClientBuilder {
+            self.base_url = Some(base_url.to_string());
+            self
+        }
+
+        /// Site UI locale (`ru`), overrides config value
+        pub fn locale(mut self, locale: &str) -> ClientBuilder {
+            self.locale = Some(locale.to_string());
+            self
+        }
+
+        /// Overrides config value
+        pub fn user_agent(mut self, user_agent: &str) -> ClientBuilder {
+            self.user_agent = Some(user_agent.to_string());
+            self
+        }
+
+        /// Overrides profile languages
+        pub fn languages(mut self, languages: &[&str]) -> ClientBuilder {
+            self.languages = Some(languages.iter().map(|language| language.to_string()).collect());
+            self
+        }
+
+        /// Use prepared http client, `user_agent` is ignored then
+        pub fn http_client(self, http_client: reqwest::Client) -> ClientBuilder {
+            self.transport(http_client)
+        }
+
+        /// Use own HTTP stack, `user_agent` is ignored then
+        pub fn transport(mut self, transport: impl HttpTransport + 'static) -> ClientBuilder {
+            self.transport = Some(Arc::new(transport));
+            self
+        }
+
+        pub fn build(self) -> OperationResult<Client> {
+            let config = self.config.unwrap_or_default();
+
+            let mut settings = config.get_settings(self.profile.as_deref())?;
+
+            if let Some(languages) = self.languages {
+                settings.languages = languages;
+            }
+
+            let transport: Arc<dyn HttpTransport> = match self.transport {
+                Some(transport) => transport,
+                None => {
+                    let user_agent = self.user_agent.or(config.user_agent)
+                                         .unwrap_or_else(|| String::from(DEFAULT_USER_AGENT));
+
+                    let http_client = reqwest::Client::builder()
+                        .user_agent(user_agent)
+                        .cookie_store(true)
+                        .build()
+                        .map_err(|e| {
+                            error!("unable to create http client: {}", e);
+                            OperationError::Error
+                        })?;
+
+                    Arc::new(http_client)
+                }
+            };
+
+            // limits every request, `transport()` users included
+            let transport: Arc<dyn HttpTransport> = match config.requests_per_second {
+                Some(rate) => Arc::new(RateLimitedTransport::new(transport, rate)?),
+                None => transport
+            };
+
+            let locale = self.locale.or(config.locale).unwrap_or_else(|| String::from(DEFAULT_LOCALE));
+
+            let locale = get_locale_code(&locale).ok_or_else(|| {
+                error!("unsupported locale '{}'", locale);
+                OperationError::InvalidArgument(format!("unsupported locale '{}'", locale))
+            })?;
+
+            debug!("client settings: {:?}", settings);
+
+            Ok(Client {
+                transport,
+                base_url: self.base_url.or(config.base_url).unwrap_or_else(|| String::from(BASE_URL)),
+                locale: locale.to_string(),
+                settings,
+                credentials: config.credentials
+            })
+        }
+    }
+
+    impl Client {
+        pub fn builder() -> ClientBuilder {
+            ClientBuilder::default()
+        }
+
+        pub fn transport(&self) -> &dyn HttpTransport {
+            self.transport.as_ref()
+        }
+
+        pub fn base_url(&self) -> &str {
+            &self.base_url
+        }
+
+        pub fn locale(&self) -> &str {
+            &self.locale
+        }
+
+        pub fn settings(&self) -> &Settings {
+            &self.settings
+        }
+
+        pub fn credentials(&self) -> Option<&Credentials> {
+            self.credentials.as_ref()
+        }
+
+        /// Search with profile languages, results filtered and ordered by profile preferences
+        pub async fn search_by_mask(&self, mask: &str) -> OperationResult<SubtitleSearchResults> {
+            let results = search_by_mask_with_locale(
+                self.transport.as_ref(), &self.base_url, &self.locale, mask, &self.settings.get_languages_param()
+            ).await?;
+
+            Ok(self.settings.apply_preferences(results))
+        }
+
+        pub async fn search_serial_season(&self, mask: &str, season: u8) -> OperationResult<SubtitleSearchResults> {
+            let results = search_serial_season_with_locale(
+                self.transport.as_ref(), &self.base_url, &self.locale, mask, &self.settings.get_languages_param(), season
+            ).await?;
+
+            Ok(self.settings.apply_preferences(results))
+        }
+
+        pub async fn search_serial_episode(&self, mask: &str, season: u8,
+                                           episode: u16) -> OperationResult<SubtitleSearchResults> {
+            let results = search_serial_episode_with_locale(
+                self.transport.as_ref(), &self.base_url, &self.locale, mask, &self.settings.get_languages_param(),
+                season, episode
+            ).await?;
+
+            Ok(self.settings.apply_preferences(results))
+        }
+
+        /// Search subtitles made for the exact video file (see `hash::get_movie_hash`)
+        pub async fn search_by_hash(&self, movie_hash: &MovieHash) -> OperationResult<SubtitleSearchResults> {
+            let results = search_by_hash(
+                self.transport.as_ref(), &self.base_url, &self.locale, movie_hash, &self.settings.get_languages_param()
+            ).await?;
+
+            Ok(self.settings.apply_preferences(results))
+        }
+
+        /// Search by IMDb id, `tt7342228` or `7342228`
+        pub async fn search_by_imdb_id(&self, imdb_id: &str) -> OperationResult<SubtitleSearchResults> {
+            let results = search_by_imdb_id(
+                self.transport.as_ref(), &self.base_url, &self.locale, imdb_id, &self.settings.get_languages_param()
+            ).await?;
+
+            Ok(self.settings.apply_preferences(results))
+        }
+
+        /// Subtitle info from a details page (`details_url` of search result item)
+        pub async fn get_subtitle_details(&self, page_url: &str) -> OperationResult<SubtitleSearchResultItem> {
+            get_subtitle_details(self.transport.as_ref(), page_url).await
+        }
+
+        pub async fn get_download_url_from_page(&self, page_url: &str) -> OptionResult<String> {
+            get_download_url_from_page(self.transport.as_ref(), page_url).await
+        }
+
+        pub async fn download_subtitles(&self, download_url: &str) -> OperationResult<Vec<SubtitleFile>> {
+            download_subtitles(self.transport.as_ref(), &self.base_url, download_url).await
+        }
+
+        /// Best subtitles for a video file in profile languages
+        pub async fn find_best_subtitles(&self, video_path: &Path,
+                                         options: &RankingOptions) -> OperationResult<Vec<RankedSubtitle>> {
+            find_best_subtitles(
+                self.transport.as_ref(), &self.base_url, &self.locale, video_path,
+                &self.settings.get_languages_param(), options
+            ).await
+        }
+
+        /// Series seasons and episodes with subtitle counts in profile languages
+        pub async fn get_series_by_mask(&self, mask: &str) -> OptionResult<Series> {
+            get_series_by_mask(
+                self.transport.as_ref(), &self.base_url, &self.locale, mask, &self.settings.get_languages_param()
+            ).await
+        }
+
+        pub async fn get_series_by_imdb_id(&self, imdb_id: &str) -> OperationResult<Series> {
+            get_series_by_imdb_id(
+                self.transport.as_ref(), &self.base_url, &self.locale, imdb_id, &self.settings.get_languages_param()
+            ).await
+        }
+
+        /// Upload subtitles with config credentials, returns new subtitle id
+        pub async fn upload_subtitles(&self, request: &UploadRequest) -> OperationResult<String> {
+            let credentials = self.credentials.as_ref().ok_or_else(|| {
+                error!("credentials are required for upload");
+                OperationError::Authentication
+            })?;
+
+            upload_subtitles(self.transport.as_ref(), &self.base_url, &self.locale, credentials, request).await
+        }
+    }
+}
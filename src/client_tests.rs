@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod client_tests {
+    use crate::BASE_URL;
+    use crate::client::client::Client;
+    use crate::config::config::Config;
+    use crate::error::error::OperationError;
+    use crate::mock_server::mock_server::{MockResponse, MockServer};
+
+    #[test]
+    fn client_should_use_config_profile() {
+        let config = Config::parse(r#"
+            base_url = "http://localhost:8080"
+
+            [profiles.kids]
+            languages = ["rus"]
+        "#).unwrap();
+
+        let client = Client::builder().config(config.clone()).profile("kids").build().unwrap();
+
+        assert_eq!("http://localhost:8080", client.base_url());
+        assert_eq!(vec!["rus"], client.settings().languages);
+        assert!(client.credentials().is_none());
+
+        let client = Client::builder().config(config).base_url(BASE_URL).languages(&["eng", "fre"]).build().unwrap();
+
+        assert_eq!(BASE_URL, client.base_url());
+        assert_eq!("eng,fre", client.settings().get_languages_param());
+    }
+
+    #[test]
+    fn invalid_rate_limit_should_be_rejected() {
+        let config = Config::parse("requests_per_second = 0.0").unwrap();
+
+        match Client::builder().config(config).build() {
+            Err(OperationError::InvalidArgument(_)) => {}
+            _ => panic!("error expected")
+        }
+    }
+
+    #[tokio::test]
+    async fn client_should_search_with_locale() {
+        let server = MockServer::start();
+        server.mock(r"^/ru/search/sublanguageid-rus/moviename-adventure\+time$",
+                    MockResponse::fixture("series-search-results.html"));
+
+        let config = Config::parse(r#"locale = "pt-BR""#).unwrap();
+
+        let client = Client::builder().config(config.clone()).build().unwrap();
+
+        assert_eq!("pb", client.locale());
+
+        let client = Client::builder().config(config).base_url(&server.url()).locale("ru")
+                                      .languages(&["rus"]).build().unwrap();
+
+        assert_eq!("ru", client.locale());
+        assert!(!client.search_by_mask("adventure time").await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn unsupported_locale_should_be_rejected() {
+        match Client::builder().locale("xx").build() {
+            Err(OperationError::InvalidArgument(_)) => {}
+            _ => panic!("error expected")
+        }
+    }
+}
@@ -0,0 +1,247 @@
+pub mod config {
+    use std::collections::HashMap;
+    use std::env;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::domain::domain::{SubtitleSearchResultItem, SubtitleSearchResults};
+    use crate::error::error::OperationError;
+    use crate::format::format::SubtitleFormat;
+    use crate::language::language::LanguageCodeStyle;
+    use crate::types::types::OperationResult;
+
+    /// Config file path override
+    pub const CONFIG_PATH_ENV: &str = "OPENSUBS_CONFIG";
+
+    const CONFIG_DIRECTORY: &str = "opensubs";
+    const CONFIG_FILENAME: &str = "config.toml";
+
+    const DEFAULT_LANGUAGE: &str = "eng";
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum HearingImpairedPreference {
+        /// No preference
+        Any,
+
+        /// Hearing impaired subtitles first
+        Prefer,
+
+        /// Hearing impaired subtitles only
+        Only,
+
+        /// No hearing impaired subtitles
+        Exclude
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct Credentials {
+        pub login: String,
+        pub password: String
+    }
+
+    /// Profile settings, unset values are taken from `[defaults]`
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct Profile {
+        /// Subtitle languages: `["rus", "eng"]`
+        pub languages: Option<Vec<String>>,
+
+        pub hearing_impaired: Option<HearingImpairedPreference>,
+
+        /// Preferred subtitle formats (extensions), best first: `["srt", "ass"]`
+        pub formats: Option<Vec<String>>,
+
+        /// Language codes in saved file names: `iso639-1` or `iso639-2`
+        pub naming: Option<LanguageCodeStyle>
+    }
+
+    /**
+    TOML config:
+
+    ```toml
+    base_url = "https://www.opensubtitles.org"
+    locale = "en"
+    profile = "anime"
+    requests_per_second = 1.0
+    cache_dir = "/var/cache/opensubs"
+
+    [credentials]
+    login = "user"
+    password = "secret"
+
+    [defaults]
+    languages = ["eng"]
+    hearing_impaired = "exclude"
+    formats = ["srt", "ass"]
+    naming = "iso639-1"
+
+    [profiles.anime]
+    languages = ["eng", "jpn"]
+    formats = ["ass"]
+    ```
+    */
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct Config {
+        pub base_url: Option<String>,
+
+        pub user_agent: Option<String>,
+
+        /// Site UI locale: `ru` gives localized titles in results
+        pub locale: Option<String>,
+
+        pub credentials: Option<Credentials>,
+
+        /// Rate limit for client calls
+        pub requests_per_second: Option<f64>,
+
+        /// Directory for cache and state files (watch queue)
+        pub cache_dir: Option<PathBuf>,
+
+        /// Profile used when none is given
+        pub profile: Option<String>,
+
+        pub defaults: Profile,
+
+        pub profiles: HashMap<String, Profile>
+    }
+
+    /// Resolved profile settings
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct Settings {
+        pub languages: Vec<String>,
+        pub hearing_impaired: HearingImpairedPreference,
+        pub formats: Vec<SubtitleFormat>,
+        pub naming: LanguageCodeStyle
+    }
+
+    impl Default for Settings {
+        fn default() -> Self {
+            Settings {
+                languages: vec![String::from(DEFAULT_LANGUAGE)],
+                hearing_impaired: HearingImpairedPreference::Any,
+                formats: Vec::new(),
+                naming: LanguageCodeStyle::Iso639_1
+            }
+        }
+    }
+
+    impl Settings {
+        /// Languages in site format: `rus,eng`
+        pub fn get_languages_param(&self) -> String {
+            self.languages.join(",")
+        }
+
+        /// Filter results by hearing impaired preference and order them by preferred formats (stable)
+        pub fn apply_preferences(&self, results: SubtitleSearchResults) -> SubtitleSearchResults {
+            let mut results: Vec<((u8, usize), SubtitleSearchResultItem)> = results.into_iter().filter_map(|item| {
+                get_preference_rank(&item, self.hearing_impaired, &self.formats).map(|rank| (rank, item))
+            }).collect();
+
+            results.sort_by_key(|(rank, _)| *rank);
+
+            results.into_iter().map(|(_, item)| item).collect()
+        }
+    }
+
+    /// Hearing impaired and format rank of result (lower is better), `None` when excluded by preference
+    pub fn get_preference_rank(item: &SubtitleSearchResultItem, hearing_impaired: HearingImpairedPreference,
+                               formats: &[SubtitleFormat]) -> Option<(u8, usize)> {
+        let hearing_impaired_rank = match hearing_impaired {
+            HearingImpairedPreference::Only if !item.hearing_impaired => return None,
+            HearingImpairedPreference::Exclude if item.hearing_impaired => return None,
+            HearingImpairedPreference::Prefer if !item.hearing_impaired => 1,
+            _ => 0
+        };
+
+        let format_rank = item.format
+            .and_then(|format| formats.iter().position(|preferred| *preferred == format))
+            .unwrap_or(formats.len());
+
+        Some((hearing_impaired_rank, format_rank))
+    }
+
+    impl Config {
+        pub fn load(path: &Path) -> OperationResult<Config> {
+            info!("load config from '{}'", path.display());
+
+            let content = fs::read_to_string(path)?;
+
+            Config::parse(&content)
+        }
+
+        pub fn parse(content: &str) -> OperationResult<Config> {
+            toml::from_str(content).map_err(|e| {
+                error!("invalid config: {}", e);
+                OperationError::InvalidArgument(format!("invalid config: {}", e))
+            })
+        }
+
+        /// Load config from `OPENSUBS_CONFIG` path or default path, empty config if default file doesn't exist
+        pub fn load_default() -> OperationResult<Config> {
+            if let Ok(path) = env::var(CONFIG_PATH_ENV) {
+                return Config::load(Path::new(&path));
+            }
+
+            match get_default_config_path() {
+                Some(path) if path.exists() => Config::load(&path),
+                _ => {
+                    debug!("config file not found, use defaults");
+                    Ok(Config::default())
+                }
+            }
+        }
+
+        /// Settings of the profile (`profile` from config if `None`) merged with defaults
+        pub fn get_settings(&self, profile_name: Option<&str>) -> OperationResult<Settings> {
+            let mut settings = Settings::default();
+            apply_profile(&mut settings, &self.defaults);
+
+            if let Some(profile_name) = profile_name.or(self.profile.as_deref()) {
+                match self.profiles.get(profile_name) {
+                    Some(profile) => apply_profile(&mut settings, profile),
+                    None => {
+                        error!("unknown profile '{}'", profile_name);
+                        return Err(OperationError::InvalidArgument(format!("unknown profile '{}'", profile_name)));
+                    }
+                }
+            }
+
+            Ok(settings)
+        }
+    }
+
+    /// `$XDG_CONFIG_HOME/opensubs/config.toml` or `~/.config/opensubs/config.toml`
+    pub fn get_default_config_path() -> Option<PathBuf> {
+        let config_home = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+
+        Some(config_home.join(CONFIG_DIRECTORY).join(CONFIG_FILENAME))
+    }
+
+    fn apply_profile(settings: &mut Settings, profile: &Profile) {
+        if let Some(languages) = &profile.languages {
+            settings.languages = languages.clone();
+        }
+
+        if let Some(hearing_impaired) = profile.hearing_impaired {
+            settings.hearing_impaired = hearing_impaired;
+        }
+
+        if let Some(formats) = &profile.formats {
+            settings.formats = formats.iter()
+                                      .map(|format| SubtitleFormat::from_extension(format))
+                                      .filter(|format| *format != SubtitleFormat::Unknown)
+                                      .collect();
+        }
+
+        if let Some(naming) = profile.naming {
+            settings.naming = naming;
+        }
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod config_tests {
+    use std::path::Path;
+
+    use crate::config::config::{Config, HearingImpairedPreference};
+    use crate::domain::domain::SubtitleSearchResultItem;
+    use crate::error::error::OperationError;
+    use crate::format::format::SubtitleFormat;
+    use crate::language::language::LanguageCodeStyle;
+
+    const CONFIG: &str = r#"
+        profile = "anime"
+        requests_per_second = 2.0
+        cache_dir = "/var/cache/opensubs"
+
+        [credentials]
+        login = "user"
+        password = "secret"
+
+        [defaults]
+        languages = ["rus", "eng"]
+        hearing_impaired = "exclude"
+
+        [profiles.anime]
+        languages = ["eng", "jpn"]
+        formats = ["ass", "srt"]
+
+        [profiles.kids]
+        hearing_impaired = "prefer"
+        naming = "iso639-2"
+    "#;
+
+    #[test]
+    fn profile_should_override_defaults() {
+        let config = Config::parse(CONFIG).unwrap();
+
+        assert_eq!(Some(2.0), config.requests_per_second);
+        assert_eq!(Some(Path::new("/var/cache/opensubs")), config.cache_dir.as_deref());
+        assert_eq!("user", config.credentials.as_ref().unwrap().login);
+
+        let anime = config.get_settings(None).unwrap();
+        assert_eq!(vec!["eng", "jpn"], anime.languages);
+        assert_eq!(HearingImpairedPreference::Exclude, anime.hearing_impaired);
+        assert_eq!(vec![SubtitleFormat::Ass, SubtitleFormat::Srt], anime.formats);
+        assert_eq!(LanguageCodeStyle::Iso639_1, anime.naming);
+
+        let kids = config.get_settings(Some("kids")).unwrap();
+        assert_eq!("rus,eng", kids.get_languages_param());
+        assert_eq!(HearingImpairedPreference::Prefer, kids.hearing_impaired);
+        assert_eq!(LanguageCodeStyle::Iso639_2, kids.naming);
+    }
+
+    #[test]
+    fn empty_config_should_have_default_settings() {
+        let settings = Config::parse("").unwrap().get_settings(None).unwrap();
+
+        assert_eq!(vec!["eng"], settings.languages);
+        assert_eq!(HearingImpairedPreference::Any, settings.hearing_impaired);
+    }
+
+    #[test]
+    fn unknown_profile_and_invalid_config_should_be_rejected() {
+        match Config::parse(CONFIG).unwrap().get_settings(Some("unknown")) {
+            Err(OperationError::InvalidArgument(_)) => {}
+            _ => panic!("error expected")
+        }
+
+        match Config::parse("[defaults]\nlanguage = \"eng\"") {
+            Err(OperationError::InvalidArgument(_)) => {}
+            _ => panic!("error expected")
+        }
+    }
+
+    #[test]
+    fn results_should_follow_preferences() {
+        let config = Config::parse(CONFIG).unwrap();
+
+        let results = vec![
+            get_item(1, false, Some(SubtitleFormat::Srt)),
+            get_item(2, true, Some(SubtitleFormat::Ass)),
+            get_item(3, false, None),
+            get_item(4, false, Some(SubtitleFormat::Ass))
+        ];
+
+        let anime = config.get_settings(Some("anime")).unwrap();
+        let indexes: Vec<u8> = anime.apply_preferences(results.clone()).iter().map(|item| item.index).collect();
+        assert_eq!(vec![4, 1, 3], indexes);
+
+        let kids = config.get_settings(Some("kids")).unwrap();
+        let indexes: Vec<u8> = kids.apply_preferences(results).iter().map(|item| item.index).collect();
+        assert_eq!(vec![2, 1, 3, 4], indexes);
+    }
+
+    fn get_item(index: u8, hearing_impaired: bool, format: Option<SubtitleFormat>) -> SubtitleSearchResultItem {
+        SubtitleSearchResultItem {
+            index,
+            title: String::from("Title"),
+            details_url: format!("url{}", index),
+            season: 0,
+            episode: 0,
+            hearing_impaired,
+            release_name: None,
+            downloads: 0,
+            rating: 0.0,
+            fps: None,
+            uploader: None,
+            uploader_rank: None,
+            imdb_id: None,
+            format
+        }
+    }
+}
@@ -0,0 +1,363 @@
+pub mod coverage {
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::get_series_by_mask;
+    use crate::batch::batch::{find_video_files, get_missing_languages};
+    use crate::domain::domain::Series;
+    use crate::error::error::OperationError;
+    use crate::language::language::{get_language_code, LanguageCodeStyle};
+    use crate::release::release::parse_release_name;
+    use crate::transport::transport::HttpTransport;
+    use crate::types::types::OperationResult;
+
+    const CSV_SEPARATOR: &str = ",";
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    pub enum CoverageStatus {
+        /// Subtitles are next to the local video
+        Local,
+
+        /// Not present locally, the site has subtitles
+        Available,
+
+        /// Neither local nor on the site
+        Missing
+    }
+
+    impl CoverageStatus {
+        pub fn name(&self) -> &'static str {
+            match self {
+                CoverageStatus::Local => "local",
+                CoverageStatus::Available => "available",
+                CoverageStatus::Missing => "missing"
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct LanguageCoverage {
+        /// Subtitle count on the site
+        pub available: u32,
+
+        /// Subtitles are next to the local video
+        pub local: bool,
+
+        pub status: CoverageStatus
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct EpisodeCoverage {
+        pub season: u8,
+        pub episode: u16,
+
+        /// Episode title from the site, empty for local videos unknown to the site
+        pub title: String,
+
+        /// Local video file of the episode
+        pub video: Option<PathBuf>,
+
+        /// Coverage per wanted language (ISO 639-2 code)
+        pub languages: BTreeMap<String, LanguageCoverage>
+    }
+
+    /// Episode count per status
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+    pub struct LanguageSummary {
+        pub local: usize,
+        pub available: usize,
+        pub missing: usize
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct CoverageReport {
+        pub series: String,
+
+        /// IMDb id: `tt1305826`
+        pub imdb_id: Option<String>,
+
+        /// Wanted languages, ISO 639-2 codes
+        pub languages: Vec<String>,
+
+        /// Ordered by season and episode
+        pub episodes: Vec<EpisodeCoverage>,
+
+        pub summary: BTreeMap<String, LanguageSummary>
+    }
+
+    /// Local video of a series episode
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct LocalEpisode {
+        pub video: PathBuf,
+
+        /// Series title parsed from the file name
+        pub title: String,
+
+        pub season: u8,
+        pub episode: u16,
+
+        /// Wanted languages (ISO 639-2 codes) with subtitles next to the video
+        pub languages: Vec<String>
+    }
+
+    /**
+    Episode videos in directory tree with their local subtitle languages.
+
+    Season and episodes are parsed from file names (`show.s01e02.mkv`), multi-episode files
+    cover every episode. Videos without season and episode are ignored.
+    */
+    pub fn find_local_episodes(directory: &Path, languages: &[String]) -> OperationResult<Vec<LocalEpisode>> {
+        let languages = get_site_languages(languages)?;
+
+        let mut results: Vec<LocalEpisode> = Vec::new();
+
+        for video in find_video_files(directory)? {
+            let filename = video.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+
+            let release = parse_release_name(&filename);
+
+            let season = match release.season {
+                Some(season) if !release.episodes.is_empty() => season,
+                _ => {
+                    debug!("video '{}' is not an episode, skip", video.display());
+                    continue;
+                }
+            };
+
+            let missing_languages = get_missing_languages(&video, &languages)?;
+
+            let local_languages: Vec<String> = languages.iter()
+                                                        .filter(|language| !missing_languages.contains(language))
+                                                        .cloned()
+                                                        .collect();
+
+            results.extend(release.episodes.iter().map(|episode| LocalEpisode {
+                video: video.clone(),
+                title: release.title.clone(),
+                season,
+                episode: *episode,
+                languages: local_languages.clone()
+            }));
+        }
+
+        info!("local episodes found: {}", results.len());
+
+        Ok(results)
+    }
+
+    /**
+    Coverage matrix of series episodes: site subtitle counts (see `get_series_by_mask`)
+    joined with local episodes of the same series.
+
+    Series must be fetched with the same languages, ISO 639-1 and ISO 639-2 codes are matched.
+    Local episodes unknown to the site are reported too.
+    */
+    pub fn get_coverage_report(series: &Series, languages: &[String],
+                               local_episodes: &[LocalEpisode]) -> OperationResult<CoverageReport> {
+        let languages = get_site_languages(languages)?;
+
+        let mut episodes: Vec<EpisodeCoverage> = Vec::new();
+
+        for site_episode in series.seasons.iter().flat_map(|season| season.episodes.iter()) {
+            let local_episode = local_episodes.iter().find(|local_episode| {
+                local_episode.season == site_episode.season && local_episode.episode == site_episode.episode
+            });
+
+            episodes.push(get_episode_coverage(
+                site_episode.season, site_episode.episode, &site_episode.title,
+                &site_episode.subtitle_counts, local_episode, &languages
+            ));
+        }
+
+        for local_episode in local_episodes {
+            let known = episodes.iter().any(|episode| {
+                episode.season == local_episode.season && episode.episode == local_episode.episode
+            });
+
+            if !known {
+                warn!("local episode S{:02}E{:02} is unknown to the site", local_episode.season, local_episode.episode);
+
+                episodes.push(get_episode_coverage(
+                    local_episode.season, local_episode.episode, "", &BTreeMap::new(), Some(local_episode), &languages
+                ));
+            }
+        }
+
+        episodes.sort_by_key(|episode| (episode.season, episode.episode));
+
+        let mut summary: BTreeMap<String, LanguageSummary> = BTreeMap::new();
+
+        for language in &languages {
+            let language_summary = summary.entry(language.clone()).or_default();
+
+            for coverage in episodes.iter().filter_map(|episode| episode.languages.get(language)) {
+                match coverage.status {
+                    CoverageStatus::Local => language_summary.local += 1,
+                    CoverageStatus::Available => language_summary.available += 1,
+                    CoverageStatus::Missing => language_summary.missing += 1
+                }
+            }
+        }
+
+        Ok(CoverageReport {
+            series: series.title.clone(),
+            imdb_id: series.imdb_id.clone(),
+            languages,
+            episodes,
+            summary
+        })
+    }
+
+    /**
+    Coverage of every series in a media library.
+
+    Local episodes are grouped by series title parsed from file names, each series is
+    looked up on the site by title. Series not found on the site or failed to load are skipped.
+    */
+    pub async fn get_library_coverage(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                                      directory: &Path, languages: &[String]) -> OperationResult<Vec<CoverageReport>> {
+        info!("library coverage for '{}'", directory.display());
+
+        let local_episodes = find_local_episodes(directory, languages)?;
+        let sub_langs = get_site_languages(languages)?.join(",");
+
+        let mut groups: BTreeMap<String, Vec<LocalEpisode>> = BTreeMap::new();
+
+        for local_episode in local_episodes {
+            groups.entry(local_episode.title.to_lowercase()).or_default().push(local_episode);
+        }
+
+        let mut reports: Vec<CoverageReport> = Vec::new();
+
+        for (title, episodes) in groups {
+            match get_series_by_mask(client, base_url, locale, &title, &sub_langs).await {
+                Ok(Some(series)) => reports.push(get_coverage_report(&series, languages, &episodes)?),
+                Ok(None) => warn!("series '{}' not found on the site, skip", title),
+                Err(e) => error!("unable to get series '{}': {}, skip", title, e)
+            }
+        }
+
+        Ok(reports)
+    }
+
+    /// `SERIES, SEASON, EPISODE, TITLE` and a column per language
+    pub fn get_table_headers(languages: &[String]) -> Vec<String> {
+        let mut headers: Vec<String> = ["SERIES", "SEASON", "EPISODE", "TITLE"].iter()
+                                                                                 .map(|header| header.to_string())
+                                                                                 .collect();
+
+        headers.extend(languages.iter().map(|language| language.to_uppercase()));
+
+        headers
+    }
+
+    /// Language cells: `local`, `available (3)`, `missing`
+    pub fn get_table_rows(reports: &[CoverageReport]) -> Vec<Vec<String>> {
+        get_rows(reports, |coverage| match coverage.status {
+            CoverageStatus::Available => vec![format!("{} ({})", coverage.status.name(), coverage.available)],
+            _ => vec![coverage.status.name().to_string()]
+        })
+    }
+
+    /**
+    CSV with header row: `series,season,episode,title` and `<language>_status`, `<language>_available`
+    columns per language of the first report.
+    */
+    pub fn to_csv(reports: &[CoverageReport]) -> String {
+        let languages: &[String] = reports.first().map(|report| report.languages.as_slice()).unwrap_or(&[]);
+
+        let mut headers: Vec<String> = ["series", "season", "episode", "title"].iter()
+                                                                                 .map(|header| header.to_string())
+                                                                                 .collect();
+
+        for language in languages {
+            headers.push(format!("{}_status", language));
+            headers.push(format!("{}_available", language));
+        }
+
+        let rows = get_rows(reports, |coverage| {
+            vec![coverage.status.name().to_string(), coverage.available.to_string()]
+        });
+
+        std::iter::once(&headers).chain(rows.iter()).map(|row| get_csv_line(row)).collect()
+    }
+
+    /// Episode columns and cells of every report language
+    fn get_rows(reports: &[CoverageReport], get_cells: impl Fn(&LanguageCoverage) -> Vec<String>) -> Vec<Vec<String>> {
+        let unknown = LanguageCoverage { available: 0, local: false, status: CoverageStatus::Missing };
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+
+        for report in reports {
+            for episode in &report.episodes {
+                let mut row = vec![
+                    report.series.clone(),
+                    episode.season.to_string(),
+                    episode.episode.to_string(),
+                    episode.title.clone()
+                ];
+
+                row.extend(report.languages.iter().flat_map(|language| {
+                    get_cells(episode.languages.get(language).unwrap_or(&unknown))
+                }));
+
+                rows.push(row);
+            }
+        }
+
+        rows
+    }
+
+    fn get_csv_line(values: &[String]) -> String {
+        let line = values.iter().map(|value| {
+            if value.contains(CSV_SEPARATOR) || value.contains('"') || value.contains('\n') {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            } else {
+                value.clone()
+            }
+        }).collect::<Vec<String>>().join(CSV_SEPARATOR);
+
+        format!("{}\n", line)
+    }
+
+    fn get_episode_coverage(season: u8, episode: u16, title: &str, subtitle_counts: &BTreeMap<String, u32>,
+                            local_episode: Option<&LocalEpisode>, languages: &[String]) -> EpisodeCoverage {
+        let coverage = languages.iter().map(|language| {
+            let available = subtitle_counts.iter()
+                                           .find(|(code, _)| get_language_code(code, LanguageCodeStyle::Iso639_2) == Some(language))
+                                           .map(|(_, count)| *count)
+                                           .unwrap_or(0);
+            let local = local_episode.is_some_and(|local_episode| local_episode.languages.contains(language));
+
+            let status = if local {
+                CoverageStatus::Local
+            } else if available > 0 {
+                CoverageStatus::Available
+            } else {
+                CoverageStatus::Missing
+            };
+
+            (language.clone(), LanguageCoverage { available, local, status })
+        }).collect();
+
+        EpisodeCoverage {
+            season,
+            episode,
+            title: title.to_string(),
+            video: local_episode.map(|local_episode| local_episode.video.clone()),
+            languages: coverage
+        }
+    }
+
+    /// Site language codes (ISO 639-2): `en` -> `eng`
+    fn get_site_languages(languages: &[String]) -> OperationResult<Vec<String>> {
+        languages.iter().map(|language| {
+            get_language_code(language, LanguageCodeStyle::Iso639_2)
+                .map(|code| code.to_string())
+                .ok_or_else(|| OperationError::InvalidArgument(format!("unsupported language '{}'", language)))
+        }).collect()
+    }
+}
@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod coverage_tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    use reqwest::Client;
+    use tempfile::TempDir;
+
+    use crate::coverage::coverage::{CoverageStatus, find_local_episodes, get_coverage_report, get_library_coverage,
+                                    get_table_headers, get_table_rows, LanguageSummary, to_csv};
+    use crate::domain::domain::{Episode, Season, Series};
+    use crate::error::error::OperationError;
+    use crate::mock_server::mock_server::{MockResponse, MockServer};
+
+    #[test]
+    fn local_episodes_should_have_subtitle_languages() {
+        let directory = get_library();
+
+        let episodes = find_local_episodes(directory.path(), &get_languages()).unwrap();
+
+        assert_eq!(vec![(1, 1), (1, 2), (1, 4), (1, 5)],
+                   episodes.iter().map(|episode| (episode.season, episode.episode)).collect::<Vec<(u8, u16)>>());
+
+        assert_eq!("Adventure Time", episodes[0].title);
+        assert_eq!(vec!["rus"], episodes[0].languages);
+        assert!(episodes[1].languages.is_empty());
+        assert_eq!(episodes[2].video, episodes[3].video);
+    }
+
+    #[test]
+    fn report_should_contain_status_per_episode_and_language() {
+        let directory = get_library();
+        let local_episodes = find_local_episodes(directory.path(), &get_languages()).unwrap();
+
+        let report = get_coverage_report(&get_series(), &get_languages(), &local_episodes).unwrap();
+
+        assert_eq!("Adventure Time", report.series);
+        assert_eq!(vec!["rus", "eng"], report.languages);
+
+        let statuses: Vec<(u8, u16, CoverageStatus, CoverageStatus)> = report.episodes.iter()
+            .map(|episode| (episode.season, episode.episode, episode.languages["rus"].status, episode.languages["eng"].status))
+            .collect();
+
+        assert_eq!(vec![
+            (1, 1, CoverageStatus::Local, CoverageStatus::Available),
+            (1, 2, CoverageStatus::Available, CoverageStatus::Missing),
+            (1, 3, CoverageStatus::Missing, CoverageStatus::Missing),
+            (1, 4, CoverageStatus::Missing, CoverageStatus::Missing),
+            (1, 5, CoverageStatus::Missing, CoverageStatus::Missing)
+        ], statuses);
+
+        assert!(report.episodes[2].video.is_none());
+        assert_eq!("", report.episodes[3].title);
+
+        assert_eq!(LanguageSummary { local: 1, available: 1, missing: 3 }, report.summary["rus"]);
+        assert_eq!(LanguageSummary { local: 0, available: 1, missing: 4 }, report.summary["eng"]);
+    }
+
+    #[test]
+    fn report_should_be_written_as_csv_and_table() {
+        let reports = vec![get_coverage_report(&get_series(), &get_languages(), &[]).unwrap()];
+
+        let csv = to_csv(&reports);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!("series,season,episode,title,rus_status,rus_available,eng_status,eng_available", lines[0]);
+        assert_eq!("Adventure Time,1,1,Slumber Party Panic,available,3,available,1", lines[1]);
+        assert_eq!("Adventure Time,1,3,\"Prisoners of Love, \"\"Part 1\"\"\",missing,0,missing,0", lines[3]);
+
+        assert_eq!(vec!["SERIES", "SEASON", "EPISODE", "TITLE", "RUS", "ENG"], get_table_headers(&reports[0].languages));
+        assert_eq!(vec!["Adventure Time", "1", "2", "Trouble in Lumpy Space", "available (1)", "missing"],
+                   get_table_rows(&reports)[1]);
+    }
+
+    #[test]
+    fn unsupported_language_should_be_rejected() {
+        match get_coverage_report(&get_series(), &[String::from("xx")], &[]) {
+            Err(OperationError::InvalidArgument(_)) => {}
+            _ => panic!("error expected")
+        }
+    }
+
+    #[tokio::test]
+    async fn library_coverage_should_skip_series_failed_to_load() {
+        let directory = get_library();
+        fs::write(directory.path().join("Broken.Show.S01E01.mkv"), "").unwrap();
+
+        let server = MockServer::start();
+        server.mock(r"^/en/search2/sublanguageid-rus,eng/moviename-adventure\+time/", MockResponse::fixture("series-search-page.html"));
+        server.mock(r"^/en/search2/", MockResponse::status(503));
+        server.mock(r"^/en/ssearch/sublanguageid-rus/idmovie-101356$", MockResponse::fixture("series-page.html"));
+        server.mock(r"^/en/ssearch/sublanguageid-eng/idmovie-101356$", MockResponse::fixture("series-page.html"));
+
+        let reports = get_library_coverage(&Client::new(), &server.url(), "en", directory.path(), &get_languages())
+            .await.unwrap();
+
+        assert_eq!(vec!["Adventure Time"], reports.iter().map(|report| report.series.as_str()).collect::<Vec<&str>>());
+    }
+
+    #[tokio::test]
+    async fn library_coverage_should_be_built_for_every_series() {
+        let directory = get_library();
+        fs::write(directory.path().join("Unknown.Show.S01E01.mkv"), "").unwrap();
+
+        let server = MockServer::start();
+        server.mock(r"^/en/search2/sublanguageid-rus,eng/moviename-adventure\+time/", MockResponse::fixture("series-search-page.html"));
+        server.mock(r"^/en/search2/", MockResponse::html(
+            "<html><body><table id=\"search_results\"><tbody></tbody></table></body></html>"
+        ));
+        server.mock(r"^/en/ssearch/sublanguageid-rus/idmovie-101356$", MockResponse::fixture("series-page.html"));
+        server.mock(r"^/en/ssearch/sublanguageid-eng/idmovie-101356$", MockResponse::fixture("series-page.html"));
+
+        let reports = get_library_coverage(&Client::new(), &server.url(), "en", directory.path(), &get_languages())
+            .await.unwrap();
+
+        assert_eq!(1, reports.len());
+        assert_eq!(CoverageStatus::Local, reports[0].episodes[0].languages["rus"].status);
+        assert_eq!(CoverageStatus::Available, reports[0].episodes[0].languages["eng"].status);
+    }
+
+    /// S01E01 with russian subtitles, S01E02 without subtitles, S01E04-E05 in one file
+    fn get_library() -> TempDir {
+        let directory = TempDir::new().unwrap();
+        let season_directory = directory.path().join("Adventure Time").join("Season 01");
+        fs::create_dir_all(&season_directory).unwrap();
+
+        fs::write(season_directory.join("Adventure.Time.S01E01.720p.mkv"), "").unwrap();
+        fs::write(season_directory.join("Adventure.Time.S01E01.720p.ru.srt"), "").unwrap();
+        fs::write(season_directory.join("Adventure.Time.S01E02.720p.mkv"), "").unwrap();
+        fs::write(season_directory.join("Adventure.Time.S01E04E05.720p.mkv"), "").unwrap();
+        fs::write(directory.path().join("movie.mkv"), "").unwrap();
+
+        directory
+    }
+
+    fn get_languages() -> Vec<String> {
+        vec![String::from("ru"), String::from("eng")]
+    }
+
+    fn get_series() -> Series {
+        let episode = |episode: u16, title: &str, rus: u32, eng: u32| Episode {
+            season: 1,
+            episode,
+            title: title.to_string(),
+            air_year: Some(2010),
+            imdb_id: None,
+            subtitle_counts: BTreeMap::from([(String::from("rus"), rus), (String::from("eng"), eng)])
+        };
+
+        Series {
+            title: String::from("Adventure Time"),
+            imdb_id: Some(String::from("tt1305826")),
+            seasons: vec![Season {
+                number: 1,
+                episodes: vec![
+                    episode(1, "Slumber Party Panic", 3, 1),
+                    episode(2, "Trouble in Lumpy Space", 1, 0),
+                    episode(3, "Prisoners of Love, \"Part 1\"", 0, 0)
+                ]
+            }]
+        }
+    }
+}
@@ -0,0 +1,49 @@
+pub mod cue {
+    use serde::{Deserialize, Serialize};
+
+    pub type Cues = Vec<Cue>;
+
+    /// Format independent subtitle cue
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct Cue {
+        pub index: u32,
+
+        /// Start time in milliseconds
+        pub start: u64,
+
+        /// End time in milliseconds
+        pub end: u64,
+
+        /**
+        Cue text. Lines are separated by `\n`, basic styling is kept
+        as `<i>`, `<b>` and `<u>` tags (like in SRT).
+        */
+        pub text: String
+    }
+
+    impl Cue {
+        pub fn new(index: u32, start: u64, end: u64, text: &str) -> Cue {
+            Cue {
+                index,
+                start,
+                end,
+                text: text.to_string()
+            }
+        }
+
+        pub fn duration(&self) -> u64 {
+            self.end.saturating_sub(self.start)
+        }
+
+        pub fn lines(&self) -> Vec<&str> {
+            self.text.split('\n').collect()
+        }
+    }
+
+    /// Assign sequential indexes starting from 1
+    pub fn renumber(cues: &mut [Cue]) {
+        for (position, cue) in cues.iter_mut().enumerate() {
+            cue.index = position as u32 + 1;
+        }
+    }
+}
@@ -1,6 +1,10 @@
 pub mod domain {
+    use std::collections::BTreeMap;
+
     use serde::{Deserialize, Serialize};
 
+    use crate::format::format::{FormatDetection, SubtitleFormat};
+
     pub type SubtitleSearchResults = Vec<SubtitleSearchResultItem>;
 
     #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -15,6 +19,81 @@ pub mod domain {
         pub details_url: String,
 
         pub season: u8,
-        pub episode: u16
+        pub episode: u16,
+
+        /// Site marks subtitles as made for hearing impaired
+        pub hearing_impaired: bool,
+
+        /// Release name (video file name) subtitles were made for, see `release::parse_release_name`
+        pub release_name: Option<String>,
+
+        /// Download count
+        pub downloads: u32,
+
+        /// Subtitle rating, 0.0 - 10.0
+        pub rating: f32,
+
+        /// Video frame rate subtitles were made for
+        pub fps: Option<f32>,
+
+        /// Uploader name, `None` for anonymous uploads
+        pub uploader: Option<String>,
+
+        /// Uploader rank shown by the site: `Trusted`, `Gold member`, `Administrator`, ...
+        pub uploader_rank: Option<String>,
+
+        /// IMDb id: `tt7342228`
+        pub imdb_id: Option<String>,
+
+        /// Subtitle format shown by the site
+        pub format: Option<SubtitleFormat>
+    }
+
+    /// Subtitle file received from the site, labeled with detected format
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    pub struct SubtitleFile {
+        /// File name inside the archive
+        pub filename: String,
+
+        pub content: Vec<u8>,
+
+        pub format: FormatDetection
+    }
+
+    /// TV series seasons and episodes as listed on the site series page
+    #[derive(Serialize, Deserialize, Clone, Debug, Default)]
+    pub struct Series {
+        pub title: String,
+
+        /// IMDb id: `tt1305826`
+        pub imdb_id: Option<String>,
+
+        /// Ordered by season number
+        pub seasons: Vec<Season>
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    pub struct Season {
+        pub number: u8,
+
+        /// Ordered by episode number
+        pub episodes: Vec<Episode>
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    pub struct Episode {
+        pub season: u8,
+        pub episode: u16,
+
+        pub title: String,
+
+        /// Year the episode was aired first
+        pub air_year: Option<u16>,
+
+        /// Episode IMDb id: `tt1512316`
+        pub imdb_id: Option<String>,
+
+        /// Subtitle count per language (`rus` -> 3), requested languages only
+        pub subtitle_counts: BTreeMap<String, u32>
     }
 }
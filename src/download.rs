@@ -0,0 +1,98 @@
+pub mod download {
+    use std::io::{Cursor, Read};
+
+    use zip::ZipArchive;
+
+    use crate::domain::domain::SubtitleFile;
+    use crate::error::error::OperationError;
+    use crate::format::format::detect_file_format;
+    use crate::types::types::OperationResult;
+
+    const ZIP_SIGNATURE: &[u8] = b"PK\x03\x04";
+    const NFO_EXTENSION: &str = ".nfo";
+
+    /// Subtitle files are small, bigger entries are skipped
+    pub const MAX_ENTRY_SIZE: u64 = 16 * 1024 * 1024;
+
+    /**
+    Get subtitle files from downloaded content.
+
+    The site serves zip archives (subtitles + `.nfo`), plain files are returned as is
+    with `fallback_filename`.
+    */
+    pub fn get_subtitle_files(content: &[u8], fallback_filename: &str) -> OperationResult<Vec<SubtitleFile>> {
+        if content.starts_with(ZIP_SIGNATURE) {
+            extract_subtitle_files(content)
+
+        } else {
+            debug!("content is not an archive, use it as subtitle file");
+            Ok(vec![get_subtitle_file(fallback_filename, content.to_vec())])
+        }
+    }
+
+    fn extract_subtitle_files(content: &[u8]) -> OperationResult<Vec<SubtitleFile>> {
+        let mut archive = ZipArchive::new(Cursor::new(content)).map_err(|e| {
+            error!("unable to open zip archive: {}", e);
+            OperationError::ArchiveError
+        })?;
+
+        let mut results: Vec<SubtitleFile> = Vec::new();
+
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index).map_err(|e| {
+                error!("unable to read archive entry: {}", e);
+                OperationError::ArchiveError
+            })?;
+
+            if entry.is_dir() {
+                debug!("skip archive directory '{}'", entry.name());
+                continue;
+            }
+
+            // Only file name is kept: `../../x` and `/etc/x` must not point outside of output directory
+            let filename = match entry.enclosed_name().and_then(|path| path.file_name()) {
+                Some(filename) => filename.to_string_lossy().to_string(),
+                None => {
+                    warn!("skip archive entry with unsafe name '{}'", entry.name());
+                    continue;
+                }
+            };
+
+            if filename.to_lowercase().ends_with(NFO_EXTENSION) {
+                debug!("skip archive entry '{}'", filename);
+                continue;
+            }
+
+            if entry.size() > MAX_ENTRY_SIZE {
+                warn!("skip archive entry '{}', size {} is over limit", filename, entry.size());
+                continue;
+            }
+
+            let mut data = Vec::new();
+            (&mut entry).take(MAX_ENTRY_SIZE + 1).read_to_end(&mut data)?;
+
+            // Declared size may be forged
+            if data.len() as u64 > MAX_ENTRY_SIZE {
+                warn!("skip archive entry '{}', content is over size limit", filename);
+                continue;
+            }
+
+            let subtitle_file = get_subtitle_file(&filename, data);
+            info!("subtitle file '{}', format {:?}", filename, subtitle_file.format);
+
+            results.push(subtitle_file);
+        }
+
+        Ok(results)
+    }
+
+    fn get_subtitle_file(filename: &str, content: Vec<u8>) -> SubtitleFile {
+        let format = detect_file_format(filename, &content);
+
+        SubtitleFile {
+            filename: filename.to_string(),
+            content,
+            format
+        }
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod download_tests {
+    use std::io::{Cursor, Write};
+
+    use zip::write::FileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    use crate::download::download::{get_subtitle_files, MAX_ENTRY_SIZE};
+    use crate::format::format::SubtitleFormat;
+
+    #[test]
+    fn archive_entries_should_be_labeled_by_content() {
+        let archive = get_archive(&[
+            ("adventure-time.srt", "[Script Info]\nScriptType: v4.00+\n[V4+ Styles]\n"),
+            ("adventure-time.nfo", "release info"),
+            ("extra.txt", "1\n00:00:01,000 --> 00:00:02,000\nHello\n")
+        ]);
+
+        let files = get_subtitle_files(&archive, "subtitles").expect("files expected");
+
+        assert_eq!(2, files.len());
+
+        assert_eq!("adventure-time.srt", files[0].filename);
+        assert_eq!(SubtitleFormat::Ass, files[0].format.format);
+
+        assert_eq!("extra.txt", files[1].filename);
+        assert_eq!(SubtitleFormat::Srt, files[1].format.format);
+    }
+
+    #[test]
+    fn plain_content_should_be_returned_as_single_file() {
+        let files = get_subtitle_files(b"WEBVTT\n", "movie.vtt").expect("files expected");
+
+        assert_eq!(1, files.len());
+        assert_eq!("movie.vtt", files[0].filename);
+        assert_eq!(SubtitleFormat::WebVtt, files[0].format.format);
+    }
+
+    #[test]
+    fn broken_archive_should_be_rejected() {
+        assert!(get_subtitle_files(b"PK\x03\x04broken", "x").is_err());
+    }
+
+    #[test]
+    fn hostile_archive_entries_should_be_skipped_or_renamed() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\nHello\n";
+
+        let archive = get_archive(&[
+            ("../../evil.srt", srt),
+            ("/etc/passwd.srt", srt),
+            ("subs/../../outside.srt", srt),
+            ("subs/nested/movie.srt", srt)
+        ]);
+
+        let files = get_subtitle_files(&archive, "subtitles").expect("files expected");
+
+        assert_eq!(vec!["movie.srt"], files.iter().map(|file| file.filename.as_str()).collect::<Vec<&str>>());
+    }
+
+    #[test]
+    fn oversized_archive_entries_should_be_skipped() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        writer.start_file("bomb.srt", options).unwrap();
+        writer.write_all(&vec![b'a'; MAX_ENTRY_SIZE as usize + 1]).unwrap();
+
+        writer.start_file("movie.srt", options).unwrap();
+        writer.write_all(b"WEBVTT\n").unwrap();
+
+        let archive = writer.finish().unwrap().into_inner();
+
+        let files = get_subtitle_files(&archive, "subtitles").expect("files expected");
+
+        assert_eq!(1, files.len());
+        assert_eq!("movie.srt", files[0].filename);
+    }
+
+    fn get_archive(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+
+        for (name, content) in entries {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+
+        writer.finish().unwrap().into_inner()
+    }
+}
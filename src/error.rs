@@ -9,9 +9,24 @@ pub mod error {
         #[error("HTML parse error")]
         HtmlParseError,
 
+        #[error("Subtitle parse error")]
+        SubtitleParseError,
+
+        #[error("Archive error")]
+        ArchiveError,
+
+        #[error("Invalid argument: {0}")]
+        InvalidArgument(String),
+
         #[error("Invalid login or password")]
         Authentication,
 
+        #[error("Subtitles are already uploaded: {0}")]
+        DuplicateSubtitles(String),
+
+        #[error("Upload rejected: {0}")]
+        UploadRejected(String),
+
         #[error(transparent)]
         IOError(#[from] std::io::Error)
     }
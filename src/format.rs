@@ -0,0 +1,201 @@
+pub mod format {
+    use std::sync::OnceLock;
+
+    use encoding::{DecoderTrap, Encoding};
+    use encoding::all::{UTF_16BE, UTF_16LE};
+    use regex::Regex;
+    use serde::{Deserialize, Serialize};
+
+    /// How many bytes are inspected by format detection
+    const SNIFF_LIMIT: usize = 64 * 1024;
+
+    const SRT_TIMESTAMP_PATTERN: &str =
+        "(?m)^\\s*\\d{1,2}:\\d{2}:\\d{2}([,.])\\d{1,3}\\s*-->\\s*\\d{1,2}:\\d{2}:\\d{2}[,.]\\d{1,3}";
+    const SRT_INDEXED_TIMESTAMP_PATTERN: &str =
+        "(?m)^\\s*\\d+\\s*\\r?\\n\\s*\\d{1,2}:\\d{2}:\\d{2},\\d{1,3}\\s*-->";
+    const MICRODVD_LINE_PATTERN: &str = "^\\{\\d+\\}\\{\\d*\\}";
+
+    static SRT_TIMESTAMP_REGEX: OnceLock<Regex> = OnceLock::new();
+    static SRT_INDEXED_TIMESTAMP_REGEX: OnceLock<Regex> = OnceLock::new();
+    static MICRODVD_LINE_REGEX: OnceLock<Regex> = OnceLock::new();
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum SubtitleFormat {
+        Srt,
+        Ass,
+        Ssa,
+        WebVtt,
+        Sami,
+        MicroDvd,
+        Ttml,
+        Unknown
+    }
+
+    impl SubtitleFormat {
+        /// Usual file extension, without dot
+        pub fn extension(&self) -> &'static str {
+            match self {
+                SubtitleFormat::Srt => "srt",
+                SubtitleFormat::Ass => "ass",
+                SubtitleFormat::Ssa => "ssa",
+                SubtitleFormat::WebVtt => "vtt",
+                SubtitleFormat::Sami => "smi",
+                SubtitleFormat::MicroDvd => "sub",
+                SubtitleFormat::Ttml => "ttml",
+                SubtitleFormat::Unknown => "txt"
+            }
+        }
+
+        /// Format by file extension or site format name (`srt`, `ssa`, `dfxp`, ...)
+        pub fn from_extension(extension: &str) -> SubtitleFormat {
+            match extension.trim_start_matches('.').to_lowercase().as_str() {
+                "srt" => SubtitleFormat::Srt,
+                "ass" => SubtitleFormat::Ass,
+                "ssa" => SubtitleFormat::Ssa,
+                "vtt" => SubtitleFormat::WebVtt,
+                "smi" | "sami" => SubtitleFormat::Sami,
+                "sub" => SubtitleFormat::MicroDvd,
+                "ttml" | "dfxp" | "xml" => SubtitleFormat::Ttml,
+                _ => SubtitleFormat::Unknown
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub struct FormatDetection {
+        pub format: SubtitleFormat,
+
+        /// From 0.0 (guess) to 1.0 (format signature found)
+        pub confidence: f32
+    }
+
+    /// Detect subtitle format by content
+    pub fn detect_format(bytes: &[u8]) -> FormatDetection {
+        let text = get_sniff_text(bytes);
+        let trimmed_text = text.trim_start();
+        let lowercase_text = text.to_lowercase();
+
+        let mut candidates: Vec<FormatDetection> = Vec::new();
+
+        if trimmed_text.starts_with("WEBVTT") {
+            candidates.push(get_detection(SubtitleFormat::WebVtt, 1.0));
+        }
+
+        if lowercase_text.contains("[script info]") {
+            if lowercase_text.contains("[v4+ styles]") || lowercase_text.contains("v4.00+") {
+                candidates.push(get_detection(SubtitleFormat::Ass, 1.0));
+
+            } else if lowercase_text.contains("[v4 styles]") {
+                candidates.push(get_detection(SubtitleFormat::Ssa, 1.0));
+
+            } else {
+                candidates.push(get_detection(SubtitleFormat::Ass, 0.8));
+            }
+        }
+
+        if lowercase_text.contains("<sami>") {
+            candidates.push(get_detection(SubtitleFormat::Sami, 1.0));
+
+        } else if lowercase_text.contains("<sync start=") {
+            candidates.push(get_detection(SubtitleFormat::Sami, 0.7));
+        }
+
+        if lowercase_text.contains("<tt ") || lowercase_text.contains("<tt>") || lowercase_text.contains(":tt ") {
+            if lowercase_text.contains("http://www.w3.org/ns/ttml") ||
+               lowercase_text.contains("http://www.w3.org/2006/10/ttaf1") {
+                candidates.push(get_detection(SubtitleFormat::Ttml, 1.0));
+
+            } else {
+                candidates.push(get_detection(SubtitleFormat::Ttml, 0.6));
+            }
+        }
+
+        let microdvd_ratio = get_microdvd_line_ratio(&text);
+
+        if microdvd_ratio > 0.5 {
+            candidates.push(get_detection(SubtitleFormat::MicroDvd, microdvd_ratio));
+        }
+
+        let srt_indexed_timestamp_pattern = SRT_INDEXED_TIMESTAMP_REGEX.get_or_init(|| Regex::new(SRT_INDEXED_TIMESTAMP_PATTERN).unwrap());
+        let srt_timestamp_pattern = SRT_TIMESTAMP_REGEX.get_or_init(|| Regex::new(SRT_TIMESTAMP_PATTERN).unwrap());
+
+        if srt_indexed_timestamp_pattern.is_match(&text) {
+            candidates.push(get_detection(SubtitleFormat::Srt, 0.95));
+
+        } else if let Some(groups) = srt_timestamp_pattern.captures(&text) {
+            if &groups[1] == "," {
+                candidates.push(get_detection(SubtitleFormat::Srt, 0.8));
+
+            } else {
+                candidates.push(get_detection(SubtitleFormat::WebVtt, 0.5));
+                candidates.push(get_detection(SubtitleFormat::Srt, 0.4));
+            }
+        }
+
+        let result = candidates.into_iter()
+            .fold(get_detection(SubtitleFormat::Unknown, 0.0), |best, candidate| {
+                if candidate.confidence > best.confidence { candidate } else { best }
+            });
+
+        debug!("detected format: {:?}", result);
+
+        result
+    }
+
+    /**
+    Detect format by content, falling back to file extension
+    (with low confidence) when content is not recognized.
+    */
+    pub fn detect_file_format(filename: &str, bytes: &[u8]) -> FormatDetection {
+        let detection = detect_format(bytes);
+
+        if detection.format == SubtitleFormat::Unknown {
+            let extension = filename.rsplit('.').next().unwrap_or("");
+            let format = SubtitleFormat::from_extension(extension);
+
+            if format != SubtitleFormat::Unknown {
+                return get_detection(format, 0.3);
+            }
+        }
+
+        detection
+    }
+
+    fn get_detection(format: SubtitleFormat, confidence: f32) -> FormatDetection {
+        FormatDetection { format, confidence }
+    }
+
+    fn get_sniff_text(bytes: &[u8]) -> String {
+        let bytes = &bytes[..bytes.len().min(SNIFF_LIMIT)];
+
+        if bytes.starts_with(&[0xFF, 0xFE]) {
+            UTF_16LE.decode(&bytes[2..], DecoderTrap::Replace).unwrap_or_default()
+
+        } else if bytes.starts_with(&[0xFE, 0xFF]) {
+            UTF_16BE.decode(&bytes[2..], DecoderTrap::Replace).unwrap_or_default()
+
+        } else if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            String::from_utf8_lossy(&bytes[3..]).to_string()
+
+        } else {
+            String::from_utf8_lossy(bytes).to_string()
+        }
+    }
+
+    fn get_microdvd_line_ratio(text: &str) -> f32 {
+        let microdvd_line_pattern = MICRODVD_LINE_REGEX.get_or_init(|| Regex::new(MICRODVD_LINE_PATTERN).unwrap());
+
+        let lines: Vec<&str> = text.lines()
+                                   .map(|line| line.trim())
+                                   .filter(|line| !line.is_empty())
+                                   .collect();
+
+        if lines.is_empty() {
+            return 0.0;
+        }
+
+        let matched = lines.iter().filter(|line| microdvd_line_pattern.is_match(line)).count();
+
+        matched as f32 / lines.len() as f32
+    }
+}
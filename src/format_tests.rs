@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod format_tests {
+    use crate::format::format::{detect_file_format, detect_format, SubtitleFormat};
+
+    #[test]
+    fn srt_should_be_detected_by_timestamps() {
+        let content = "1\r\n00:00:01,000 --> 00:00:02,500\r\nHello\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,000\r\nBye\r\n";
+
+        let detection = detect_format(content.as_bytes());
+
+        assert_eq!(SubtitleFormat::Srt, detection.format);
+        assert!(detection.confidence > 0.9);
+    }
+
+    #[test]
+    fn signatures_should_be_detected() {
+        let samples = vec![
+            ("WEBVTT\n\n00:00:01.000 --> 00:00:02.000\nHello", SubtitleFormat::WebVtt),
+            ("[Script Info]\nScriptType: v4.00+\n\n[V4+ Styles]\n", SubtitleFormat::Ass),
+            ("[Script Info]\nScriptType: v4.00\n\n[V4 Styles]\n", SubtitleFormat::Ssa),
+            ("<SAMI><BODY><SYNC Start=1000><P>Hello</SAMI>", SubtitleFormat::Sami),
+            ("{0}{25}Hello\n{30}{60}World|second line\n", SubtitleFormat::MicroDvd),
+            ("<?xml version=\"1.0\"?><tt xmlns=\"http://www.w3.org/ns/ttml\"></tt>", SubtitleFormat::Ttml)
+        ];
+
+        for (content, format) in samples {
+            let detection = detect_format(content.as_bytes());
+            assert_eq!(format, detection.format, "{}", content);
+            assert!(detection.confidence >= 0.9, "{}", content);
+        }
+    }
+
+    #[test]
+    fn utf16_content_with_bom_should_be_detected() {
+        let mut content = vec![0xFF, 0xFE];
+
+        for unit in "WEBVTT\n".encode_utf16() {
+            content.extend_from_slice(&unit.to_le_bytes());
+        }
+
+        assert_eq!(SubtitleFormat::WebVtt, detect_format(&content).format);
+    }
+
+    #[test]
+    fn unknown_content_should_fall_back_to_extension() {
+        let detection = detect_format(b"just some text");
+        assert_eq!(SubtitleFormat::Unknown, detection.format);
+        assert_eq!(0.0, detection.confidence);
+
+        let detection = detect_file_format("movie.srt", b"just some text");
+        assert_eq!(SubtitleFormat::Srt, detection.format);
+        assert!(detection.confidence < 0.5);
+    }
+
+    #[test]
+    fn content_should_win_over_extension() {
+        let detection = detect_file_format("movie.srt", b"[Script Info]\n[V4+ Styles]\n");
+        assert_eq!(SubtitleFormat::Ass, detection.format);
+    }
+}
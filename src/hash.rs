@@ -0,0 +1,63 @@
+pub mod hash {
+    use std::fs::File;
+    use std::io::{Read, Seek, SeekFrom};
+    use std::path::Path;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::error::error::OperationError;
+    use crate::types::types::OperationResult;
+
+    /// Hashed chunk size at the start and at the end of the file
+    const CHUNK_SIZE: u64 = 65536;
+
+    /// Movie hash used by the site to find subtitles made for the exact video file
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct MovieHash {
+        /// 16 hex digits
+        pub hash: String,
+
+        /// File size, bytes
+        pub size: u64
+    }
+
+    pub fn get_movie_hash(path: &Path) -> OperationResult<MovieHash> {
+        info!("get movie hash for '{}'", path.display());
+
+        let mut file = File::open(path)?;
+        let size = file.metadata()?.len();
+
+        get_movie_hash_from_reader(&mut file, size)
+    }
+
+    /**
+    File size plus 64-bit little-endian words of the first and the last 64 KB,
+    with wrapping arithmetic (OpenSubtitles hash).
+    */
+    pub fn get_movie_hash_from_reader<R: Read + Seek>(reader: &mut R, size: u64) -> OperationResult<MovieHash> {
+        if size < CHUNK_SIZE {
+            error!("file is too small for movie hash: {} bytes", size);
+            return Err(OperationError::InvalidArgument(format!("file is too small: {} bytes", size)));
+        }
+
+        let mut hash = size;
+
+        for offset in [0, size - CHUNK_SIZE].iter() {
+            reader.seek(SeekFrom::Start(*offset))?;
+
+            let mut chunk = vec![0_u8; CHUNK_SIZE as usize];
+            reader.read_exact(&mut chunk)?;
+
+            for word in chunk.chunks_exact(8) {
+                let mut bytes = [0_u8; 8];
+                bytes.copy_from_slice(word);
+                hash = hash.wrapping_add(u64::from_le_bytes(bytes));
+            }
+        }
+
+        let result = MovieHash { hash: format!("{:016x}", hash), size };
+        debug!("movie hash: {:?}", result);
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod hash_tests {
+    use std::io::Cursor;
+
+    use crate::error::error::OperationError;
+    use crate::hash::hash::get_movie_hash_from_reader;
+
+    #[test]
+    fn hash_of_zero_filled_file_should_be_equal_to_size() {
+        let content = vec![0_u8; 200_000];
+
+        let result = get_movie_hash_from_reader(&mut Cursor::new(&content), content.len() as u64).unwrap();
+
+        assert_eq!("0000000000030d40", result.hash);
+        assert_eq!(200_000, result.size);
+    }
+
+    #[test]
+    fn head_and_tail_words_should_be_summed() {
+        let mut content = vec![0_u8; 200_000];
+        content[0] = 1;
+        content[199_992] = 2;
+        // middle part is ignored
+        content[100_000] = 0xff;
+
+        let result = get_movie_hash_from_reader(&mut Cursor::new(&content), content.len() as u64).unwrap();
+
+        assert_eq!(format!("{:016x}", 200_000 + 1 + 2), result.hash);
+    }
+
+    #[test]
+    fn sum_should_wrap() {
+        let content = vec![0xff_u8; 65_536];
+
+        let result = get_movie_hash_from_reader(&mut Cursor::new(&content), content.len() as u64).unwrap();
+
+        // 2 * 8192 words of u64::MAX (-1 each) plus size
+        assert_eq!(format!("{:016x}", 65_536_u64.wrapping_sub(16_384)), result.hash);
+    }
+
+    #[test]
+    fn small_file_should_be_rejected() {
+        let content = vec![0_u8; 1000];
+
+        match get_movie_hash_from_reader(&mut Cursor::new(&content), content.len() as u64) {
+            Err(OperationError::InvalidArgument(_)) => {}
+            _ => panic!("error expected")
+        }
+    }
+}
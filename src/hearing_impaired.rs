@@ -0,0 +1,107 @@
+pub mod hearing_impaired {
+    use regex::Regex;
+
+    use crate::cue::cue::{Cue, Cues, renumber};
+
+    /// `[door slams]`, `(laughs)`, may span several lines
+    const SOUND_DESCRIPTION_PATTERN: &str = "\\[[^\\]]*\\]|\\([^)]*\\)";
+
+    const LYRICS_PATTERN: &str = "[♪♫][^♪♫]*[♪♫]";
+    const LYRICS_LINE_PATTERN: &str = "^\\s*(<[^>]*>)*\\s*[♪♫]";
+
+    /// `# Happy birthday #`: `#` marks lyrics only when it opens and closes the whole line
+    const HASH_LYRICS_LINE_PATTERN: &str = "^\\s*(<[^>]*>)*\\s*#[^#]*#\\s*(</[^>]*>)*\\s*$";
+
+    /// `JOHN:`, `MAN #2:`, `- NARRATOR (V.O.):`, `DR. SMITH:` at line start, up to two words
+    const SPEAKER_LABEL_PATTERN: &str =
+        "^(\\s*(?:<[^>]*>)*\\s*-?\\s*)(?:[A-Z][A-Z0-9.'&-]*(?: [A-Z][A-Z0-9.'&-]*)?|[А-ЯЁ][А-ЯЁ0-9.'&-]*(?: [А-ЯЁ][А-ЯЁ0-9.'&-]*)?)(?: #\\d+)?\\s*:\\s*";
+
+    const EMPTY_TAGS_PATTERN: &str = "<([ibu])>\\s*</([ibu])>";
+
+    /// Max line length when lines are rebalanced
+    const MAX_LINE_LENGTH: usize = 42;
+
+    /**
+    Remove SDH/HI content: sound descriptions, lyrics and uppercase speaker labels.
+
+    Cues that become empty are dropped, changed cues get rebalanced lines, cues are renumbered.
+    */
+    pub fn remove_hearing_impaired(cues: &[Cue]) -> Cues {
+        info!("remove hearing impaired annotations from {} cues", cues.len());
+
+        let sound_description_pattern = Regex::new(SOUND_DESCRIPTION_PATTERN).unwrap();
+        let lyrics_pattern = Regex::new(LYRICS_PATTERN).unwrap();
+        let lyrics_line_pattern = Regex::new(LYRICS_LINE_PATTERN).unwrap();
+        let hash_lyrics_line_pattern = Regex::new(HASH_LYRICS_LINE_PATTERN).unwrap();
+        let speaker_label_pattern = Regex::new(SPEAKER_LABEL_PATTERN).unwrap();
+        let empty_tags_pattern = Regex::new(EMPTY_TAGS_PATTERN).unwrap();
+
+        let mut results: Cues = Vec::new();
+
+        for cue in cues {
+            let without_sounds = sound_description_pattern.replace_all(&cue.text, "");
+            let without_lyrics = lyrics_pattern.replace_all(&without_sounds, "");
+
+            let lines: Vec<String> = without_lyrics.lines()
+                .filter(|line| !lyrics_line_pattern.is_match(line) && !hash_lyrics_line_pattern.is_match(line))
+                .map(|line| speaker_label_pattern.replace(line, "$1").to_string())
+                .map(|line| empty_tags_pattern.replace_all(&line, "").trim().to_string())
+                .filter(|line| !is_blank_line(line))
+                .collect();
+
+            if lines.is_empty() {
+                debug!("drop cue {}: '{}'", cue.index, cue.text);
+                continue;
+            }
+
+            let text = lines.join("\n");
+
+            let text = if text == cue.text {
+                text
+            } else {
+                rebalance_lines(&lines)
+            };
+
+            results.push(Cue::new(cue.index, cue.start, cue.end, &text));
+        }
+
+        renumber(&mut results);
+
+        info!("cues left: {}", results.len());
+
+        results
+    }
+
+    /// Single speaker text is rewrapped into one or two lines, dialogue lines are kept
+    fn rebalance_lines(lines: &[String]) -> String {
+        let dialogue_lines = lines.iter().filter(|line| line.starts_with('-')).count();
+
+        if dialogue_lines > 1 {
+            return lines.join("\n");
+        }
+
+        let text = lines.iter()
+                        .map(|line| line.trim_start_matches('-').trim())
+                        .collect::<Vec<&str>>()
+                        .join(" ");
+
+        if text.chars().count() <= MAX_LINE_LENGTH {
+            return text;
+        }
+
+        let middle = text.len() / 2;
+
+        let split_position = text.match_indices(' ')
+                                 .map(|(position, _)| position)
+                                 .min_by_key(|position| (*position as i64 - middle as i64).abs());
+
+        match split_position {
+            Some(position) => format!("{}\n{}", &text[..position], &text[position + 1..]),
+            None => text
+        }
+    }
+
+    fn is_blank_line(line: &str) -> bool {
+        line.chars().all(|character| character.is_whitespace() || character == '-')
+    }
+}
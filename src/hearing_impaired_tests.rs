@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod hearing_impaired_tests {
+    use crate::cue::cue::Cue;
+    use crate::hearing_impaired::hearing_impaired::remove_hearing_impaired;
+
+    #[test]
+    fn sound_descriptions_and_lyrics_should_be_removed() {
+        let cues = vec![
+            Cue::new(1, 0, 1000, "[door slams]"),
+            Cue::new(2, 2000, 3000, "(laughs) You got me."),
+            Cue::new(3, 4000, 5000, "♪ Adventure time ♪"),
+            Cue::new(4, 6000, 7000, "<i>[thunder rumbling]</i>\nWhat was that?"),
+            Cue::new(5, 8000, 9000, "♪ Come on, grab your friends\nWe'll go to very distant lands ♪")
+        ];
+
+        assert_eq!(vec![
+            Cue::new(1, 2000, 3000, "You got me."),
+            Cue::new(2, 6000, 7000, "What was that?")
+        ], remove_hearing_impaired(&cues));
+    }
+
+    #[test]
+    fn uppercase_speaker_labels_should_be_removed() {
+        let cues = vec![
+            Cue::new(1, 0, 2000, "- JAKE: Dude!\n- FINN (whispering): What?"),
+            Cue::new(2, 3000, 5000, "NARRATOR: Long ago...")
+        ];
+
+        assert_eq!(vec![
+            Cue::new(1, 0, 2000, "- Dude!\n- What?"),
+            Cue::new(2, 3000, 5000, "Long ago...")
+        ], remove_hearing_impaired(&cues));
+    }
+
+    #[test]
+    fn regular_text_should_be_kept_as_is() {
+        let cues = vec![
+            Cue::new(1, 0, 2000, "It's 5:30 already.\nLet's go, Jake!"),
+            Cue::new(2, 3000, 5000, "<i>Mathematical!</i>")
+        ];
+
+        assert_eq!(cues, remove_hearing_impaired(&cues));
+    }
+
+    #[test]
+    fn hash_lyrics_should_be_removed_only_when_enclosing_line() {
+        let cues = vec![
+            Cue::new(1, 0, 1000, "# Happy birthday to you #"),
+            Cue::new(2, 2000, 3000, "Room #2 and room #3 are free"),
+            Cue::new(3, 4000, 5000, "#1 fan of the show")
+        ];
+
+        assert_eq!(vec![
+            Cue::new(1, 2000, 3000, "Room #2 and room #3 are free"),
+            Cue::new(2, 4000, 5000, "#1 fan of the show")
+        ], remove_hearing_impaired(&cues));
+    }
+
+    #[test]
+    fn uppercase_clauses_should_not_be_taken_for_speaker_labels() {
+        let cues = vec![
+            Cue::new(1, 0, 2000, "I WAS THERE: he said"),
+            Cue::new(2, 3000, 5000, "MAN #2: Over here!
+DR. SMITH: Wait.")
+        ];
+
+        assert_eq!(vec![
+            Cue::new(1, 0, 2000, "I WAS THERE: he said"),
+            Cue::new(2, 3000, 5000, "Over here! Wait.")
+        ], remove_hearing_impaired(&cues));
+    }
+
+    #[test]
+    fn lines_should_be_rebalanced_after_removal() {
+        let cues = vec![
+            Cue::new(1, 0, 2000, "[sighs]\nI don't think we should go into\nthat dungeon tonight, buddy.")
+        ];
+
+        assert_eq!(
+            vec![Cue::new(1, 0, 2000, "I don't think we should go into\nthat dungeon tonight, buddy.")],
+            remove_hearing_impaired(&cues)
+        );
+
+        let cues = vec![Cue::new(1, 0, 2000, "- [gasps]\n- Princess Bubblegum!")];
+
+        assert_eq!(vec![Cue::new(1, 0, 2000, "Princess Bubblegum!")], remove_hearing_impaired(&cues));
+    }
+}
@@ -0,0 +1,79 @@
+pub mod language {
+    use serde::{Deserialize, Serialize};
+
+    /**
+    ISO 639-1, ISO 639-2/B (used by the site) and ISO 639-2/T codes.
+
+    Site-only variants have OpenSubtitles two letter codes: `pob` (Brazilian Portuguese) -> `pb`,
+    `zht` (Traditional Chinese) -> `zt`.
+    */
+    const LANGUAGES: [(&str, &str, &str); 44] = [
+        ("ar", "ara", "ara"),
+        ("bg", "bul", "bul"),
+        ("ca", "cat", "cat"),
+        ("cs", "cze", "ces"),
+        ("da", "dan", "dan"),
+        ("de", "ger", "deu"),
+        ("el", "gre", "ell"),
+        ("en", "eng", "eng"),
+        ("es", "spa", "spa"),
+        ("et", "est", "est"),
+        ("fa", "per", "fas"),
+        ("fi", "fin", "fin"),
+        ("fr", "fre", "fra"),
+        ("he", "heb", "heb"),
+        ("hi", "hin", "hin"),
+        ("hr", "hrv", "hrv"),
+        ("hu", "hun", "hun"),
+        ("id", "ind", "ind"),
+        ("is", "ice", "isl"),
+        ("it", "ita", "ita"),
+        ("ja", "jpn", "jpn"),
+        ("ka", "geo", "kat"),
+        ("ko", "kor", "kor"),
+        ("lt", "lit", "lit"),
+        ("lv", "lav", "lav"),
+        ("mk", "mac", "mkd"),
+        ("ms", "may", "msa"),
+        ("nl", "dut", "nld"),
+        ("no", "nor", "nor"),
+        ("pb", "pob", "pob"),
+        ("pl", "pol", "pol"),
+        ("pt", "por", "por"),
+        ("ro", "rum", "ron"),
+        ("ru", "rus", "rus"),
+        ("sk", "slo", "slk"),
+        ("sl", "slv", "slv"),
+        ("sr", "scc", "srp"),
+        ("sv", "swe", "swe"),
+        ("th", "tha", "tha"),
+        ("tr", "tur", "tur"),
+        ("uk", "ukr", "ukr"),
+        ("vi", "vie", "vie"),
+        ("zh", "chi", "zho"),
+        ("zt", "zht", "zht")
+    ];
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub enum LanguageCodeStyle {
+        /// Two letters: `en`, `ru`
+        #[serde(alias = "iso639-1")]
+        Iso639_1,
+
+        /// Three letters (bibliographic, as on the site): `eng`, `rus`, `fre`
+        #[serde(alias = "iso639-2")]
+        Iso639_2
+    }
+
+    /// Convert ISO 639-1 or ISO 639-2 (B or T) code to the given style, case insensitive
+    pub fn get_language_code(code: &str, style: LanguageCodeStyle) -> Option<&'static str> {
+        let code = code.trim().to_lowercase();
+
+        LANGUAGES.iter()
+                 .find(|(iso1, iso2b, iso2t)| *iso1 == code || *iso2b == code || *iso2t == code)
+                 .map(|(iso1, iso2b, _)| match style {
+                     LanguageCodeStyle::Iso639_1 => *iso1,
+                     LanguageCodeStyle::Iso639_2 => *iso2b
+                 })
+    }
+}
@@ -1,152 +1,514 @@
+#![allow(clippy::module_inception)]
+
 #[macro_use]
-extern crate log;
-extern crate log4rs;
+extern crate tracing;
+
+use std::path::Path;
+use std::time::Instant;
 
-use crate::domain::domain::SubtitleSearchResults;
+use tracing::{field, Instrument, Span};
+
+use crate::domain::domain::{Series, SubtitleFile, SubtitleSearchResultItem, SubtitleSearchResults};
+use crate::download::download::get_subtitle_files;
 use crate::error::error::OperationError;
-use crate::parser::parser::{get_page_type, get_sub_download_url_from_page, PageType, parse_episode_page, parse_search_results};
+use crate::hash::hash::{get_movie_hash, MovieHash};
+use crate::locale::locale::DEFAULT_LOCALE;
+use crate::parser::parser::{get_page_type, get_sub_download_url_from_page, PageType, parse_episode_page, parse_search_results,
+                            parse_series_page, parse_series_search_results};
+use crate::ranking::ranking::{Candidate, CandidateSource, rank_candidates, RankedSubtitle, RankingOptions};
+use crate::release::release::parse_release_name;
+use crate::transport::transport::{HttpResponse, HttpTransport};
 use crate::types::types::{OperationResult, OptionResult};
 
+pub mod ass;
+pub mod batch;
+#[cfg(any(test, feature = "blocking"))]
+pub mod blocking;
+#[cfg(any(test, feature = "test-util"))]
+pub mod cassette;
+pub mod clean;
+pub mod client;
+pub mod config;
+pub mod coverage;
+pub mod cue;
 pub mod domain;
 pub mod error;
+pub mod format;
+pub mod hash;
+pub mod hearing_impaired;
+pub mod language;
+pub mod lint;
+pub mod locale;
+pub mod merge;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock_server;
+pub mod ranking;
+pub mod release;
+pub mod save;
+pub mod sync;
+pub mod timing;
+pub mod transport;
+pub mod ttml;
 pub mod types;
+pub mod upload;
+#[cfg(target_os = "linux")]
+pub mod watch;
+mod batch_tests;
+mod blocking_tests;
+mod cassette_tests;
+mod clean_tests;
+mod client_tests;
+mod config_tests;
+mod coverage_tests;
+mod download;
+mod download_tests;
+mod format_tests;
+mod hash_tests;
+mod hearing_impaired_tests;
+mod lint_tests;
+mod locale_tests;
+mod merge_tests;
 mod parser;
 mod parser_tests;
+mod ranking_tests;
 mod strip;
 mod strip_tests;
+mod release_tests;
+mod save_tests;
 mod search_tests;
+mod series_tests;
+mod sync_tests;
+mod timing_tests;
+mod tracing_tests;
+mod transport_tests;
+mod ttml_tests;
+mod upload_tests;
+mod watch_tests;
 mod test_utils;
 
 /// Open subtitles site url
 pub const BASE_URL: &str = "https://www.opensubtitles.org";
 
-pub async fn search_by_mask(client: &reqwest::Client, base_url: &str,
+const OK_STATUS: u16 = 200;
+
+pub async fn search_by_mask(client: &(impl HttpTransport + ?Sized), base_url: &str,
                             mask: &str, sub_langs: &str) -> OperationResult<SubtitleSearchResults> {
-    info!("search subtitles by mask '{}'", mask);
-    info!("- languages: '{}'", sub_langs);
+    search_by_mask_with_locale(client, base_url, DEFAULT_LOCALE, mask, sub_langs).await
+}
 
-    let request_url = get_default_search_url(base_url, mask, sub_langs);
+/// Search on site in UI `locale`, see `locale::get_locales`
+#[instrument(name = "search", skip_all, fields(kind = "mask", locale = %locale, mask = %mask, sub_langs = %sub_langs, result_count))]
+pub async fn search_by_mask_with_locale(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                                        mask: &str, sub_langs: &str) -> OperationResult<SubtitleSearchResults> {
+    let request_url = get_default_search_url(base_url, locale, mask, sub_langs);
 
-    fetch_and_parse(
-        client, &request_url,
-        parse_search_results,
-        html_parse_error_func_with_two_args
-    ).await
+    search(client, &request_url, html_parse_error_func_with_two_args).await
 }
 
-pub async fn search_serial_season(client: &reqwest::Client, base_url: &str,
+pub async fn search_serial_season(client: &(impl HttpTransport + ?Sized), base_url: &str,
                                   mask: &str, sub_langs: &str, season: u8) ->
                                                           OperationResult<SubtitleSearchResults> {
-    info!("search series subtitles by mask '{}'", mask);
-    info!("- season '{}'", season);
-    info!("- sub langs '{}'", sub_langs);
+    search_serial_season_with_locale(client, base_url, DEFAULT_LOCALE, mask, sub_langs, season).await
+}
 
-    let request_url = get_serial_season_search_url(base_url, mask, sub_langs, season);
+/// Search on site in UI `locale`, see `locale::get_locales`
+#[instrument(name = "search", skip_all, fields(kind = "season", locale = %locale, mask = %mask, sub_langs = %sub_langs, season = season, result_count))]
+pub async fn search_serial_season_with_locale(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                                              mask: &str, sub_langs: &str, season: u8) ->
+                                                          OperationResult<SubtitleSearchResults> {
+    let request_url = get_serial_season_search_url(base_url, locale, mask, sub_langs, season);
 
-    fetch_and_parse(
-        client, &request_url,
-        parse_search_results, html_parse_error_func_with_two_args
-    ).await
+    search(client, &request_url, html_parse_error_func_with_two_args).await
 }
 
-pub async fn search_serial_episode(client: &reqwest::Client, base_url: &str,
+pub async fn search_serial_episode(client: &(impl HttpTransport + ?Sized), base_url: &str,
                                   mask: &str, sub_langs: &str, season: u8, episode: u16) ->
                                   OperationResult<SubtitleSearchResults> {
-    info!("search series subtitles by mask '{}'", mask);
-    info!("- season '{}'", season);
-    info!("- episode '{}'", episode);
-    info!("- sub langs '{}'", sub_langs);
+    search_serial_episode_with_locale(client, base_url, DEFAULT_LOCALE, mask, sub_langs, season, episode).await
+}
 
+/// Search on site in UI `locale`, see `locale::get_locales`
+#[instrument(name = "search", skip_all, fields(kind = "episode", locale = %locale, mask = %mask, sub_langs = %sub_langs, season = season, episode = episode, result_count))]
+pub async fn search_serial_episode_with_locale(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                                               mask: &str, sub_langs: &str, season: u8, episode: u16) ->
+                                               OperationResult<SubtitleSearchResults> {
     let request_url = get_serial_episode_search_url(
-        base_url, mask, sub_langs, season, episode
+        base_url, locale, mask, sub_langs, season, episode
     );
 
-    fetch_and_parse(
-        client, &request_url,
-        parse_search_results, parse_episode_page
-    ).await
+    search(client, &request_url, parse_episode_page).await
+}
+
+/// Search subtitles made for the exact video file (see `hash::get_movie_hash`)
+#[instrument(name = "search", skip_all, fields(kind = "hash", locale = %locale, hash = %movie_hash.hash, sub_langs = %sub_langs, result_count))]
+pub async fn search_by_hash(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                            movie_hash: &MovieHash, sub_langs: &str) -> OperationResult<SubtitleSearchResults> {
+    let request_url = get_hash_search_url(base_url, locale, movie_hash, sub_langs);
+
+    search(client, &request_url, parse_episode_page).await
+}
+
+/// Search subtitles by IMDb id, `tt7342228` or `7342228`
+#[instrument(name = "search", skip_all, fields(kind = "imdb", locale = %locale, imdb_id = %imdb_id, sub_langs = %sub_langs, result_count))]
+pub async fn search_by_imdb_id(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                               imdb_id: &str, sub_langs: &str) -> OperationResult<SubtitleSearchResults> {
+    let request_url = get_imdb_search_url(base_url, locale, imdb_id, sub_langs);
+
+    search(client, &request_url, parse_episode_page).await
+}
+
+/**
+Seasons and episodes of the first TV series found by mask, `None` if search results have no series.
+
+Subtitle counts are collected per language from `sub_langs` (`rus,eng`), one series page per language.
+*/
+#[instrument(name = "series", skip_all, fields(kind = "mask", locale = %locale, mask = %mask, sub_langs = %sub_langs))]
+pub async fn get_series_by_mask(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                                mask: &str, sub_langs: &str) -> OptionResult<Series> {
+    let request_url = get_series_search_url(base_url, locale, mask, sub_langs);
+
+    let series_id = fetch_and_parse(client, &request_url,
+                                    parse_series_search_results,
+                                    html_parse_error_func_with_two_args).await?;
+
+    match series_id {
+        Some(series_id) => Ok(Some(get_series(client, base_url, locale, &series_id, sub_langs).await?)),
+        None => {
+            warn!("series '{}' not found", mask);
+            Ok(None)
+        }
+    }
+}
+
+/// Seasons and episodes of TV series by IMDb id, `tt1305826` or `1305826`, see `get_series_by_mask`
+#[instrument(name = "series", skip_all, fields(kind = "imdb", locale = %locale, imdb_id = %imdb_id, sub_langs = %sub_langs))]
+pub async fn get_series_by_imdb_id(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                                   imdb_id: &str, sub_langs: &str) -> OperationResult<Series> {
+    let series_id = format!("imdbid-{}", imdb_id.trim_start_matches("tt"));
+
+    get_series(client, base_url, locale, &series_id, sub_langs).await
+}
+
+/// Series page for every language, pages are merged into one structure
+async fn get_series(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                    series_id: &str, sub_langs: &str) -> OperationResult<Series> {
+    let languages: Vec<&str> = sub_langs.split(',')
+                                        .map(|language| language.trim())
+                                        .filter(|language| !language.is_empty())
+                                        .collect();
+
+    if languages.is_empty() {
+        return Err(OperationError::InvalidArgument(String::from("no subtitle languages")));
+    }
+
+    let mut series: Option<Series> = None;
+
+    for language in &languages {
+        let request_url = get_series_page_url(base_url, locale, series_id, language);
+
+        let page = fetch_and_parse(client, &request_url,
+                                   |html| parse_series_page(html, language),
+                                   html_parse_error_func_with_two_args).await?;
+
+        series = Some(match series {
+            Some(series) => merge_series(series, page),
+            None => page
+        });
+    }
+
+    let mut series = series.unwrap_or_default();
+
+    for episode in series.seasons.iter_mut().flat_map(|season| season.episodes.iter_mut()) {
+        for language in &languages {
+            episode.subtitle_counts.entry(language.to_string()).or_insert(0);
+        }
+    }
+
+    Ok(series)
+}
+
+/// Episodes missing on one language page are taken from another one
+fn merge_series(mut series: Series, other: Series) -> Series {
+    for other_season in other.seasons {
+        match series.seasons.iter_mut().find(|season| season.number == other_season.number) {
+            Some(season) => {
+                for other_episode in other_season.episodes {
+                    match season.episodes.iter_mut().find(|episode| episode.episode == other_episode.episode) {
+                        Some(episode) => episode.subtitle_counts.extend(other_episode.subtitle_counts),
+                        None => season.episodes.push(other_episode)
+                    }
+                }
+
+                season.episodes.sort_by_key(|episode| episode.episode);
+            }
+            None => series.seasons.push(other_season)
+        }
+    }
+
+    series.seasons.sort_by_key(|season| season.number);
+
+    if series.imdb_id.is_none() {
+        series.imdb_id = other.imdb_id;
+    }
+
+    series
+}
+
+/// Fetch and parse search results, result count goes to current span
+async fn search(client: &(impl HttpTransport + ?Sized), request_url: &str,
+                single_option_parser: impl Fn(&str, &str) -> OperationResult<SubtitleSearchResults>) ->
+                                                                OperationResult<SubtitleSearchResults> {
+    let results = fetch_and_parse(client, request_url, parse_search_results, single_option_parser).await;
+
+    if let Ok(items) = &results {
+        Span::current().record("result_count", items.len() as u64);
+        debug!("search results: {}", items.len());
+    }
+
+    results
+}
+
+/**
+Find the best subtitles for a local video file.
+
+Hash search goes first, then search by title parsed from the file name,
+then by `options.imdb_id`. Each fallback runs only if previous searches found nothing.
+Returns shortlist (`options.max_results`) with score breakdowns, best first.
+*/
+#[instrument(skip_all, fields(video = %video_path.display(), sub_langs = %sub_langs, result_count))]
+pub async fn find_best_subtitles(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                                 video_path: &Path, sub_langs: &str, options: &RankingOptions) -> OperationResult<Vec<RankedSubtitle>> {
+    let video_filename = video_path.file_name()
+                                   .map(|name| name.to_string_lossy().to_string())
+                                   .ok_or_else(|| {
+                                       OperationError::InvalidArgument(format!("invalid video path '{}'", video_path.display()))
+                                   })?;
+
+    // files smaller than hash chunks are searched by name
+    let mut candidates = match get_movie_hash(video_path) {
+        Ok(movie_hash) => get_candidates(
+            search_by_hash(client, base_url, locale, &movie_hash, sub_langs).await, CandidateSource::Hash
+        )?,
+        Err(OperationError::InvalidArgument(message)) => {
+            warn!("no movie hash for '{}': {}", video_path.display(), message);
+            Vec::new()
+        }
+        Err(e) => return Err(e)
+    };
+
+    if candidates.is_empty() {
+        let release = parse_release_name(&video_filename);
+
+        let results = match (release.season, release.episodes.first()) {
+            (Some(season), Some(episode)) =>
+                search_serial_episode_with_locale(client, base_url, locale, &release.title, sub_langs, season, *episode).await,
+            _ => search_by_mask_with_locale(client, base_url, locale, &release.title, sub_langs).await
+        };
+
+        candidates = get_candidates(results, CandidateSource::Filename)?;
+    }
+
+    if candidates.is_empty() {
+        if let Some(imdb_id) = &options.imdb_id {
+            candidates = get_candidates(
+                search_by_imdb_id(client, base_url, locale, imdb_id, sub_langs).await, CandidateSource::Imdb
+            )?;
+        }
+    }
+
+    let ranked = rank_candidates(&video_filename, &candidates, options);
+    Span::current().record("result_count", ranked.len() as u64);
+
+    Ok(ranked)
+}
+
+/// Page without results table is treated as empty results
+fn get_candidates(results: OperationResult<SubtitleSearchResults>,
+                  source: CandidateSource) -> OperationResult<Vec<Candidate>> {
+    match results {
+        Ok(items) => {
+            info!("{:?} search results: {}", source, items.len());
+            Ok(items.into_iter().map(|item| Candidate { item, source }).collect())
+        }
+        Err(OperationError::HtmlParseError) => {
+            warn!("{:?} search: no results", source);
+            Ok(Vec::new())
+        }
+        Err(e) => Err(e)
+    }
+}
+
+/// Subtitle info from a details page (`details_url` of search result item)
+#[instrument(name = "details", skip_all, fields(url = %page_url))]
+pub async fn get_subtitle_details(client: &(impl HttpTransport + ?Sized),
+                                  page_url: &str) -> OperationResult<SubtitleSearchResultItem> {
+    let mut results = fetch_and_parse(client, page_url,
+                                      html_parse_error_func,
+                                      parse_episode_page).await?;
+
+    if results.is_empty() {
+        error!("details page doesn't contain subtitle info");
+        Err(OperationError::HtmlParseError)
+
+    } else {
+        Ok(results.remove(0))
+    }
 }
 
-pub async fn get_download_url_from_page(client: &reqwest::Client,
+#[instrument(name = "download_url", skip_all, fields(url = %page_url))]
+pub async fn get_download_url_from_page(client: &(impl HttpTransport + ?Sized),
                                         page_url: &str) -> OptionResult<String> {
-    info!("get subtitles download url from page '{}'", page_url);
-    fetch_and_parse(client, &page_url,
+    fetch_and_parse(client, page_url,
                     html_parse_error_func,
                     get_sub_download_url_from_page).await
 }
 
-fn get_default_search_url(base_url: &str, search_mask: &str, sub_langs: &str) -> String {
+/**
+Download subtitle files.
+
+`download_url` - absolute or relative (as returned by `get_download_url_from_page`) url.
+Every file is labeled with format detected from its content.
+*/
+#[instrument(name = "download", skip_all, fields(url = %download_url, status, elapsed_ms, file_count))]
+pub async fn download_subtitles(client: &(impl HttpTransport + ?Sized), base_url: &str,
+                                download_url: &str) -> OperationResult<Vec<SubtitleFile>> {
+    let request_url = get_absolute_url(base_url, download_url);
+
+    let started_at = Instant::now();
+    let resp = client.get(&request_url).await;
+    Span::current().record("elapsed_ms", started_at.elapsed().as_millis() as u64);
+
+    let resp = resp?;
+    Span::current().record("status", resp.status);
+
+    if resp.status == OK_STATUS {
+        let filename = get_attachment_filename(&resp)
+                                    .unwrap_or_else(|| String::from("subtitles"));
+
+        let files = get_subtitle_files(&resp.body, &filename)?;
+        Span::current().record("file_count", files.len() as u64);
+
+        Ok(files)
+
+    } else {
+        error!("unexpected server status code: {}", resp.status);
+        Err(OperationError::Error)
+    }
+}
+
+fn get_absolute_url(base_url: &str, url: &str) -> String {
+    if url.starts_with('/') {
+        format!("{}{}", base_url.trim_end_matches('/'), url)
+
+    } else {
+        url.to_string()
+    }
+}
+
+/// Only file name is kept: `../../x` and `/etc/x` must not point outside of output directory
+fn get_attachment_filename(resp: &HttpResponse) -> Option<String> {
+    let header = resp.header("Content-Disposition")?;
+
+    let filename = header.split(';')
+                         .map(|part| part.trim())
+                         .find(|part| part.starts_with("filename="))
+                         .map(|part| part["filename=".len()..].trim_matches('"'))?;
+
+    Path::new(filename).file_name().map(|name| name.to_string_lossy().to_string())
+}
+
+fn get_default_search_url(base_url: &str, locale: &str, search_mask: &str, sub_langs: &str) -> String {
     let sanitized_mask = search_mask.replace(" ", "+");
 
     format!(
-        "{}/en/search/sublanguageid-{}/moviename-{}",
-        base_url, sub_langs, sanitized_mask
+        "{}/{}/search/sublanguageid-{}/moviename-{}",
+        base_url, locale, sub_langs, sanitized_mask
     )
 }
 
-fn get_serial_season_search_url(base_url: &str, search_mask: &str,
+fn get_serial_season_search_url(base_url: &str, locale: &str, search_mask: &str,
                                 sub_langs: &str, season: u8) -> String {
 
     let sanitized_mask = search_mask.replace(" ", "+");
 
     format!(
-        "{}/en/search/sublanguageid-{}/moviename-{}/season-{}/SearchOnlyTVSeries-on",
-        base_url, sub_langs, sanitized_mask, season
+        "{}/{}/search/sublanguageid-{}/moviename-{}/season-{}/SearchOnlyTVSeries-on",
+        base_url, locale, sub_langs, sanitized_mask, season
     )
 }
 
-fn get_serial_episode_search_url(base_url: &str, search_mask: &str,
+fn get_serial_episode_search_url(base_url: &str, locale: &str, search_mask: &str,
                                  sub_langs: &str, season: u8, episode: u16) -> String {
 
     let sanitized_mask = search_mask.replace(" ", "+");
 
     format!(
-        "{}/en/search/sublanguageid-{}/moviename-{}/season-{}/episode-{}/SearchOnlyTVSeries-on",
-        base_url, sub_langs, sanitized_mask, season, episode
+        "{}/{}/search/sublanguageid-{}/moviename-{}/season-{}/episode-{}/SearchOnlyTVSeries-on",
+        base_url, locale, sub_langs, sanitized_mask, season, episode
+    )
+}
+
+fn get_series_search_url(base_url: &str, locale: &str, search_mask: &str, sub_langs: &str) -> String {
+    let sanitized_mask = search_mask.replace(" ", "+");
+
+    format!(
+        "{}/{}/search2/sublanguageid-{}/moviename-{}/SearchOnlyTVSeries-on",
+        base_url, locale, sub_langs, sanitized_mask
     )
 }
 
+fn get_series_page_url(base_url: &str, locale: &str, series_id: &str, language: &str) -> String {
+    format!("{}/{}/ssearch/sublanguageid-{}/{}", base_url, locale, language, series_id)
+}
+
+fn get_hash_search_url(base_url: &str, locale: &str, movie_hash: &MovieHash, sub_langs: &str) -> String {
+    format!(
+        "{}/{}/search/sublanguageid-{}/moviebytesize-{}/moviehash-{}",
+        base_url, locale, sub_langs, movie_hash.size, movie_hash.hash
+    )
+}
+
+fn get_imdb_search_url(base_url: &str, locale: &str, imdb_id: &str, sub_langs: &str) -> String {
+    format!(
+        "{}/{}/search/sublanguageid-{}/imdbid-{}",
+        base_url, locale, sub_langs, imdb_id.trim_start_matches("tt")
+    )
+}
+
+/// `fetch` span with url, status and elapsed time, then `parse` span
 async fn fetch_and_parse<R>(
-    client: &reqwest::Client, url: &str,
+    client: &(impl HttpTransport + ?Sized), url: &str,
     multi_option_parser: impl Fn(&str) -> OperationResult<R>,
     single_option_parser: impl Fn(&str, &str) -> OperationResult<R>
 ) -> OperationResult<R> {
-    debug!("request url:");
-    debug!("'{}'", url);
-
-    match client.get(url).send().await {
-        Ok(resp) => {
-            let status: reqwest::StatusCode = resp.status();
-            debug!("server response code: {}", status.as_str());
-
-            if status == reqwest::StatusCode::OK {
-                match resp.text().await {
-                    Ok(response_text) => {
-                        trace!("---[SEARCH RESULTS]---");
-                        trace!("{}", &response_text);
-                        trace!("---[/SEARCH RESULTS]---");
-
-                        match get_page_type(&response_text) {
-                            PageType::MultipleOptions => multi_option_parser(&response_text),
-                            PageType::SingleOption => single_option_parser(&response_text, &url)
-                        }
-                    }
-                    Err(e) => {
-                        error!("unable to get response text: {}", e);
-                        Err(OperationError::Error)
-                    }
-                }
+    let fetch_span = info_span!("fetch", url, status = field::Empty, elapsed_ms = field::Empty);
 
-            } else {
-                error!("unexpected server status code: {}", status);
-                Err(OperationError::Error)
-            }
-        }
-        Err(e) => {
-            error!("unable to get data from url: {}", e);
-            Err(OperationError::Error)
-        }
+    let started_at = Instant::now();
+    let resp = client.get(url).instrument(fetch_span.clone()).await;
+    fetch_span.record("elapsed_ms", started_at.elapsed().as_millis() as u64);
+
+    let resp = resp?;
+    fetch_span.record("status", resp.status);
+
+    if resp.status != OK_STATUS {
+        fetch_span.in_scope(|| error!("unexpected server status code: {}", resp.status));
+        return Err(OperationError::Error);
     }
+
+    let response_text = resp.text();
+
+    let page_type = get_page_type(&response_text);
+
+    info_span!("parse", page_type = ?page_type).in_scope(|| {
+        trace!("---[SEARCH RESULTS]---");
+        trace!("{}", &response_text);
+        trace!("---[/SEARCH RESULTS]---");
+
+        match page_type {
+            PageType::MultipleOptions => multi_option_parser(&response_text),
+            PageType::SingleOption => single_option_parser(&response_text, url)
+        }
+    })
 }
 
 fn html_parse_error_func<R>(arg: &str) -> OperationResult<R> {
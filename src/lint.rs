@@ -0,0 +1,232 @@
+pub mod lint {
+    use regex::Regex;
+    use serde::{Deserialize, Serialize};
+
+    use crate::clean::clean::AdFilter;
+    use crate::cue::cue::Cue;
+
+    const MARKUP_TAG_PATTERN: &str = "<[^>]*>";
+
+    /// UTF-8 text decoded as windows-1251 (`Р°Р±`) or latin-1 (`Ã©`)
+    const MOJIBAKE_PATTERN: &str =
+        "[РС][°±Ііґµ¶·ё№є»јЅѕїЂЃ‚ѓ„…†‡€‰Љ‹ЊЌЋЏђ‘’“”•–—˜™љ›њќћџЎўЈ¤Ґ¦§Ё©Є«¬®Ї]|[ÃÂ][\u{80}-\u{bf}]";
+
+    /// Mojibake sequences in a cue to report it
+    const MOJIBAKE_MIN_MATCHES: usize = 2;
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub struct LintOptions {
+        /// Minimal cue duration, ms
+        pub min_duration: u64,
+
+        /// Maximal cue duration, ms
+        pub max_duration: u64,
+
+        pub max_chars_per_second: f64,
+
+        pub max_line_length: usize,
+
+        pub max_lines: usize
+    }
+
+    impl Default for LintOptions {
+        fn default() -> Self {
+            LintOptions {
+                min_duration: 500,
+                max_duration: 10_000,
+                max_chars_per_second: 21.0,
+                max_line_length: 42,
+                max_lines: 2
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub enum LintIssue {
+        /// Cue starts before previous cue (`with` index) ends
+        Overlap { with: u32 },
+
+        /// End time is equal to or before start time
+        NonPositiveDuration,
+
+        TooShort { duration: u64 },
+
+        TooLong { duration: u64 },
+
+        ReadingSpeed { chars_per_second: f64 },
+
+        LineTooLong { line: usize, length: usize },
+
+        TooManyLines { count: usize },
+
+        UnbalancedItalicTags,
+
+        /// Text looks like broken encoding
+        Mojibake,
+
+        /// Advertising, site link or uploader credit leftover (see `clean::DEFAULT_AD_PATTERNS`)
+        AdLine
+    }
+
+    impl LintIssue {
+        /// How much the issue lowers subtitle score
+        pub fn weight(&self) -> f32 {
+            match self {
+                LintIssue::Overlap { .. } => 1.0,
+                LintIssue::NonPositiveDuration => 1.0,
+                LintIssue::Mojibake => 1.0,
+                LintIssue::AdLine => 0.5,
+                LintIssue::UnbalancedItalicTags => 0.3,
+                LintIssue::TooShort { .. } => 0.3,
+                LintIssue::TooLong { .. } => 0.2,
+                LintIssue::ReadingSpeed { .. } => 0.3,
+                LintIssue::TooManyLines { .. } => 0.2,
+                LintIssue::LineTooLong { .. } => 0.1
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct CueIssues {
+        /// Cue index
+        pub index: u32,
+
+        pub issues: Vec<LintIssue>
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct LintReport {
+        pub cue_count: usize,
+
+        /// Cues with at least one issue
+        pub cues: Vec<CueIssues>,
+
+        /// From 0.0 (unusable) to 1.0 (no issues), use it to rank downloads
+        pub score: f32
+    }
+
+    pub fn lint(cues: &[Cue], options: &LintOptions) -> LintReport {
+        info!("lint {} cues", cues.len());
+
+        let markup_tag_pattern = Regex::new(MARKUP_TAG_PATTERN).unwrap();
+        let mojibake_pattern = Regex::new(MOJIBAKE_PATTERN).unwrap();
+        let ad_filter = AdFilter::default();
+
+        let mut sorted_cues: Vec<&Cue> = cues.iter().collect();
+        sorted_cues.sort_by_key(|cue| cue.start);
+
+        let mut results: Vec<CueIssues> = Vec::new();
+        let mut previous: Option<&Cue> = None;
+
+        for cue in sorted_cues {
+            let mut issues: Vec<LintIssue> = Vec::new();
+
+            if let Some(previous_cue) = previous {
+                if cue.start < previous_cue.end {
+                    issues.push(LintIssue::Overlap { with: previous_cue.index });
+                }
+            }
+
+            let duration = cue.duration();
+
+            if cue.end <= cue.start {
+                issues.push(LintIssue::NonPositiveDuration);
+
+            } else if duration < options.min_duration {
+                issues.push(LintIssue::TooShort { duration });
+
+            } else if duration > options.max_duration {
+                issues.push(LintIssue::TooLong { duration });
+            }
+
+            let plain_text = markup_tag_pattern.replace_all(&cue.text, "").to_string();
+            let lines: Vec<&str> = plain_text.lines().collect();
+
+            if duration > 0 {
+                let chars: usize = lines.iter().map(|line| line.trim().chars().count()).sum();
+                let chars_per_second = chars as f64 * 1000.0 / duration as f64;
+
+                if chars_per_second > options.max_chars_per_second {
+                    issues.push(LintIssue::ReadingSpeed { chars_per_second });
+                }
+            }
+
+            for (position, line) in lines.iter().enumerate() {
+                let length = line.trim().chars().count();
+
+                if length > options.max_line_length {
+                    issues.push(LintIssue::LineTooLong { line: position + 1, length });
+                }
+            }
+
+            if lines.len() > options.max_lines {
+                issues.push(LintIssue::TooManyLines { count: lines.len() });
+            }
+
+            if !has_balanced_italic_tags(&cue.text) {
+                issues.push(LintIssue::UnbalancedItalicTags);
+            }
+
+            if mojibake_pattern.find_iter(&cue.text).count() >= MOJIBAKE_MIN_MATCHES {
+                issues.push(LintIssue::Mojibake);
+            }
+
+            if ad_filter.is_match(&plain_text) {
+                issues.push(LintIssue::AdLine);
+            }
+
+            if !issues.is_empty() {
+                debug!("cue {} issues: {:?}", cue.index, issues);
+                results.push(CueIssues { index: cue.index, issues });
+            }
+
+            previous = match previous {
+                Some(previous_cue) if previous_cue.end >= cue.end => Some(previous_cue),
+                _ => Some(cue)
+            };
+        }
+
+        let score = get_score(cues.len(), &results);
+        info!("cues with issues: {}, score: {}", results.len(), score);
+
+        LintReport {
+            cue_count: cues.len(),
+            cues: results,
+            score
+        }
+    }
+
+    fn get_score(cue_count: usize, cues: &[CueIssues]) -> f32 {
+        if cue_count == 0 {
+            return 0.0;
+        }
+
+        let penalty: f32 = cues.iter()
+                               .map(|cue| cue.issues.iter().map(|issue| issue.weight()).sum::<f32>().min(1.0))
+                               .sum();
+
+        (1.0 - penalty / cue_count as f32).max(0.0)
+    }
+
+    fn has_balanced_italic_tags(text: &str) -> bool {
+        let lowercase_text = text.to_lowercase();
+        let mut depth: i32 = 0;
+
+        for (position, _) in lowercase_text.match_indices('<') {
+            let tail = &lowercase_text[position..];
+
+            if tail.starts_with("<i>") {
+                depth += 1;
+
+            } else if tail.starts_with("</i>") {
+                depth -= 1;
+
+                if depth < 0 {
+                    return false;
+                }
+            }
+        }
+
+        depth == 0
+    }
+}
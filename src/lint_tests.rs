@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod lint_tests {
+    use crate::cue::cue::Cue;
+    use crate::lint::lint::{lint, LintIssue, LintOptions};
+
+    #[test]
+    fn clean_cues_should_have_full_score() {
+        let cues = vec![
+            Cue::new(1, 1000, 3000, "<i>Привет!</i>"),
+            Cue::new(2, 3500, 6000, "How are you?\nFine.")
+        ];
+
+        let report = lint(&cues, &LintOptions::default());
+
+        assert!(report.cues.is_empty(), "{:?}", report);
+        assert_eq!(1.0, report.score);
+    }
+
+    #[test]
+    fn timing_issues_should_be_reported() {
+        let cues = vec![
+            Cue::new(1, 1000, 3000, "One"),
+            Cue::new(2, 2500, 2800, "Two"),
+            Cue::new(3, 5000, 5000, "Three"),
+            Cue::new(4, 6000, 20000, "Four")
+        ];
+
+        let report = lint(&cues, &LintOptions::default());
+
+        assert_eq!(3, report.cues.len());
+        assert_eq!(vec![LintIssue::Overlap { with: 1 }, LintIssue::TooShort { duration: 300 }], report.cues[0].issues);
+        assert_eq!(vec![LintIssue::NonPositiveDuration], report.cues[1].issues);
+        assert_eq!(vec![LintIssue::TooLong { duration: 14000 }], report.cues[2].issues);
+        assert!(report.score < 0.5);
+    }
+
+    #[test]
+    fn text_issues_should_be_reported() {
+        let options = LintOptions { max_line_length: 25, ..LintOptions::default() };
+
+        let cues = vec![
+            Cue::new(1, 0, 1000, "This line is definitely too long to read"),
+            Cue::new(2, 2000, 5000, "One\nTwo\nThree"),
+            Cue::new(3, 6000, 8000, "<i>Unclosed"),
+            Cue::new(4, 9000, 11000, "РџСЂРёРІРµС‚"),
+            Cue::new(5, 12000, 14000, "Visit\nwww.OpenSubtitles.org")
+        ];
+
+        let report = lint(&cues, &options);
+
+        assert!(matches!(report.cues[0].issues[0], LintIssue::ReadingSpeed { .. }));
+        assert_eq!(LintIssue::LineTooLong { line: 1, length: 40 }, report.cues[0].issues[1]);
+        assert_eq!(vec![LintIssue::TooManyLines { count: 3 }], report.cues[1].issues);
+        assert_eq!(vec![LintIssue::UnbalancedItalicTags], report.cues[2].issues);
+        assert_eq!(vec![LintIssue::Mojibake], report.cues[3].issues);
+        assert_eq!(vec![LintIssue::AdLine], report.cues[4].issues);
+    }
+
+    #[test]
+    fn empty_track_should_have_zero_score() {
+        assert_eq!(0.0, lint(&[], &LintOptions::default()).score);
+    }
+}
@@ -0,0 +1,92 @@
+pub mod locale {
+    /// Site UI locale used when none is set
+    pub const DEFAULT_LOCALE: &str = "en";
+
+    /**
+    Site UI locales: url path code, page `lang` code and the word "subtitles"
+    as used in subtitle page titles (`Adventure Time subtitles Russian S10E04`), if known.
+    */
+    const LOCALES: [(&str, &str, Option<&str>); 56] = [
+        ("an", "an", None),
+        ("ar", "ar", None),
+        ("at", "at", None),
+        ("bg", "bg", Some("субтитри")),
+        ("br", "br", None),
+        ("ca", "ca", Some("subtítols")),
+        ("cs", "cs", Some("titulky")),
+        ("da", "da", Some("undertekster")),
+        ("de", "de", Some("Untertitel")),
+        ("el", "el", None),
+        ("en", "en", Some("subtitles")),
+        ("eo", "eo", None),
+        ("es", "es", Some("subtítulos")),
+        ("et", "et", None),
+        ("eu", "eu", None),
+        ("fa", "fa", None),
+        ("fi", "fi", Some("tekstitykset")),
+        ("fr", "fr", Some("sous-titres")),
+        ("gl", "gl", None),
+        ("he", "he", None),
+        ("hi", "hi", None),
+        ("hr", "hr", Some("titlovi")),
+        ("hu", "hu", Some("feliratok")),
+        ("hy", "hy", None),
+        ("id", "id", None),
+        ("is", "is", None),
+        ("it", "it", Some("sottotitoli")),
+        ("ja", "ja", None),
+        ("ka", "ka", None),
+        ("km", "km", None),
+        ("ko", "ko", None),
+        ("mk", "mk", None),
+        ("ms", "ms", None),
+        ("nl", "nl", Some("ondertitels")),
+        ("no", "no", Some("undertekster")),
+        ("oc", "oc", None),
+        ("pb", "pt-br", Some("legendas")),
+        ("pl", "pl", Some("napisy")),
+        ("pt", "pt", Some("legendas")),
+        ("ro", "ro", Some("subtitrări")),
+        ("ru", "ru", Some("субтитры")),
+        ("si", "si", None),
+        ("sk", "sk", Some("titulky")),
+        ("sl", "sl", None),
+        ("sq", "sq", None),
+        ("sr", "sr", None),
+        ("sv", "sv", Some("undertexter")),
+        ("th", "th", None),
+        ("tl", "tl", None),
+        ("tr", "tr", None),
+        ("tt", "tt", None),
+        ("uk", "uk", Some("субтитри")),
+        ("uz", "uz", None),
+        ("vi", "vi", None),
+        ("zh", "zh", None),
+        ("zt", "zh-tw", None)
+    ];
+
+    /// Site url path codes: `en`, `ru`, `pb`, ...
+    pub fn get_locales() -> Vec<&'static str> {
+        LOCALES.iter().map(|(code, _, _)| *code).collect()
+    }
+
+    pub fn is_supported_locale(locale: &str) -> bool {
+        get_locale_code(locale).is_some()
+    }
+
+    /// Url path code for url code or page `lang` code, case insensitive: `pt-BR` -> `pb`
+    pub fn get_locale_code(locale: &str) -> Option<&'static str> {
+        LOCALES.iter()
+               .find(|(code, lang, _)| code.eq_ignore_ascii_case(locale) || lang.eq_ignore_ascii_case(locale))
+               .map(|(code, _, _)| *code)
+    }
+
+    /// The word "subtitles" in subtitle page titles, `None` if unknown
+    pub fn get_subtitles_word(locale: &str) -> Option<&'static str> {
+        let code = get_locale_code(locale)?;
+
+        LOCALES.iter()
+               .find(|(locale_code, _, _)| *locale_code == code)
+               .and_then(|(_, _, word)| *word)
+    }
+}
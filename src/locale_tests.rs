@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod locale_tests {
+    use crate::locale::locale::{DEFAULT_LOCALE, get_locale_code, get_locales, get_subtitles_word, is_supported_locale};
+
+    #[test]
+    fn site_locales_should_be_supported() {
+        assert_eq!(56, get_locales().len());
+        assert!(get_locales().contains(&DEFAULT_LOCALE));
+
+        assert!(is_supported_locale("ru"));
+        assert!(is_supported_locale("EN"));
+        assert!(!is_supported_locale("xx"));
+        assert!(!is_supported_locale(""));
+    }
+
+    #[test]
+    fn page_lang_should_be_mapped_to_url_code() {
+        assert_eq!(Some("pb"), get_locale_code("pt-BR"));
+        assert_eq!(Some("zt"), get_locale_code("zh-tw"));
+        assert_eq!(Some("ru"), get_locale_code("ru"));
+        assert_eq!(None, get_locale_code("pt-pt"));
+    }
+
+    #[test]
+    fn subtitles_word_should_be_localized() {
+        assert_eq!(Some("subtitles"), get_subtitles_word("en"));
+        assert_eq!(Some("субтитры"), get_subtitles_word("ru"));
+        assert_eq!(Some("legendas"), get_subtitles_word("pt-br"));
+        assert_eq!(None, get_subtitles_word("ja"));
+        assert_eq!(None, get_subtitles_word("xx"));
+    }
+}
@@ -0,0 +1,171 @@
+pub mod merge {
+    use serde::{Deserialize, Serialize};
+
+    use crate::ass::ass::{ALIGNMENT_BOTTOM, ALIGNMENT_TOP, AssEvent, AssStyle, write_ass};
+    use crate::cue::cue::{Cue, Cues};
+
+    pub const TOP_STYLE_NAME: &str = "Top";
+    pub const BOTTOM_STYLE_NAME: &str = "Bottom";
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub struct MergeOptions {
+        /// Cue starts (and ends) closer than this (ms) are treated as simultaneous
+        pub tolerance: u64
+    }
+
+    impl Default for MergeOptions {
+        fn default() -> Self {
+            MergeOptions { tolerance: 500 }
+        }
+    }
+
+    /// Cue of a merged bilingual track
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct MergedCue {
+        pub index: u32,
+        pub start: u64,
+        pub end: u64,
+
+        /// Text of the first (top) track
+        pub top: Option<String>,
+
+        /// Text of the second (bottom) track
+        pub bottom: Option<String>
+    }
+
+    /**
+    Merge two cue tracks (e.g. two languages) into one.
+
+    Nearly simultaneous cues (within `options.tolerance`) get common timing, then the
+    timeline is split so that every merged cue shows whatever is active in both tracks.
+    */
+    pub fn merge_tracks(top: &[Cue], bottom: &[Cue], options: &MergeOptions) -> Vec<MergedCue> {
+        info!("merge tracks, top cues: {}, bottom cues: {}", top.len(), bottom.len());
+
+        let mut top = top.to_vec();
+        let mut bottom = bottom.to_vec();
+
+        top.sort_by_key(|cue| cue.start);
+        bottom.sort_by_key(|cue| cue.start);
+
+        pair_simultaneous_cues(&mut top, &mut bottom, options.tolerance);
+
+        let mut boundaries: Vec<u64> = top.iter().chain(bottom.iter())
+                                          .flat_map(|cue| vec![cue.start, cue.end])
+                                          .collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let mut results: Vec<MergedCue> = Vec::new();
+
+        for window in boundaries.windows(2) {
+            let (start, end) = (window[0], window[1]);
+
+            let top_text = get_active_text(&top, start);
+            let bottom_text = get_active_text(&bottom, start);
+
+            if top_text.is_none() && bottom_text.is_none() {
+                continue;
+            }
+
+            match results.last_mut() {
+                Some(last) if last.end == start && last.top == top_text && last.bottom == bottom_text => {
+                    last.end = end;
+                }
+                _ => results.push(MergedCue {
+                    index: results.len() as u32 + 1,
+                    start,
+                    end,
+                    top: top_text,
+                    bottom: bottom_text
+                })
+            }
+        }
+
+        info!("merged cues: {}", results.len());
+
+        results
+    }
+
+    /// Plain cues: top track text above bottom track text
+    pub fn get_merged_cues(merged: &[MergedCue]) -> Cues {
+        merged.iter().map(|cue| {
+            let text = cue.top.iter().chain(cue.bottom.iter())
+                          .map(|text| text.as_str())
+                          .collect::<Vec<&str>>()
+                          .join("\n");
+
+            Cue::new(cue.index, cue.start, cue.end, &text)
+        }).collect()
+    }
+
+    /// ASS document: top track with `Top` style at the top of the screen, bottom track at the bottom
+    pub fn write_merged_ass(merged: &[MergedCue]) -> String {
+        let styles = vec![
+            AssStyle::new(TOP_STYLE_NAME, ALIGNMENT_TOP),
+            AssStyle::new(BOTTOM_STYLE_NAME, ALIGNMENT_BOTTOM)
+        ];
+
+        let mut events: Vec<AssEvent> = Vec::new();
+
+        for cue in merged {
+            for &(text, style) in [(&cue.top, TOP_STYLE_NAME), (&cue.bottom, BOTTOM_STYLE_NAME)].iter() {
+                if let Some(text) = text {
+                    events.push(AssEvent {
+                        start: cue.start,
+                        end: cue.end,
+                        style: style.to_string(),
+                        text: text.to_string()
+                    });
+                }
+            }
+        }
+
+        write_ass(&styles, &events)
+    }
+
+    /// Snap start/end of the best overlapping cue pairs to common values
+    fn pair_simultaneous_cues(top: &mut [Cue], bottom: &mut [Cue], tolerance: u64) {
+        let mut paired = vec![false; bottom.len()];
+
+        for top_cue in top.iter_mut() {
+            let candidate = bottom.iter().enumerate()
+                .filter(|(position, cue)| !paired[*position] && get_distance(cue.start, top_cue.start) <= tolerance)
+                .min_by_key(|(_, cue)| get_distance(cue.start, top_cue.start))
+                .map(|(position, _)| position);
+
+            if let Some(position) = candidate {
+                paired[position] = true;
+                let bottom_cue = &mut bottom[position];
+
+                let start = top_cue.start.min(bottom_cue.start);
+                top_cue.start = start;
+                bottom_cue.start = start;
+
+                if get_distance(top_cue.end, bottom_cue.end) <= tolerance {
+                    let end = top_cue.end.max(bottom_cue.end);
+                    top_cue.end = end;
+                    bottom_cue.end = end;
+                }
+            }
+        }
+    }
+
+    fn get_active_text(cues: &[Cue], time: u64) -> Option<String> {
+        let texts: Vec<&str> = cues.iter()
+                                   .filter(|cue| cue.start <= time && time < cue.end)
+                                   .map(|cue| cue.text.as_str())
+                                   .filter(|text| !text.trim().is_empty())
+                                   .collect();
+
+        if texts.is_empty() {
+            None
+        } else {
+            Some(texts.join("\n"))
+        }
+    }
+
+    fn get_distance(first: u64, second: u64) -> u64 {
+        first.max(second) - first.min(second)
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod merge_tests {
+    use crate::cue::cue::Cue;
+    use crate::merge::merge::{get_merged_cues, merge_tracks, MergedCue, MergeOptions, write_merged_ass};
+
+    #[test]
+    fn nearly_simultaneous_cues_should_be_paired() {
+        let top = vec![Cue::new(1, 1000, 3000, "Привет"), Cue::new(2, 5000, 6000, "Пока")];
+        let bottom = vec![Cue::new(1, 1200, 2900, "Hello"), Cue::new(2, 5100, 6300, "Bye")];
+
+        let merged = merge_tracks(&top, &bottom, &MergeOptions::default());
+
+        assert_eq!(vec![
+            get_merged_cue(1, 1000, 3000, Some("Привет"), Some("Hello")),
+            get_merged_cue(2, 5000, 6300, Some("Пока"), Some("Bye"))
+        ], merged);
+    }
+
+    #[test]
+    fn overlapping_cues_should_be_split_by_timeline() {
+        let top = vec![Cue::new(1, 1000, 4000, "Long line")];
+        let bottom = vec![Cue::new(1, 2000, 3000, "Short"), Cue::new(2, 8000, 9000, "Alone")];
+
+        let merged = merge_tracks(&top, &bottom, &MergeOptions { tolerance: 100 });
+
+        assert_eq!(vec![
+            get_merged_cue(1, 1000, 2000, Some("Long line"), None),
+            get_merged_cue(2, 2000, 3000, Some("Long line"), Some("Short")),
+            get_merged_cue(3, 3000, 4000, Some("Long line"), None),
+            get_merged_cue(4, 8000, 9000, None, Some("Alone"))
+        ], merged);
+    }
+
+    #[test]
+    fn merged_cues_should_have_top_text_above_bottom_text() {
+        let merged = vec![
+            get_merged_cue(1, 1000, 2000, Some("Верх"), Some("Top")),
+            get_merged_cue(2, 3000, 4000, None, Some("Only bottom"))
+        ];
+
+        let cues = get_merged_cues(&merged);
+
+        assert_eq!(Cue::new(1, 1000, 2000, "Верх\nTop"), cues[0]);
+        assert_eq!(Cue::new(2, 3000, 4000, "Only bottom"), cues[1]);
+    }
+
+    #[test]
+    fn ass_output_should_use_top_and_bottom_styles() {
+        let merged = vec![get_merged_cue(1, 1000, 2500, Some("<i>Верх</i>\nстрока"), Some("Bottom"))];
+
+        let ass = write_merged_ass(&merged);
+
+        assert!(ass.contains("[V4+ Styles]"));
+        assert!(ass.contains("Style: Top,Arial,20,&H00FFFFFF,"));
+        assert!(ass.contains("Dialogue: 0,0:00:01.00,0:00:02.50,Top,,0,0,0,,{\\i1}Верх{\\i0}\\Nстрока\n"));
+        assert!(ass.contains("Dialogue: 0,0:00:01.00,0:00:02.50,Bottom,,0,0,0,,Bottom\n"));
+    }
+
+    fn get_merged_cue(index: u32, start: u64, end: u64,
+                      top: Option<&str>, bottom: Option<&str>) -> MergedCue {
+        MergedCue {
+            index,
+            start,
+            end,
+            top: top.map(|text| text.to_string()),
+            bottom: bottom.map(|text| text.to_string())
+        }
+    }
+}
@@ -0,0 +1,271 @@
+pub mod mock_server {
+    use std::fs;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{SocketAddr, TcpListener, TcpStream};
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use regex::Regex;
+
+    /// Canned pages directory, relative to package root
+    pub const FIXTURES_DIRECTORY: &str = "tests";
+
+    const HTML_CONTENT_TYPE: &str = "text/html; charset=utf-8";
+
+    const CAPTCHA_PAGE: &str = "<html><head><title>Captcha</title></head><body>\
+        <form method=\"post\" action=\"/en/captcha\"><p>Please confirm you are not a robot</p>\
+        <div class=\"g-recaptcha\" data-sitekey=\"key\"></div><input type=\"submit\" value=\"Submit\"></form>\
+        </body></html>";
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct MockResponse {
+        pub status: u16,
+        pub headers: Vec<(String, String)>,
+        pub body: Vec<u8>,
+
+        /// Wait before response is sent
+        pub delay: Option<Duration>
+    }
+
+    impl MockResponse {
+        pub fn html(body: &str) -> MockResponse {
+            MockResponse {
+                status: 200,
+                headers: vec![(String::from("Content-Type"), String::from(HTML_CONTENT_TYPE))],
+                body: body.as_bytes().to_vec(),
+                delay: None
+            }
+        }
+
+        /// Page from `tests/` directory
+        pub fn fixture(filename: &str) -> MockResponse {
+            let path = Path::new(FIXTURES_DIRECTORY).join(filename);
+
+            let content = fs::read(&path).unwrap_or_else(|e| {
+                panic!("unable to read fixture '{}': {}", path.display(), e)
+            });
+
+            MockResponse::bytes(&content, HTML_CONTENT_TYPE)
+        }
+
+        pub fn bytes(content: &[u8], content_type: &str) -> MockResponse {
+            MockResponse {
+                status: 200,
+                headers: vec![(String::from("Content-Type"), content_type.to_string())],
+                body: content.to_vec(),
+                delay: None
+            }
+        }
+
+        /// Empty response with status
+        pub fn status(status: u16) -> MockResponse {
+            MockResponse { status, headers: Vec::new(), body: Vec::new(), delay: None }
+        }
+
+        /// `302 Found` to location
+        pub fn redirect(location: &str) -> MockResponse {
+            MockResponse::status(302).with_header("Location", location)
+        }
+
+        /// Page the site shows instead of results when it suspects a robot
+        pub fn captcha() -> MockResponse {
+            MockResponse::html(CAPTCHA_PAGE)
+        }
+
+        pub fn with_status(mut self, status: u16) -> MockResponse {
+            self.status = status;
+            self
+        }
+
+        pub fn with_header(mut self, name: &str, value: &str) -> MockResponse {
+            self.headers.push((name.to_string(), value.to_string()));
+            self
+        }
+
+        /// Slow response
+        pub fn with_delay(mut self, delay: Duration) -> MockResponse {
+            self.delay = Some(delay);
+            self
+        }
+    }
+
+    /// Request received by mock server
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct MockRequest {
+        pub method: String,
+
+        /// Path with query: `/en/search/sublanguageid-rus/moviename-tideland`
+        pub path: String,
+
+        pub headers: Vec<(String, String)>,
+        pub body: Vec<u8>
+    }
+
+    impl MockRequest {
+        /// Header value, name is case insensitive
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers.iter()
+                        .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+                        .map(|(_, value)| value.as_str())
+        }
+    }
+
+    type Routes = Arc<Mutex<Vec<(Regex, MockResponse)>>>;
+
+    type Handler = Arc<dyn Fn(&MockRequest) -> MockResponse + Send + Sync>;
+
+    /**
+    Local HTTP server with canned responses, use `url()` as `base_url`.
+
+    Responses are matched by regular expression on request path (with query),
+    first added route wins, unmatched requests get `404` (or go to handler, see `start_with_handler`).
+    */
+    pub struct MockServer {
+        address: SocketAddr,
+        routes: Routes,
+        requests: Arc<Mutex<Vec<MockRequest>>>
+    }
+
+    impl MockServer {
+        pub fn start() -> MockServer {
+            MockServer::start_with_handler(|_| MockResponse::status(404))
+        }
+
+        /// Server with `handler` for requests without matching route
+        pub fn start_with_handler(handler: impl Fn(&MockRequest) -> MockResponse + Send + Sync + 'static) -> MockServer {
+            let handler: Handler = Arc::new(handler);
+
+            let listener = TcpListener::bind("127.0.0.1:0").expect("unable to start mock server");
+            let address = listener.local_addr().expect("unable to get mock server address");
+
+            let routes: Routes = Arc::new(Mutex::new(Vec::new()));
+            let requests = Arc::new(Mutex::new(Vec::new()));
+
+            let server_routes = routes.clone();
+            let server_requests = requests.clone();
+
+            thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let routes = server_routes.clone();
+                    let requests = server_requests.clone();
+                    let handler = handler.clone();
+
+                    thread::spawn(move || handle_connection(stream, routes, handler, requests));
+                }
+            });
+
+            MockServer { address, routes, requests }
+        }
+
+        /// Server url without trailing slash: `http://127.0.0.1:41234`
+        pub fn url(&self) -> String {
+            format!("http://{}", self.address)
+        }
+
+        /// Respond with `response` to requests with path matching `path_pattern`
+        pub fn mock(&self, path_pattern: &str, response: MockResponse) -> &MockServer {
+            let pattern = Regex::new(path_pattern).expect("invalid path pattern");
+            self.routes.lock().unwrap().push((pattern, response));
+            self
+        }
+
+        /// Received requests, oldest first
+        pub fn requests(&self) -> Vec<MockRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn handle_connection(stream: TcpStream, routes: Routes, handler: Handler,
+                         requests: Arc<Mutex<Vec<MockRequest>>>) {
+        let mut reader = BufReader::new(match stream.try_clone() {
+            Ok(stream) => stream,
+            Err(_) => return
+        });
+
+        let request = match read_request(&mut reader) {
+            Some(request) => request,
+            None => return
+        };
+
+        let route_response = routes.lock().unwrap().iter()
+                                   .find(|(pattern, _)| pattern.is_match(&request.path))
+                                   .map(|(_, response)| response.clone());
+
+        let response = route_response.unwrap_or_else(|| handler(&request));
+
+        requests.lock().unwrap().push(request);
+
+        if let Some(delay) = response.delay {
+            thread::sleep(delay);
+        }
+
+        let _ = write_response(stream, &response);
+    }
+
+    fn read_request(reader: &mut BufReader<TcpStream>) -> Option<MockRequest> {
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).ok()?;
+
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next()?.to_string();
+        let path = parts.next()?.to_string();
+
+        let mut headers: Vec<(String, String)> = Vec::new();
+
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).ok()?;
+
+            let line = line.trim_end();
+
+            if line.is_empty() {
+                break;
+            }
+
+            if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_string(), value.trim().to_string()));
+            }
+        }
+
+        let content_length: usize = headers.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("Content-Length"))
+            .and_then(|(_, value)| value.parse().ok())
+            .unwrap_or(0);
+
+        let mut body = vec![0_u8; content_length];
+        reader.read_exact(&mut body).ok()?;
+
+        Some(MockRequest { method, path, headers, body })
+    }
+
+    fn write_response(mut stream: TcpStream, response: &MockResponse) -> std::io::Result<()> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", response.status, get_reason(response.status));
+
+        for (name, value) in &response.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+
+        head.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", response.body.len()));
+
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&response.body)?;
+        stream.flush()
+    }
+
+    fn get_reason(status: u16) -> &'static str {
+        match status {
+            200 => "OK",
+            301 => "Moved Permanently",
+            302 => "Found",
+            400 => "Bad Request",
+            403 => "Forbidden",
+            404 => "Not Found",
+            429 => "Too Many Requests",
+            500 => "Internal Server Error",
+            503 => "Service Unavailable",
+            _ => "Unknown"
+        }
+    }
+}
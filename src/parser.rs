@@ -1,12 +1,48 @@
 pub mod parser {
+    use std::collections::BTreeMap;
+
     use regex::Regex;
     use scraper::{ElementRef, Html, Selector};
 
-    use crate::domain::domain::{SubtitleSearchResultItem, SubtitleSearchResults};
+    use crate::domain::domain::{Episode, Season, Series, SubtitleSearchResultItem, SubtitleSearchResults};
     use crate::error::error::OperationError;
-    use crate::strip::strip::strip_html_tags;
+    use crate::format::format::SubtitleFormat;
+    use crate::locale::locale::{DEFAULT_LOCALE, get_subtitles_word};
+    use crate::strip::strip::{collapse_whitespace, strip_html_tags};
     use crate::types::types::{OperationResult, OptionResult};
 
+    /// Part of hearing impaired icon url, same for all site locales
+    const HEARING_IMPAIRED_ICON: &str = "hearing_impaired";
+
+    /// Release name link on subtitle page, same for all site locales
+    const RELEASE_NAME_LINK_SELECTOR: &str = "a[href*=\"/download/nfo/\"]";
+
+    const IMDB_ID_PATTERN: &str = "/title/(tt\\d+)";
+
+    /// Schema.org episode properties on subtitle page, same for all site locales
+    const SEASON_NUMBER_PROP: &str = "seasonNumber";
+    const EPISODE_NUMBER_PROP: &str = "episodeNumber";
+
+    /// Series page link in TV series search results: `/en/ssearch/sublanguageid-all/idmovie-101356`
+    const SERIES_LINK_PATTERN: &str = "/ssearch/[^/]+/((?:idmovie|imdbid)-\\d+)";
+
+    /// Episode link on series page: `/en/search/sublanguageid-rus/imdbid-1512316`
+    const EPISODE_IMDB_ID_PATTERN: &str = "imdbid-(\\d+)";
+
+    /// Season header on series page: `<span id="season-1">`
+    const SEASON_HEADER_SELECTOR: &str = "span[id^=\"season-\"]";
+    const SEASON_HEADER_ID_PREFIX: &str = "season-";
+
+    /// Series page episode row columns
+    const EPISODE_SUBTITLE_COUNT_COLUMN: usize = 1;
+
+    /// Search results row columns
+    const DATE_COLUMN: usize = 3;
+    const DOWNLOADS_COLUMN: usize = 4;
+    const RATING_COLUMN: usize = 5;
+    const IMDB_COLUMN: usize = 7;
+    const UPLOADER_COLUMN: usize = 8;
+
     #[derive(PartialEq, Debug)]
     pub enum PageType {
         /// Page contains multiple results
@@ -43,33 +79,54 @@ pub mod parser {
 
         match document.select(&title_selector).next() {
             Some(title_element) => {
-                let title = strip_html_tags(&title_element.inner_html());
-
-                let sanitized_title = title.replace(" subtitles ", " ");
+                let sanitized_title = get_episode_title(&document, &title_element);
 
                 let series_info_pattern = Regex::new(".*S(\\d{1,2})E(\\d{1,2}).*").unwrap();
 
-                let mut season = 0;
-                let mut episode = 0;
+                let title_series_info = series_info_pattern.captures(&sanitized_title);
 
-                if series_info_pattern.is_match(&sanitized_title) {
-                    let groups = series_info_pattern.captures_iter(&sanitized_title)
-                                                                .next().unwrap();
+                let season = get_item_prop_number(&document, SEASON_NUMBER_PROP)
+                    .or_else(|| title_series_info.as_ref().and_then(|groups| groups[1].parse().ok()))
+                    .unwrap_or(0);
 
-                    season = String::from(&groups[1]).parse()
-                                                    .expect("unable to parse season value");
-                    episode = String::from(&groups[2]).parse()
-                                                    .expect("unable to parse episode value");
-                }
+                let episode = get_item_prop_number(&document, EPISODE_NUMBER_PROP)
+                    .or_else(|| title_series_info.as_ref().and_then(|groups| groups[2].parse().ok()))
+                    .unwrap_or(0);
 
                 let mut results: SubtitleSearchResults = Vec::new();
 
+                let hearing_impaired = match title_element.parent().and_then(ElementRef::wrap) {
+                    Some(title_container) => has_hearing_impaired_icon(&title_container),
+                    None => false
+                };
+
+                let release_name_selector = Selector::parse(RELEASE_NAME_LINK_SELECTOR).unwrap();
+
+                let release_name = document.select(&release_name_selector).next()
+                    .map(|a_element| get_column_text(&a_element))
+                    .filter(|value| !value.is_empty());
+
+                let imdb_link_selector = Selector::parse("a[href*=\"imdb.com/title/\"]").unwrap();
+
+                let imdb_id = document.select(&imdb_link_selector).next()
+                    .and_then(|a_element| a_element.value().attr("href"))
+                    .and_then(get_imdb_id);
+
                 let item = SubtitleSearchResultItem {
                     index: 1,
                     title: sanitized_title,
                     details_url: page_url.to_string(),
                     season,
-                    episode
+                    episode,
+                    hearing_impaired,
+                    release_name,
+                    downloads: 0,
+                    rating: 0.0,
+                    fps: None,
+                    uploader: None,
+                    uploader_rank: None,
+                    imdb_id,
+                    format: None
                 };
 
                 results.push(item);
@@ -162,7 +219,149 @@ pub mod parser {
             }
         }
 
-        return result
+        result
+    }
+
+    /**
+    Series id (`idmovie-101356`) of the first show in TV series search results,
+    `None` if nothing was found.
+    */
+    pub fn parse_series_search_results(html: &str) -> OptionResult<String> {
+        info!("parse series search results");
+        let document = Html::parse_fragment(html);
+
+        let series_link_selector = Selector::parse("#search_results a[href*=\"/ssearch/\"]").unwrap();
+        let series_link_pattern = Regex::new(SERIES_LINK_PATTERN).unwrap();
+
+        let series_id = document.select(&series_link_selector)
+                                .filter_map(|a_element| a_element.value().attr("href"))
+                                .find_map(|href| series_link_pattern.captures(href))
+                                .map(|groups| groups[1].to_string());
+
+        debug!("series id: {:?}", series_id);
+
+        Ok(series_id)
+    }
+
+    /**
+    Series page (`/en/ssearch/sublanguageid-rus/imdbid-1305826`): season header rows
+    followed by schema.org `TVEpisode` rows.
+
+    Page shows subtitle counts for one language filter, they are stored under `language` key.
+    */
+    pub fn parse_series_page(html: &str, language: &str) -> OperationResult<Series> {
+        info!("parse series page");
+        let document = Html::parse_fragment(html);
+
+        let rows_selector = Selector::parse("#search_results tr").unwrap();
+        let season_header_selector = Selector::parse(SEASON_HEADER_SELECTOR).unwrap();
+
+        let mut seasons: Vec<Season> = Vec::new();
+
+        for row in document.select(&rows_selector) {
+            if let Some(season_header) = row.select(&season_header_selector).next() {
+                let season_number = season_header.value().attr("id")
+                                                 .and_then(|id| id.trim_start_matches(SEASON_HEADER_ID_PREFIX).parse().ok());
+
+                match season_number {
+                    Some(number) => seasons.push(Season { number, episodes: Vec::new() }),
+                    None => warn!("unable to get season number from header")
+                }
+
+            } else if row.value().attr("itemprop") == Some("episode") {
+                if seasons.is_empty() {
+                    warn!("episode row without season header");
+                    seasons.push(Season { number: 0, episodes: Vec::new() });
+                }
+
+                let season = seasons.last_mut().unwrap();
+
+                match get_episode_from_row(&row, season.number, language) {
+                    Some(episode) => season.episodes.push(episode),
+                    None => error!("unable to extract episode from row")
+                }
+            }
+        }
+
+        if seasons.is_empty() {
+            error!("unable to parse series page, no seasons found");
+            return Err(OperationError::HtmlParseError);
+        }
+
+        let imdb_link_selector = Selector::parse("a[href*=\"imdb.com/title/\"]").unwrap();
+
+        let series = Series {
+            title: get_series_title(&document),
+            imdb_id: document.select(&imdb_link_selector).next()
+                             .and_then(|a_element| a_element.value().attr("href"))
+                             .and_then(get_imdb_id),
+            seasons
+        };
+
+        info!("series '{}': {} seasons", series.title, series.seasons.len());
+
+        Ok(series)
+    }
+
+    /// `<h1><span itemprop="name">"Adventure Time" subtitles</span> (2010)</h1>` -> `Adventure Time`
+    fn get_series_title(document: &Html) -> String {
+        let name_selector = Selector::parse("h1 [itemprop=\"name\"]").unwrap();
+        let title_selector = Selector::parse("h1").unwrap();
+
+        let title = document.select(&name_selector).next()
+                            .or_else(|| document.select(&title_selector).next())
+                            .map(|element| get_column_text(&element))
+                            .unwrap_or_default();
+
+        // quoted name is locale independent
+        if let Some((name, _)) = title.strip_prefix('"').and_then(|quoted| quoted.split_once('"')) {
+            return name.to_string();
+        }
+
+        remove_subtitles_word(&title, &get_page_locale(document)).trim_matches('"').to_string()
+    }
+
+    fn get_episode_from_row(row: &ElementRef, season: u8, language: &str) -> Option<Episode> {
+        let episode_number_selector = Selector::parse(&format!("[itemprop=\"{}\"]", EPISODE_NUMBER_PROP)).unwrap();
+        let name_selector = Selector::parse("[itemprop=\"name\"]").unwrap();
+        let url_selector = Selector::parse("a[itemprop=\"url\"]").unwrap();
+        let date_selector = Selector::parse("[itemprop=\"datePublished\"]").unwrap();
+        let column_selector = Selector::parse("td").unwrap();
+
+        let episode = row.select(&episode_number_selector).next()
+                         .and_then(|element| get_column_text(&element).parse().ok())?;
+
+        let imdb_id_pattern = Regex::new(EPISODE_IMDB_ID_PATTERN).unwrap();
+
+        let imdb_id = row.select(&url_selector).next()
+                         .and_then(|a_element| a_element.value().attr("href"))
+                         .and_then(|href| imdb_id_pattern.captures(href))
+                         .map(|groups| format!("tt{}", &groups[1]));
+
+        let air_year = row.select(&date_selector).next().and_then(|element| {
+            element.value().attr("datetime")
+                   .map(|datetime| datetime.chars().take(4).collect::<String>())
+                   .unwrap_or_else(|| get_column_text(&element))
+                   .parse().ok()
+        });
+
+        let subtitle_count = row.select(&column_selector).nth(EPISODE_SUBTITLE_COUNT_COLUMN)
+                                .and_then(|column| get_column_text(&column).parse().ok())
+                                .unwrap_or(0);
+
+        let mut subtitle_counts = BTreeMap::new();
+        subtitle_counts.insert(language.to_string(), subtitle_count);
+
+        Some(Episode {
+            season,
+            episode,
+            title: row.select(&name_selector).next()
+                      .map(|element| get_column_text(&element))
+                      .unwrap_or_default(),
+            air_year,
+            imdb_id,
+            subtitle_counts
+        })
     }
 
     fn get_search_item_from_row(row_index: u8, row: &ElementRef,
@@ -180,19 +379,14 @@ pub mod parser {
 
         let mut title: String = String::new();
 
-        match row.select(&title_col_selector).next() {
+        match row.select(title_col_selector).next() {
             Some(title_col) => {
-                match title_col.select(&title_details_url_selector).next() {
-                    Some(a_element) => {
-                        title = a_element.text().next().unwrap()
-                                         .replace("\n", " ").to_string();
-
-                        match a_element.value().attr("href") {
-                            Some(href) => details_page_url = href,
-                            None => {}
-                        }
+                if let Some(a_element) = title_col.select(title_details_url_selector).next() {
+                    title = get_column_text(&a_element);
+
+                    if let Some(href) = a_element.value().attr("href") {
+                        details_page_url = href
                     }
-                    None => {}
                 }
 
                 let title_row = strip_html_tags(&title_col.inner_html());
@@ -201,11 +395,8 @@ pub mod parser {
                 if title_row.len() > 1 {
                     let mut year = String::new();
 
-                    match year_pattern.find(&title_row) {
-                        Some(year_match) => {
-                            year = title_row[year_match.start()+1..year_match.end()-1].to_string();
-                        }
-                        None => {}
+                    if let Some(year_match) = year_pattern.find(&title_row) {
+                        year = title_row[year_match.start()+1..year_match.end()-1].to_string();
                     }
 
                     info!("year '{}'", year);
@@ -229,12 +420,34 @@ pub mod parser {
                         }
                     }
 
+                    let columns: Vec<ElementRef> = row.select(title_col_selector).collect();
+
+                    let uploader_col = columns.get(UPLOADER_COLUMN);
+
                     let search_result_item = SubtitleSearchResultItem {
                         index: row_index,
                         title,
                         details_url: details_page_url.to_string(),
                         season,
-                        episode
+                        episode,
+                        hearing_impaired: has_hearing_impaired_icon(&title_col),
+                        release_name: get_row_release_name(&title_col, series_pattern),
+                        downloads: columns.get(DOWNLOADS_COLUMN)
+                                          .and_then(get_downloads).unwrap_or(0),
+                        rating: columns.get(RATING_COLUMN)
+                                       .and_then(|column| get_column_text(column).parse().ok())
+                                       .unwrap_or(0.0),
+                        fps: columns.get(DATE_COLUMN).and_then(get_fps),
+                        uploader: uploader_col.and_then(|column| {
+                            Some(get_column_text(column)).filter(|value| !value.is_empty())
+                        }),
+                        uploader_rank: uploader_col.and_then(get_uploader_rank),
+                        imdb_id: columns.get(IMDB_COLUMN).and_then(|column| {
+                            column.select(title_details_url_selector).next()
+                                  .and_then(|a_element| a_element.value().attr("href"))
+                                  .and_then(get_imdb_id)
+                        }),
+                        format: columns.get(DOWNLOADS_COLUMN).and_then(get_format)
                     };
 
                     result = Ok(search_result_item);
@@ -245,4 +458,168 @@ pub mod parser {
 
         result
     }
+
+    /**
+    Release text is a plain text of title column: `[S10E04] Adventure Time - S10E04 - Bonnibel Bubblegum`.
+
+    Long release names are truncated, full name is in `<span title="...">`.
+    */
+    fn get_row_release_name(title_col: &ElementRef, series_pattern: &Regex) -> Option<String> {
+        let span_selector = Selector::parse("span[title]").unwrap();
+
+        if let Some(span) = title_col.select(&span_selector).next() {
+            let release_name = collapse_whitespace(span.value().attr("title").unwrap_or(""));
+
+            if !release_name.is_empty() {
+                return Some(release_name);
+            }
+        }
+
+        let text: String = title_col.children()
+                                    .filter_map(|node| node.value().as_text())
+                                    .map(|text| text.to_string())
+                                    .collect();
+
+        let release_name = collapse_whitespace(&series_pattern.replace(&text, ""));
+
+        if release_name.is_empty() {
+            None
+        } else {
+            Some(release_name)
+        }
+    }
+
+    /// Element text with decoded entities and collapsed whitespace, `<br>` is a line break
+    fn get_column_text(column: &ElementRef) -> String {
+        strip_html_tags(&column.inner_html())
+    }
+
+    /// `49x`
+    fn get_downloads(column: &ElementRef) -> Option<u32> {
+        let a_selector = Selector::parse("a").unwrap();
+
+        column.select(&a_selector).next()
+              .and_then(|a_element| get_column_text(&a_element).trim_end_matches('x').parse().ok())
+    }
+
+    /// Frame rate is shown under upload date: `<span class="p">23.976</span>`
+    fn get_fps(column: &ElementRef) -> Option<f32> {
+        let span_selector = Selector::parse("span").unwrap();
+
+        column.select(&span_selector).next()
+              .and_then(|span| get_column_text(&span).parse().ok())
+              .filter(|fps: &f32| *fps > 0.0)
+    }
+
+    /// Format is shown under download count: `<span class="p">ssa</span>`
+    fn get_format(column: &ElementRef) -> Option<SubtitleFormat> {
+        let span_selector = Selector::parse("span").unwrap();
+
+        column.select(&span_selector).next()
+              .map(|span| SubtitleFormat::from_extension(&get_column_text(&span)))
+              .filter(|format| *format != SubtitleFormat::Unknown)
+    }
+
+    /// Rank icon title: `<img title="Trusted" src=".../ranks/trusted.gif">`
+    fn get_uploader_rank(column: &ElementRef) -> Option<String> {
+        let img_selector = Selector::parse("img[title]").unwrap();
+
+        column.select(&img_selector).next()
+              .and_then(|img| img.value().attr("title"))
+              .map(collapse_whitespace)
+              .filter(|title| !title.is_empty())
+    }
+
+    /**
+    Title without the localized "subtitles" word: `The Midnight Gospel Russian S01E01`.
+
+    Title is `<series> <subtitles word> <language> <episode>` in `<h1><span itemprop="name">`, series name
+    is quoted at the start of `<h2>` (`"The Midnight Gospel" Taste of the King ...`), so the word after it
+    is dropped whatever the locale is. Pages without quoted series name fall back to locale words.
+    */
+    fn get_episode_title(document: &Html, title_element: &ElementRef) -> String {
+        let name_selector = Selector::parse("[itemprop=\"name\"]").unwrap();
+        let subtitle_selector = Selector::parse("h2").unwrap();
+
+        let title = title_element.select(&name_selector).next()
+                                 .map(|name_element| get_column_text(&name_element))
+                                 .unwrap_or_else(|| strip_html_tags(&title_element.inner_html()));
+
+        let series_name = document.select(&subtitle_selector).next()
+                                  .map(|subtitle_element| get_column_text(&subtitle_element))
+                                  .and_then(|subtitle| {
+                                      let quoted = subtitle.strip_prefix('"')?;
+                                      quoted.split_once('"').map(|(name, _)| name.to_string())
+                                  })
+                                  .filter(|name| !name.is_empty());
+
+        let rest = series_name.as_ref().and_then(|name| {
+            title.strip_prefix(name.as_str()).and_then(|rest| rest.strip_prefix(' ')).map(|rest| (name, rest))
+        });
+
+        match rest {
+            Some((name, rest)) => {
+                let without_word = rest.split_once(' ').map(|(_, rest)| rest).unwrap_or("");
+                format!("{} {}", name, without_word).trim().to_string()
+            }
+            None => {
+                let locale = get_page_locale(document);
+                debug!("no series name on page, remove subtitles word of locale '{}'", locale);
+
+                remove_subtitles_word(&title, &locale)
+            }
+        }
+    }
+
+    /// Page `<html lang="ru">` value
+    fn get_page_locale(document: &Html) -> String {
+        let html_selector = Selector::parse("html[lang]").unwrap();
+
+        document.select(&html_selector).next()
+                .and_then(|html| html.value().attr("lang"))
+                .map(|lang| lang.trim().to_string())
+                .filter(|lang| !lang.is_empty())
+                .unwrap_or_else(|| String::from(DEFAULT_LOCALE))
+    }
+
+    /**
+    `Adventure Time subtitles Russian S10E04` -> `Adventure Time Russian S10E04`.
+
+    Only the last matching word is removed, the word may be a part of the title.
+    */
+    fn remove_subtitles_word(title: &str, locale: &str) -> String {
+        let word = match get_subtitles_word(locale) {
+            Some(word) => word.to_lowercase(),
+            None => return title.to_string()
+        };
+
+        let mut parts: Vec<&str> = title.split(' ').collect();
+
+        if let Some(position) = parts.iter().rposition(|part| part.to_lowercase() == word) {
+            parts.remove(position);
+        }
+
+        parts.join(" ")
+    }
+
+    fn get_item_prop_number<T: std::str::FromStr>(document: &Html, prop: &str) -> Option<T> {
+        let prop_selector = Selector::parse(&format!("[itemprop=\"{}\"]", prop)).unwrap();
+
+        document.select(&prop_selector).next()
+                .and_then(|element| get_column_text(&element).parse().ok())
+    }
+
+    fn get_imdb_id(url: &str) -> Option<String> {
+        let imdb_id_pattern = Regex::new(IMDB_ID_PATTERN).unwrap();
+
+        imdb_id_pattern.captures(url).map(|groups| groups[1].to_string())
+    }
+
+    fn has_hearing_impaired_icon(element: &ElementRef) -> bool {
+        let img_selector = Selector::parse("img").unwrap();
+
+        element.select(&img_selector).any(|img| {
+            img.value().attr("src").is_some_and(|src| src.contains(HEARING_IMPAIRED_ICON))
+        })
+    }
 }
@@ -2,7 +2,9 @@
 mod parser_tests {
     use log::LevelFilter;
 
-    use crate::parser::parser::{get_page_type, get_sub_download_url_from_page, PageType, parse_episode_page, parse_search_results};
+    use crate::format::format::SubtitleFormat;
+    use crate::parser::parser::{get_page_type, get_sub_download_url_from_page, PageType, parse_episode_page, parse_search_results,
+                                parse_series_page, parse_series_search_results};
     use crate::test_utils::test_utils::{get_html_content, get_logging_config};
 
     #[test]
@@ -23,11 +25,94 @@ mod parser_tests {
                 assert_eq!(first_result.details_url, "https://www.opensubtitles.org/en/subtitles/7863206/adventure-time-bonnibel-bubblegum-ru");
                 assert_eq!(first_result.season, 10);
                 assert_eq!(first_result.episode, 4);
+                assert!(!first_result.hearing_impaired);
+                assert_eq!(Some("Adventure Time - S10E04 - Bonnibel Bubblegum"), first_result.release_name.as_deref());
+                assert_eq!(49, first_result.downloads);
+                assert_eq!(0.0, first_result.rating);
+                assert_eq!(None, first_result.fps);
+                assert_eq!(None, first_result.uploader);
+                assert_eq!(Some("tt7342228"), first_result.imdb_id.as_deref());
+                assert_eq!(Some(SubtitleFormat::Ssa), first_result.format);
             }
             Err(_) => panic!("results expected")
         }
     }
 
+    #[test]
+    fn search_result_items_should_have_fps_and_full_release_name() {
+        let content = get_html_content("series-search-results.html");
+
+        let search_results = parse_search_results(&content).expect("results expected");
+
+        assert_eq!(Some(23.976), search_results[2].fps);
+        assert_eq!(99, search_results[2].downloads);
+
+        let truncated_name_item = search_results.iter()
+            .find(|item| item.details_url.contains("/6380932/"))
+            .expect("item expected");
+
+        assert_eq!(Some("Adventure Time - 412a - The Hard Easy (PotentPortables)"), truncated_name_item.release_name.as_deref());
+    }
+
+    #[test]
+    fn search_result_item_title_entities_should_be_decoded() {
+        let content = get_html_content("series-search-results.html").replacen(
+            "\"Adventure Time\" Bonnibel Bubblegum",
+            "&quot;Adventure Time&quot; Finn &amp; Jake&#39;s&nbsp;Day",
+            1
+        );
+
+        let first_result = parse_search_results(&content).expect("results expected").remove(0);
+
+        assert_eq!("\"Adventure Time\" Finn & Jake's Day (2017)", first_result.title);
+    }
+
+    #[test]
+    fn search_result_item_should_have_uploader_and_rank() {
+        let content = get_html_content("series-search-results.html").replacen(
+            "reLink(event,&#39;/en/profile/iduser-0&#39;);\"></a>",
+            "reLink(event,&#39;/en/profile/iduser-0&#39;);\">borek8000</a>\
+             <img src=\"//static.opensubtitles.org/gfx/icons/ranks/trusted.gif\" title=\"Trusted\">",
+            1
+        );
+
+        let first_result = parse_search_results(&content).expect("results expected").remove(0);
+
+        assert_eq!(Some("borek8000"), first_result.uploader.as_deref());
+        assert_eq!(Some("Trusted"), first_result.uploader_rank.as_deref());
+    }
+
+    #[test]
+    fn search_result_item_should_have_hearing_impaired_flag() {
+        let content = get_html_content("series-search-results.html").replace(
+            "<td id=\"main7863206\">",
+            "<td id=\"main7863206\"><img src=\"//static.opensubtitles.org/gfx/icons/hearing_impaired.gif\" \
+             title=\"Subtitles for hearing impaired\" />"
+        );
+
+        let search_results = parse_search_results(&content).expect("results expected");
+
+        assert!(search_results[0].hearing_impaired);
+        assert!(!search_results[1].hearing_impaired);
+    }
+
+    #[test]
+    fn episode_page_item_should_have_hearing_impaired_flag() {
+        let content = get_html_content("episode-page.html");
+
+        let item = parse_episode_page(&content, "xyz").expect("results expected").remove(0);
+        assert!(!item.hearing_impaired);
+
+        let hearing_impaired_content = content.replacen(
+            "<img style=\"margin-left:5px\" width=\"16\" height=\"16\" src=\"./episode-page_files/hd.webp\"",
+            "<img style=\"margin-left:5px\" width=\"16\" height=\"16\" src=\"./episode-page_files/hearing_impaired.webp\"",
+            1
+        );
+
+        let item = parse_episode_page(&hearing_impaired_content, "xyz").expect("results expected").remove(0);
+        assert!(item.hearing_impaired);
+    }
+
     #[test]
     fn parse_item_from_episode_page() {
         let content = get_html_content("season-page.html");
@@ -77,6 +162,47 @@ mod parser_tests {
         }
     }
 
+    #[test]
+    fn parse_item_from_episode_page_release_name_should_be_parsed() {
+        let content = get_html_content("episode-page.html");
+
+        let item = parse_episode_page(&content, "xyz").expect("results expected").remove(0);
+
+        assert_eq!(Some("The.Midnight.Gospel.S01E01.1080p.NF.WEBRip.DDP5.1.x264-NTb"), item.release_name.as_deref());
+        assert_eq!(Some("tt11970572"), item.imdb_id.as_deref());
+    }
+
+    #[test]
+    fn parse_item_from_localized_episode_page() {
+        let content = get_html_content("episode-page.html")
+            .replacen("lang=\"en\" xml:lang=\"en\"", "lang=\"ru\" xml:lang=\"ru\"", 1)
+            .replacen("The Midnight Gospel subtitles Russian S01E01", "The Midnight Gospel субтитры Russian Серия 1", 1);
+
+        let item = parse_episode_page(&content, "xyz").expect("results expected").remove(0);
+
+        assert_eq!("The Midnight Gospel Russian Серия 1", item.title);
+        assert_eq!(1, item.season);
+        assert_eq!(1, item.episode);
+    }
+
+    #[test]
+    fn episode_title_should_be_taken_from_page_structure() {
+        // locale without known subtitles word
+        let content = get_html_content("episode-page.html")
+            .replacen("lang=\"en\" xml:lang=\"en\"", "lang=\"ar\" xml:lang=\"ar\"", 1)
+            .replacen("The Midnight Gospel subtitles Russian S01E01", "The Midnight Gospel ترجمات Russian S01E01", 1);
+
+        let item = parse_episode_page(&content, "xyz").expect("results expected").remove(0);
+        assert_eq!("The Midnight Gospel Russian S01E01", item.title);
+
+        // subtitles word in series name
+        let content = get_html_content("episode-page.html")
+            .replace("The Midnight Gospel", "Subtitles Gospel");
+
+        let item = parse_episode_page(&content, "xyz").expect("results expected").remove(0);
+        assert_eq!("Subtitles Gospel Russian S01E01", item.title);
+    }
+
     #[test]
     fn parse_sub_download_url_from_episode_page() {
         let content = get_html_content("season-page.html");
@@ -103,4 +229,51 @@ mod parser_tests {
         let content = get_html_content("episode-page.html");
         assert_eq!(get_page_type(&content), PageType::SingleOption)
     }
+
+    #[test]
+    fn series_page_should_contain_seasons_and_episodes() {
+        let content = get_html_content("series-page.html");
+
+        let series = parse_series_page(&content, "rus").expect("series expected");
+
+        assert_eq!("Adventure Time", series.title);
+        assert_eq!(Some("tt1305826"), series.imdb_id.as_deref());
+        assert_eq!(2, series.seasons.len());
+
+        let episode = &series.seasons[0].episodes[0];
+
+        assert_eq!(1, episode.season);
+        assert_eq!(1, episode.episode);
+        assert_eq!("Slumber Party Panic", episode.title);
+        assert_eq!(Some(2010), episode.air_year);
+        assert_eq!(Some("tt1512316"), episode.imdb_id.as_deref());
+        assert_eq!(Some(&3), episode.subtitle_counts.get("rus"));
+
+        assert_eq!("Trouble in Lumpy Space", series.seasons[0].episodes[1].title);
+        assert_eq!(Some(&0), series.seasons[0].episodes[2].subtitle_counts.get("rus"));
+
+        let last_episode = &series.seasons[1].episodes[1];
+
+        assert_eq!(2, last_episode.season);
+        assert_eq!("The Eyes & Ricardio", last_episode.title);
+        assert_eq!(None, last_episode.air_year);
+    }
+
+    #[test]
+    fn page_without_seasons_should_not_be_parsed_as_series() {
+        let content = get_html_content("series-search-results.html");
+
+        assert!(parse_series_page(&content, "rus").is_err());
+    }
+
+    #[test]
+    fn series_search_results_should_contain_series_id() {
+        let content = get_html_content("series-search-page.html");
+
+        assert_eq!(Some(String::from("idmovie-101356")), parse_series_search_results(&content).unwrap());
+
+        let content = get_html_content("series-search-results.html");
+
+        assert_eq!(None, parse_series_search_results(&content).unwrap());
+    }
 }
@@ -0,0 +1,220 @@
+pub mod ranking {
+    use serde::{Deserialize, Serialize};
+
+    use crate::domain::domain::SubtitleSearchResultItem;
+    use crate::release::release::parse_release_name;
+
+    pub const HASH_MATCH_POINTS: f32 = 50.0;
+    pub const RELEASE_GROUP_POINTS: f32 = 15.0;
+    pub const SOURCE_POINTS: f32 = 10.0;
+    pub const FPS_POINTS: f32 = 10.0;
+    pub const DOWNLOADS_MAX_POINTS: f32 = 10.0;
+    pub const RATING_MAX_POINTS: f32 = 5.0;
+    pub const UPLOADER_TRUST_POINTS: f32 = 5.0;
+
+    /// Download count that gets `DOWNLOADS_MAX_POINTS`, less popular subtitles get points on log scale
+    const DOWNLOADS_FOR_MAX_POINTS: f32 = 10_000.0;
+
+    const FPS_TOLERANCE: f32 = 0.01;
+
+    /// Search which found the candidate
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub enum CandidateSource {
+        /// Movie hash (exact video file) search
+        Hash,
+
+        /// Search by title parsed from video file name
+        Filename,
+
+        Imdb
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    pub struct Candidate {
+        pub item: SubtitleSearchResultItem,
+        pub source: CandidateSource
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub enum ScoreCriterion {
+        HashMatch,
+        ReleaseGroup,
+        Source,
+        Fps,
+        Downloads,
+        Rating,
+        UploaderTrust
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct ScoreComponent {
+        pub criterion: ScoreCriterion,
+        pub points: f32,
+
+        /// Human readable explanation: `release group 'NTb' matches`
+        pub explanation: String
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    pub struct RankedSubtitle {
+        pub item: SubtitleSearchResultItem,
+        pub source: CandidateSource,
+
+        /// Sum of breakdown points
+        pub score: f32,
+
+        pub breakdown: Vec<ScoreComponent>
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct RankingOptions {
+        /// Shortlist size
+        pub max_results: usize,
+
+        /// Video frame rate, if known
+        pub fps: Option<f32>,
+
+        /// IMDb id (`tt7342228`) for the last fallback search
+        pub imdb_id: Option<String>,
+
+        /// Uploader names to trust (case insensitive)
+        pub trusted_uploaders: Vec<String>,
+
+        /// Uploader ranks to trust (case insensitive)
+        pub trusted_ranks: Vec<String>
+    }
+
+    impl Default for RankingOptions {
+        fn default() -> Self {
+            RankingOptions {
+                max_results: 5,
+                fps: None,
+                imdb_id: None,
+                trusted_uploaders: Vec::new(),
+                trusted_ranks: ["Administrator", "Trusted", "Platinum member", "Gold member"].iter()
+                                   .map(|rank| rank.to_string()).collect()
+            }
+        }
+    }
+
+    /**
+    Score candidates against video file name and return the best ones, best first.
+
+    Candidates with the same details url are counted once (first one wins).
+    */
+    pub fn rank_candidates(video_filename: &str, candidates: &[Candidate],
+                           options: &RankingOptions) -> Vec<RankedSubtitle> {
+        info!("rank {} candidates for '{}'", candidates.len(), video_filename);
+
+        let video_release = parse_release_name(video_filename);
+
+        let mut results: Vec<RankedSubtitle> = Vec::new();
+
+        for candidate in candidates {
+            if results.iter().any(|ranked| ranked.item.details_url == candidate.item.details_url) {
+                debug!("skip duplicate candidate '{}'", candidate.item.details_url);
+                continue;
+            }
+
+            let candidate_release = candidate.item.release_name.as_deref().map(parse_release_name);
+
+            let mut breakdown: Vec<ScoreComponent> = Vec::new();
+
+            breakdown.push(if candidate.source == CandidateSource::Hash {
+                get_component(ScoreCriterion::HashMatch, HASH_MATCH_POINTS, String::from("movie hash matches"))
+            } else {
+                get_component(ScoreCriterion::HashMatch, 0.0, format!("found by {:?} search", candidate.source).to_lowercase())
+            });
+
+            let candidate_group = candidate_release.as_ref().and_then(|release| release.release_group.clone());
+
+            breakdown.push(match (&video_release.release_group, &candidate_group) {
+                (Some(video_group), Some(group)) if video_group.eq_ignore_ascii_case(group) =>
+                    get_component(ScoreCriterion::ReleaseGroup, RELEASE_GROUP_POINTS,
+                                  format!("release group '{}' matches", group)),
+                (Some(video_group), Some(group)) =>
+                    get_component(ScoreCriterion::ReleaseGroup, 0.0,
+                                  format!("release group '{}' differs from '{}'", group, video_group)),
+                _ => get_component(ScoreCriterion::ReleaseGroup, 0.0, String::from("release group unknown"))
+            });
+
+            let candidate_source = candidate_release.as_ref().and_then(|release| release.source);
+
+            breakdown.push(match (video_release.source, candidate_source) {
+                (Some(video_source), Some(source)) if video_source == source =>
+                    get_component(ScoreCriterion::Source, SOURCE_POINTS, format!("source {:?} matches", source)),
+                (Some(video_source), Some(source)) =>
+                    get_component(ScoreCriterion::Source, 0.0,
+                                  format!("source {:?} differs from {:?}", source, video_source)),
+                _ => get_component(ScoreCriterion::Source, 0.0, String::from("source unknown"))
+            });
+
+            breakdown.push(match (options.fps, candidate.item.fps) {
+                (Some(video_fps), Some(fps)) if (video_fps - fps).abs() < FPS_TOLERANCE =>
+                    get_component(ScoreCriterion::Fps, FPS_POINTS, format!("fps {:.3} matches", fps)),
+                (Some(video_fps), Some(fps)) =>
+                    get_component(ScoreCriterion::Fps, 0.0, format!("fps {:.3} differs from {:.3}", fps, video_fps)),
+                _ => get_component(ScoreCriterion::Fps, 0.0, String::from("fps unknown"))
+            });
+
+            breakdown.push(get_component(
+                ScoreCriterion::Downloads, get_downloads_points(candidate.item.downloads),
+                format!("{} downloads", candidate.item.downloads)
+            ));
+
+            // NaN survives clamp
+            let rating = if candidate.item.rating.is_nan() { 0.0 } else { candidate.item.rating.clamp(0.0, 10.0) };
+
+            breakdown.push(get_component(
+                ScoreCriterion::Rating, rating / 10.0 * RATING_MAX_POINTS, format!("rating {:.1}", rating)
+            ));
+
+            breakdown.push(if is_trusted_uploader(&candidate.item, options) {
+                get_component(ScoreCriterion::UploaderTrust, UPLOADER_TRUST_POINTS, format!(
+                    "trusted uploader '{}'", candidate.item.uploader.as_deref().unwrap_or("")
+                ))
+            } else {
+                get_component(ScoreCriterion::UploaderTrust, 0.0, String::from("uploader not trusted"))
+            });
+
+            let score = breakdown.iter().map(|component| component.points).sum();
+
+            debug!("candidate '{}' score: {}", candidate.item.details_url, score);
+
+            results.push(RankedSubtitle {
+                item: candidate.item.clone(),
+                source: candidate.source,
+                score,
+                breakdown
+            });
+        }
+
+        results.sort_by(|first, second| second.score.total_cmp(&first.score));
+        results.truncate(options.max_results);
+
+        info!("shortlist size: {}", results.len());
+
+        results
+    }
+
+    fn get_component(criterion: ScoreCriterion, points: f32, explanation: String) -> ScoreComponent {
+        ScoreComponent { criterion, points, explanation }
+    }
+
+    fn get_downloads_points(downloads: u32) -> f32 {
+        let ratio = (downloads as f32 + 1.0).log10() / DOWNLOADS_FOR_MAX_POINTS.log10();
+        ratio.min(1.0) * DOWNLOADS_MAX_POINTS
+    }
+
+    fn is_trusted_uploader(item: &SubtitleSearchResultItem, options: &RankingOptions) -> bool {
+        let trusted_name = item.uploader.as_ref().is_some_and(|uploader| {
+            options.trusted_uploaders.iter().any(|name| name.eq_ignore_ascii_case(uploader))
+        });
+
+        let trusted_rank = item.uploader_rank.as_ref().is_some_and(|uploader_rank| {
+            options.trusted_ranks.iter().any(|rank| rank.eq_ignore_ascii_case(uploader_rank))
+        });
+
+        trusted_name || trusted_rank
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod ranking_tests {
+    use crate::domain::domain::SubtitleSearchResultItem;
+    use crate::ranking::ranking::{Candidate, CandidateSource, rank_candidates, RankingOptions, ScoreCriterion};
+
+    const VIDEO_FILENAME: &str = "The.Midnight.Gospel.S01E01.1080p.NF.WEBRip.DDP5.1.x264-NTb.mkv";
+
+    #[test]
+    fn hash_match_should_win() {
+        let candidates = vec![
+            get_candidate(1, "The.Midnight.Gospel.S01E01.1080p.NF.WEBRip.DDP5.1.x264-NTb", CandidateSource::Filename),
+            get_candidate(2, "The.Midnight.Gospel.S01E01.720p.HDTV.x264-OTHER", CandidateSource::Hash)
+        ];
+
+        let results = rank_candidates(VIDEO_FILENAME, &candidates, &RankingOptions::default());
+
+        assert_eq!(2, results.len());
+        assert_eq!("url2", results[0].item.details_url);
+        assert_eq!(CandidateSource::Hash, results[0].source);
+    }
+
+    #[test]
+    fn release_group_and_source_match_should_be_scored() {
+        let candidates = vec![
+            get_candidate(1, "The.Midnight.Gospel.S01E01.720p.HDTV.x264-OTHER", CandidateSource::Filename),
+            get_candidate(2, "The.Midnight.Gospel.S01E01.1080p.NF.WEBRip.DDP5.1.x264-NTb", CandidateSource::Filename)
+        ];
+
+        let results = rank_candidates(VIDEO_FILENAME, &candidates, &RankingOptions::default());
+
+        let best = &results[0];
+        assert_eq!("url2", best.item.details_url);
+        assert_eq!(25.0, get_points(best, ScoreCriterion::ReleaseGroup) + get_points(best, ScoreCriterion::Source));
+
+        let group = best.breakdown.iter().find(|component| component.criterion == ScoreCriterion::ReleaseGroup).unwrap();
+        assert_eq!("release group 'NTb' matches", group.explanation);
+
+        assert_eq!(0.0, get_points(&results[1], ScoreCriterion::ReleaseGroup));
+    }
+
+    #[test]
+    fn fps_downloads_rating_and_uploader_should_be_scored() {
+        let mut candidate = get_candidate(1, "Other.Name", CandidateSource::Imdb);
+        candidate.item.fps = Some(23.976);
+        candidate.item.downloads = 10_000;
+        candidate.item.rating = 8.0;
+        candidate.item.uploader = Some(String::from("borek"));
+        candidate.item.uploader_rank = Some(String::from("trusted"));
+
+        let options = RankingOptions { fps: Some(23.976), ..RankingOptions::default() };
+
+        let result = rank_candidates(VIDEO_FILENAME, &[candidate], &options).remove(0);
+
+        assert_eq!(10.0, get_points(&result, ScoreCriterion::Fps));
+        assert_eq!(10.0, get_points(&result, ScoreCriterion::Downloads));
+        assert_eq!(4.0, get_points(&result, ScoreCriterion::Rating));
+        assert_eq!(5.0, get_points(&result, ScoreCriterion::UploaderTrust));
+        assert_eq!(0.0, get_points(&result, ScoreCriterion::HashMatch));
+        assert_eq!(29.0, result.score);
+    }
+
+    #[test]
+    fn different_fps_should_not_be_scored() {
+        let mut candidate = get_candidate(1, "Other.Name", CandidateSource::Filename);
+        candidate.item.fps = Some(25.0);
+
+        let options = RankingOptions { fps: Some(23.976), ..RankingOptions::default() };
+
+        let result = rank_candidates(VIDEO_FILENAME, &[candidate], &options).remove(0);
+
+        assert_eq!(0.0, get_points(&result, ScoreCriterion::Fps));
+    }
+
+    #[test]
+    fn shortlist_should_be_limited_and_deduplicated() {
+        let mut candidates: Vec<Candidate> = (1..=8)
+            .map(|index| get_candidate(index, "Other.Name", CandidateSource::Filename))
+            .collect();
+        candidates.push(get_candidate(1, "Other.Name", CandidateSource::Imdb));
+
+        let options = RankingOptions { max_results: 3, ..RankingOptions::default() };
+
+        let results = rank_candidates(VIDEO_FILENAME, &candidates, &options);
+
+        assert_eq!(3, results.len());
+        // equal scores keep search order
+        assert_eq!("url1", results[0].item.details_url);
+        assert_eq!(CandidateSource::Filename, results[0].source);
+    }
+
+    #[test]
+    fn nan_rating_should_not_be_scored() {
+        let mut candidate = get_candidate(1, "Other.Name", CandidateSource::Filename);
+        candidate.item.rating = f32::NAN;
+
+        let candidates = vec![candidate, get_candidate(2, "Other.Name", CandidateSource::Hash)];
+
+        let results = rank_candidates(VIDEO_FILENAME, &candidates, &RankingOptions::default());
+
+        assert_eq!("url2", results[0].item.details_url);
+        assert_eq!(0.0, get_points(&results[1], ScoreCriterion::Rating));
+        assert!(!results[1].score.is_nan());
+    }
+
+    fn get_points(result: &crate::ranking::ranking::RankedSubtitle, criterion: ScoreCriterion) -> f32 {
+        result.breakdown.iter()
+              .filter(|component| component.criterion == criterion)
+              .map(|component| component.points)
+              .sum()
+    }
+
+    fn get_candidate(index: u8, release_name: &str, source: CandidateSource) -> Candidate {
+        Candidate {
+            item: SubtitleSearchResultItem {
+                index,
+                title: String::from("The Midnight Gospel"),
+                details_url: format!("url{}", index),
+                season: 1,
+                episode: 1,
+                hearing_impaired: false,
+                release_name: Some(release_name.to_string()),
+                downloads: 0,
+                rating: 0.0,
+                fps: None,
+                uploader: None,
+                uploader_rank: None,
+                imdb_id: None,
+                format: None
+            },
+            source
+        }
+    }
+}
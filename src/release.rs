@@ -0,0 +1,288 @@
+pub mod release {
+    use std::sync::OnceLock;
+
+    use regex::Regex;
+    use serde::{Deserialize, Serialize};
+
+    const FILE_EXTENSION_PATTERN: &str =
+        "(?i)\\.(srt|ass|ssa|sub|vtt|smi|ttml|dfxp|txt|mkv|mp4|m4v|avi|mov|wmv|ts|m2ts|webm|mpg|mpeg)$";
+
+    /// `-GROUP` at the end, or `[GROUP]`
+    const RELEASE_GROUP_PATTERN: &str = "(?:-([A-Za-z0-9]+)|\\[([A-Za-z0-9 ._-]+)\\])$";
+
+    /// Dashed tokens which end with `-GROUP` look-alike: `WEB-DL`
+    const DASHED_TOKEN_PATTERN: &str = "(?i)(?:^|[ .\\[])(WEB-DL|WEB-Rip|Blu-Ray|DTS-HD)$";
+
+    /// `[GROUP]` at the start (fansub style)
+    const LEADING_RELEASE_GROUP_PATTERN: &str = "^\\[([A-Za-z0-9 ._-]+)\\]\\s*";
+
+    const EPISODE_PATTERN: &str = "(?i)(?:^|[ .])S(\\d{1,2})((?:[ .]?E\\d{1,3})+)(?:-E?(\\d{1,3}))?(?:[ .-]|$)";
+    const EPISODE_NUMBER_PATTERN: &str = "(?i)E(\\d{1,3})";
+    const CROSS_EPISODE_PATTERN: &str = "(?i)(?:^|[ .])(\\d{1,2})x(\\d{2,3})(?:[ .-]|$)";
+    const YEAR_PATTERN: &str = "(?:^|[ .(\\[])((?:19|20)\\d{2})(?:[ .)\\]]|$)";
+
+    const RESOLUTION_PATTERN: &str = "(?i)(?:^|[ .(\\[-])(2160p|1080p|1080i|720p|576p|480p|4K|UHD)(?:[ .)\\]-]|$)";
+    const SOURCE_PATTERN: &str =
+        "(?i)(?:^|[ .\\[-])(WEB-?Rip|WEB-?DL|WEB|Blu-?Ray|BDRip|BRRip|BDRemux|HDTV|PDTV|DVDRip|DVD|HDRip|CAM|TS)(?:[ .\\]-]|$)";
+    const STREAMING_SERVICE_PATTERN: &str =
+        "(?:^|[ .\\[-])(NF|AMZN|DSNP|HMAX|ATVP|HULU|PCOK|PMTP|iT|CR|STAN|iP)(?:[ .\\]-]|$)";
+    const AUDIO_CODEC_PATTERN: &str =
+        "(?i)(?:^|[ .\\[-])(DDP|DD\\+|EAC3|DD|AC3|AAC|DTS-HD[ .]MA|DTS-HD|DTS|TrueHD|FLAC|MP3|OPUS)[ .]?((?:[1-7][ .][01])?)(?:[ .\\]-]|$)";
+    const VIDEO_CODEC_PATTERN: &str =
+        "(?i)(?:^|[ .\\[-])(x264|x265|H[ .]?264|H[ .]?265|HEVC|AVC|XviD|DivX|AV1|VP9)(?:[ .\\]-]|$)";
+    const PROPER_PATTERN: &str = "(?i)(?:^|[ .\\[-])PROPER(?:[ .\\]-]|$)";
+    const REPACK_PATTERN: &str = "(?i)(?:^|[ .\\[-])(REPACK|RERIP)(?:[ .\\]-]|$)";
+
+    static FILE_EXTENSION_REGEX: OnceLock<Regex> = OnceLock::new();
+    static RELEASE_GROUP_REGEX: OnceLock<Regex> = OnceLock::new();
+    static DASHED_TOKEN_REGEX: OnceLock<Regex> = OnceLock::new();
+    static LEADING_RELEASE_GROUP_REGEX: OnceLock<Regex> = OnceLock::new();
+    static EPISODE_REGEX: OnceLock<Regex> = OnceLock::new();
+    static EPISODE_NUMBER_REGEX: OnceLock<Regex> = OnceLock::new();
+    static CROSS_EPISODE_REGEX: OnceLock<Regex> = OnceLock::new();
+    static YEAR_REGEX: OnceLock<Regex> = OnceLock::new();
+    static RESOLUTION_REGEX: OnceLock<Regex> = OnceLock::new();
+    static SOURCE_REGEX: OnceLock<Regex> = OnceLock::new();
+    static STREAMING_SERVICE_REGEX: OnceLock<Regex> = OnceLock::new();
+    static AUDIO_CODEC_REGEX: OnceLock<Regex> = OnceLock::new();
+    static VIDEO_CODEC_REGEX: OnceLock<Regex> = OnceLock::new();
+    static PROPER_REGEX: OnceLock<Regex> = OnceLock::new();
+    static REPACK_REGEX: OnceLock<Regex> = OnceLock::new();
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum ReleaseSource {
+        WebRip,
+        WebDl,
+        Web,
+        BluRay,
+        HdTv,
+        Dvd,
+        HdRip,
+        Cam
+    }
+
+    /// Information extracted from release name (`The.Show.S01E01.1080p.NF.WEBRip.DDP5.1.x264-GROUP`)
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+    pub struct ReleaseInfo {
+        pub title: String,
+
+        pub year: Option<u16>,
+
+        pub season: Option<u8>,
+
+        /// Several episodes for multi-episode releases (`S01E01E02`, `S01E01-E03`)
+        pub episodes: Vec<u16>,
+
+        /// Normalized resolution: `2160p`, `1080p`, `720p`, ...
+        pub resolution: Option<String>,
+
+        pub source: Option<ReleaseSource>,
+
+        /// Streaming service tag: `NF`, `AMZN`, ...
+        pub streaming_service: Option<String>,
+
+        /// Audio codec with channels: `DDP5.1`, `AAC2.0`, `AC3`, ...
+        pub audio_codec: Option<String>,
+
+        /// Normalized video codec: `x264`, `x265`, `H.264`, `H.265`, ...
+        pub video_codec: Option<String>,
+
+        pub release_group: Option<String>,
+
+        pub proper: bool,
+
+        pub repack: bool
+    }
+
+    pub fn parse_release_name(name: &str) -> ReleaseInfo {
+        debug!("parse release name '{}'", name);
+
+        let file_extension_pattern = FILE_EXTENSION_REGEX.get_or_init(|| Regex::new(FILE_EXTENSION_PATTERN).unwrap());
+
+        let name = name.trim().replace('_', ".");
+        let name = file_extension_pattern.replace(&name, "").to_string();
+
+        let mut result = ReleaseInfo::default();
+
+        // position where title ends: first season/year/quality token
+        let mut title_end = name.len();
+
+        let release_group_pattern = RELEASE_GROUP_REGEX.get_or_init(|| Regex::new(RELEASE_GROUP_PATTERN).unwrap());
+
+        if let Some(groups) = release_group_pattern.captures(&name) {
+            let group = groups.get(1).or_else(|| groups.get(2)).unwrap();
+            let group_start = groups.get(0).unwrap().start();
+
+            // `- Episode Name` (spaced dash) is not a group
+            let is_spaced_dash = groups.get(1).is_some() && name[..group_start].ends_with(' ');
+
+            let dashed_token_pattern = DASHED_TOKEN_REGEX.get_or_init(|| Regex::new(DASHED_TOKEN_PATTERN).unwrap());
+            let is_dashed_token = groups.get(1).is_some() && dashed_token_pattern.is_match(&name);
+
+            if !is_spaced_dash && !is_dashed_token && group_start > 0 {
+                result.release_group = Some(group.as_str().trim().to_string());
+                title_end = group_start;
+            }
+        }
+
+        let name = &name[..title_end];
+
+        let leading_release_group_pattern = LEADING_RELEASE_GROUP_REGEX.get_or_init(|| Regex::new(LEADING_RELEASE_GROUP_PATTERN).unwrap());
+        let mut name_start = 0;
+
+        if let Some(groups) = leading_release_group_pattern.captures(name) {
+            if result.release_group.is_none() {
+                result.release_group = Some(groups[1].trim().to_string());
+            }
+
+            name_start = groups.get(0).unwrap().end();
+        }
+
+        let name = &name[name_start..];
+        title_end = name.len();
+
+        let episode_pattern = EPISODE_REGEX.get_or_init(|| Regex::new(EPISODE_PATTERN).unwrap());
+        let cross_episode_pattern = CROSS_EPISODE_REGEX.get_or_init(|| Regex::new(CROSS_EPISODE_PATTERN).unwrap());
+
+        if let Some(groups) = episode_pattern.captures(name) {
+            result.season = groups[1].parse().ok();
+            result.episodes = get_episodes(&groups[2], groups.get(3).map(|value| value.as_str()));
+            title_end = groups.get(0).unwrap().start();
+
+        } else if let Some(groups) = cross_episode_pattern.captures(name) {
+            result.season = groups[1].parse().ok();
+            result.episodes = groups[2].parse().into_iter().collect();
+            title_end = groups.get(0).unwrap().start();
+        }
+
+        let year_pattern = YEAR_REGEX.get_or_init(|| Regex::new(YEAR_PATTERN).unwrap());
+
+        // year at the very start is a part of the title (`2012.2009.1080p`)
+        if let Some(groups) = year_pattern.captures_iter(name).find(|groups| groups.get(1).unwrap().start() > 0) {
+            result.year = groups[1].parse().ok();
+            title_end = title_end.min(groups.get(0).unwrap().start());
+        }
+
+        // quality tokens follow year or season/episode, title words before them are not tokens (`Cam.2018`)
+        let tokens_start = if title_end < name.len() { title_end } else { 0 };
+        let tokens = &name[tokens_start..];
+
+        let resolution_pattern = RESOLUTION_REGEX.get_or_init(|| Regex::new(RESOLUTION_PATTERN).unwrap());
+        let source_pattern = SOURCE_REGEX.get_or_init(|| Regex::new(SOURCE_PATTERN).unwrap());
+        let streaming_service_pattern = STREAMING_SERVICE_REGEX.get_or_init(|| Regex::new(STREAMING_SERVICE_PATTERN).unwrap());
+        let video_codec_pattern = VIDEO_CODEC_REGEX.get_or_init(|| Regex::new(VIDEO_CODEC_PATTERN).unwrap());
+
+        let resolution = find_token(resolution_pattern, tokens, tokens_start);
+        result.resolution = resolution.as_ref().map(|(value, _)| normalize_resolution(value));
+
+        let source = find_token(source_pattern, tokens, tokens_start);
+        result.source = source.as_ref().and_then(|(value, _)| get_source(value));
+
+        let streaming_service = find_token(streaming_service_pattern, tokens, tokens_start);
+        result.streaming_service = streaming_service.as_ref().map(|(value, _)| value.to_string());
+
+        let video_codec = find_token(video_codec_pattern, tokens, tokens_start);
+        result.video_codec = video_codec.as_ref().map(|(value, _)| normalize_video_codec(value));
+
+        let audio_codec_pattern = AUDIO_CODEC_REGEX.get_or_init(|| Regex::new(AUDIO_CODEC_PATTERN).unwrap());
+        let mut audio_codec_start = None;
+
+        if let Some(groups) = audio_codec_pattern.captures_iter(tokens).last() {
+            let channels = groups[2].replace(' ', ".");
+            result.audio_codec = Some(format!("{}{}", groups[1].to_uppercase(), channels));
+            audio_codec_start = Some(tokens_start + groups.get(0).unwrap().start());
+        }
+
+        let proper_pattern = PROPER_REGEX.get_or_init(|| Regex::new(PROPER_PATTERN).unwrap());
+        let repack_pattern = REPACK_REGEX.get_or_init(|| Regex::new(REPACK_PATTERN).unwrap());
+
+        result.proper = proper_pattern.is_match(tokens);
+        result.repack = repack_pattern.is_match(tokens);
+
+        let token_starts = [
+            resolution.map(|(_, start)| start), source.map(|(_, start)| start),
+            streaming_service.map(|(_, start)| start), video_codec.map(|(_, start)| start),
+            audio_codec_start
+        ];
+
+        for token_start in token_starts.iter().flatten() {
+            if *token_start > 0 {
+                title_end = title_end.min(*token_start);
+            }
+        }
+
+        result.title = get_title(&name[..title_end]);
+
+        debug!("release info: {:?}", result);
+
+        result
+    }
+
+    /// Last token match is the most specific one (`Show.2019.WEB.WEBRip`), start is shifted by `offset`
+    fn find_token(pattern: &Regex, tokens: &str, offset: usize) -> Option<(String, usize)> {
+        pattern.captures_iter(tokens).last().map(|groups| {
+            (groups[1].to_string(), offset + groups.get(0).unwrap().start())
+        })
+    }
+
+    fn get_episodes(episodes_part: &str, range_end: Option<&str>) -> Vec<u16> {
+        let episode_number_pattern = EPISODE_NUMBER_REGEX.get_or_init(|| Regex::new(EPISODE_NUMBER_PATTERN).unwrap());
+
+        let mut episodes: Vec<u16> = episode_number_pattern.captures_iter(episodes_part)
+                                                           .filter_map(|groups| groups[1].parse().ok())
+                                                           .collect();
+
+        if let (Some(first), Some(last)) = (episodes.first().cloned(), range_end.and_then(|value| value.parse::<u16>().ok())) {
+            if last > first {
+                episodes = (first..=last).collect();
+            }
+        }
+
+        episodes
+    }
+
+    fn get_title(value: &str) -> String {
+        value.replace('.', " ")
+             .trim_matches(|character: char| character.is_whitespace() || character == '-' || character == '[' || character == '(')
+             .split_whitespace()
+             .collect::<Vec<&str>>()
+             .join(" ")
+    }
+
+    fn normalize_resolution(value: &str) -> String {
+        match value.to_lowercase().as_str() {
+            "4k" | "uhd" => String::from("2160p"),
+            other => other.to_string()
+        }
+    }
+
+    fn normalize_video_codec(value: &str) -> String {
+        let compact = value.to_lowercase().replace(['.', ' '], "");
+
+        match compact.as_str() {
+            "x264" => String::from("x264"),
+            "x265" => String::from("x265"),
+            "h264" | "avc" => String::from("H.264"),
+            "h265" | "hevc" => String::from("H.265"),
+            "xvid" => String::from("XviD"),
+            "divx" => String::from("DivX"),
+            _ => value.to_uppercase()
+        }
+    }
+
+    fn get_source(value: &str) -> Option<ReleaseSource> {
+        let compact = value.to_lowercase().replace('-', "");
+
+        match compact.as_str() {
+            "webrip" => Some(ReleaseSource::WebRip),
+            "webdl" => Some(ReleaseSource::WebDl),
+            "web" => Some(ReleaseSource::Web),
+            "bluray" | "bdrip" | "brrip" | "bdremux" => Some(ReleaseSource::BluRay),
+            "hdtv" | "pdtv" => Some(ReleaseSource::HdTv),
+            "dvdrip" | "dvd" => Some(ReleaseSource::Dvd),
+            "hdrip" => Some(ReleaseSource::HdRip),
+            "cam" | "ts" => Some(ReleaseSource::Cam),
+            _ => None
+        }
+    }
+}
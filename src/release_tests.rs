@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod release_tests {
+    use crate::release::release::{parse_release_name, ReleaseInfo, ReleaseSource};
+
+    #[test]
+    fn scene_release_name_should_be_parsed() {
+        let info = parse_release_name("The.Midnight.Gospel.S01E01.1080p.NF.WEBRip.DDP5.1.x264-NTb.srt");
+
+        assert_eq!(ReleaseInfo {
+            title: String::from("The Midnight Gospel"),
+            year: None,
+            season: Some(1),
+            episodes: vec![1],
+            resolution: Some(String::from("1080p")),
+            source: Some(ReleaseSource::WebRip),
+            streaming_service: Some(String::from("NF")),
+            audio_codec: Some(String::from("DDP5.1")),
+            video_codec: Some(String::from("x264")),
+            release_group: Some(String::from("NTb")),
+            proper: false,
+            repack: false
+        }, info);
+    }
+
+    #[test]
+    fn movie_release_name_should_be_parsed() {
+        let info = parse_release_name("Tideland.2005.PROPER.720p.BluRay.DTS.H.264-CtrlHD.mkv");
+
+        assert_eq!("Tideland", info.title);
+        assert_eq!(Some(2005), info.year);
+        assert_eq!(None, info.season);
+        assert_eq!(Some(ReleaseSource::BluRay), info.source);
+        assert_eq!(Some("DTS"), info.audio_codec.as_deref());
+        assert_eq!(Some("H.264"), info.video_codec.as_deref());
+        assert_eq!(Some("CtrlHD"), info.release_group.as_deref());
+        assert!(info.proper);
+    }
+
+    #[test]
+    fn multi_episode_ranges_should_be_parsed() {
+        assert_eq!(vec![1, 2], parse_release_name("Show.S02E01E02.720p.HDTV.x264-GRP").episodes);
+        assert_eq!(vec![3, 4, 5], parse_release_name("Show.S02E03-E05.REPACK.WEB-DL.AMZN-GRP").episodes);
+
+        let info = parse_release_name("Show.S02E03-E05.REPACK.AMZN.WEB-DL.AAC2.0.H265-GRP");
+        assert!(info.repack);
+        assert_eq!(Some(ReleaseSource::WebDl), info.source);
+        assert_eq!(Some("AMZN"), info.streaming_service.as_deref());
+        assert_eq!(Some("AAC2.0"), info.audio_codec.as_deref());
+        assert_eq!(Some("H.265"), info.video_codec.as_deref());
+    }
+
+    #[test]
+    fn search_row_release_text_should_be_parsed() {
+        let info = parse_release_name("Adventure Time - S10E04 - Bonnibel Bubblegum");
+
+        assert_eq!("Adventure Time", info.title);
+        assert_eq!(Some(10), info.season);
+        assert_eq!(vec![4], info.episodes);
+        assert_eq!(None, info.release_group);
+    }
+
+    #[test]
+    fn bracketed_group_and_resolution_aliases_should_be_parsed() {
+        let info = parse_release_name("[SubsPlease] Some Anime - 1x05 (4K) [SubsPlease]");
+
+        assert_eq!(Some(1), info.season);
+        assert_eq!(vec![5], info.episodes);
+        assert_eq!(Some("2160p"), info.resolution.as_deref());
+        assert_eq!(Some("SubsPlease"), info.release_group.as_deref());
+    }
+
+    #[test]
+    fn title_words_before_year_should_not_be_tokens() {
+        let info = parse_release_name("Charlottes.Web.2006.1080p.BluRay.x264-GRP");
+        assert_eq!("Charlottes Web", info.title);
+        assert_eq!(Some(ReleaseSource::BluRay), info.source);
+        assert_eq!(Some("GRP"), info.release_group.as_deref());
+
+        let info = parse_release_name("Cam.2018.1080p.NF.WEBRip.x264");
+        assert_eq!("Cam", info.title);
+        assert_eq!(Some(ReleaseSource::WebRip), info.source);
+        assert_eq!(Some("NF"), info.streaming_service.as_deref());
+
+        let info = parse_release_name("The.Dvd.Collectors.2001.720p.BluRay");
+        assert_eq!("The Dvd Collectors", info.title);
+        assert_eq!(Some(ReleaseSource::BluRay), info.source);
+
+        let info = parse_release_name("Dd.2019.1080p.WEB-DL");
+        assert_eq!("Dd", info.title);
+        assert_eq!(Some(ReleaseSource::WebDl), info.source);
+        assert_eq!(None, info.audio_codec);
+        assert_eq!(None, info.release_group);
+    }
+}
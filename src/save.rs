@@ -0,0 +1,275 @@
+pub mod save {
+    use std::fs;
+    use std::fs::{File, OpenOptions};
+    use std::io::{ErrorKind, Write};
+    use std::path::{Path, PathBuf};
+    use std::process;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::domain::domain::SubtitleFile;
+    use crate::error::error::OperationError;
+    use crate::format::format::SubtitleFormat;
+    use crate::language::language::{get_language_code, LanguageCodeStyle};
+    use crate::types::types::OperationResult;
+
+    /// Max number suffix for `CollisionPolicy::Number`
+    const MAX_FILE_NUMBER: u32 = 99;
+
+    /// Subtitle flavour tag, added after language: `movie.en.forced.srt`
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub enum SubtitleTag {
+        Forced,
+        Sdh,
+        Hi
+    }
+
+    impl SubtitleTag {
+        pub fn name(&self) -> &'static str {
+            match self {
+                SubtitleTag::Forced => "forced",
+                SubtitleTag::Sdh => "sdh",
+                SubtitleTag::Hi => "hi"
+            }
+        }
+
+        /// Tag by file name part: `forced`, `sdh`, `hi`
+        pub fn from_name(name: &str) -> Option<SubtitleTag> {
+            [SubtitleTag::Forced, SubtitleTag::Sdh, SubtitleTag::Hi].iter()
+                .copied()
+                .find(|tag| tag.name().eq_ignore_ascii_case(name))
+        }
+    }
+
+    /// What to do when subtitle file already exists
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub enum CollisionPolicy {
+        /// Keep existing file, don't write
+        Keep,
+
+        /// Replace existing file
+        Overwrite,
+
+        /// Write with a number: `movie.en.1.srt`, `movie.en.2.srt`, ...
+        Number
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct SaveOptions {
+        pub language_code_style: LanguageCodeStyle,
+
+        pub tags: Vec<SubtitleTag>,
+
+        /// Existing files are never replaced unless `CollisionPolicy::Overwrite` is set
+        pub collision_policy: CollisionPolicy
+    }
+
+    impl Default for SaveOptions {
+        fn default() -> Self {
+            SaveOptions {
+                language_code_style: LanguageCodeStyle::Iso639_1,
+                tags: Vec::new(),
+                collision_policy: CollisionPolicy::Keep
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub enum SaveStatus {
+        Written,
+        Overwritten,
+
+        /// File exists, kept as is (`CollisionPolicy::Keep`)
+        Skipped
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct SaveResult {
+        pub path: PathBuf,
+        pub status: SaveStatus
+    }
+
+    /**
+    Subtitle path next to the video: `<video-stem>.<lang>[.<tag>].<ext>`.
+
+    Naming works for Plex, Jellyfin and Kodi.
+    */
+    pub fn get_subtitle_path(video_path: &Path, language: &str, extension: &str,
+                             options: &SaveOptions) -> OperationResult<PathBuf> {
+        get_numbered_subtitle_path(video_path, language, extension, options, None)
+    }
+
+    /**
+    Save subtitle file next to the video according to `options.collision_policy`.
+
+    Content goes to a temporary file in the same directory first, then it's moved in place,
+    so readers never see a partially written file.
+    */
+    pub fn save_subtitle(video_path: &Path, subtitle: &SubtitleFile, language: &str,
+                         options: &SaveOptions) -> OperationResult<SaveResult> {
+        info!("save subtitle '{}' next to '{}'", subtitle.filename, video_path.display());
+
+        let extension = get_extension(subtitle);
+
+        let path = get_subtitle_path(video_path, language, &extension, options)?;
+
+        if options.collision_policy == CollisionPolicy::Keep && path.exists() {
+            info!("file '{}' exists, keep it", path.display());
+            return Ok(SaveResult { path, status: SaveStatus::Skipped });
+        }
+
+        // removed on every return path, errors included
+        let temp_file = write_temp_file(&path, &subtitle.content)?;
+        let temp_path = temp_file.path.as_path();
+
+        let result = match options.collision_policy {
+            CollisionPolicy::Overwrite => {
+                let status = if path.exists() {
+                    SaveStatus::Overwritten
+                } else {
+                    SaveStatus::Written
+                };
+
+                fs::rename(temp_path, &path)?;
+
+                Ok(SaveResult { path, status })
+            }
+            CollisionPolicy::Keep => {
+                if link_no_clobber(temp_path, &path)? {
+                    Ok(SaveResult { path, status: SaveStatus::Written })
+                } else {
+                    info!("file '{}' exists, keep it", path.display());
+                    Ok(SaveResult { path, status: SaveStatus::Skipped })
+                }
+            }
+            CollisionPolicy::Number => save_numbered(temp_path, &path, video_path, language, &extension, options)
+        };
+
+        if let Ok(save_result) = &result {
+            info!("subtitle saved: {:?}", save_result);
+        }
+
+        result
+    }
+
+    fn save_numbered(temp_path: &Path, path: &Path, video_path: &Path, language: &str,
+                     extension: &str, options: &SaveOptions) -> OperationResult<SaveResult> {
+        if link_no_clobber(temp_path, path)? {
+            return Ok(SaveResult { path: path.to_path_buf(), status: SaveStatus::Written });
+        }
+
+        for number in 1..=MAX_FILE_NUMBER {
+            let numbered_path = get_numbered_subtitle_path(video_path, language, extension, options, Some(number))?;
+
+            if link_no_clobber(temp_path, &numbered_path)? {
+                return Ok(SaveResult { path: numbered_path, status: SaveStatus::Written });
+            }
+        }
+
+        error!("no free file name for '{}'", path.display());
+        Err(OperationError::Error)
+    }
+
+    fn get_numbered_subtitle_path(video_path: &Path, language: &str, extension: &str,
+                                  options: &SaveOptions, number: Option<u32>) -> OperationResult<PathBuf> {
+        let stem = video_path.file_stem().ok_or_else(|| {
+            OperationError::InvalidArgument(format!("invalid video path '{}'", video_path.display()))
+        })?;
+
+        let language_code = get_language_code(language, options.language_code_style).ok_or_else(|| {
+            OperationError::InvalidArgument(format!("unsupported language '{}'", language))
+        })?;
+
+        let mut filename = format!("{}.{}", stem.to_string_lossy(), language_code);
+
+        for tag in &options.tags {
+            filename.push('.');
+            filename.push_str(tag.name());
+        }
+
+        if let Some(number) = number {
+            filename.push_str(&format!(".{}", number));
+        }
+
+        filename.push('.');
+        filename.push_str(extension);
+
+        Ok(video_path.with_file_name(filename))
+    }
+
+    /// Extension of detected format, file name extension for unknown format
+    fn get_extension(subtitle: &SubtitleFile) -> String {
+        if subtitle.format.format != SubtitleFormat::Unknown {
+            return subtitle.format.format.extension().to_string();
+        }
+
+        Path::new(&subtitle.filename).extension()
+                                     .map(|extension| extension.to_string_lossy().to_lowercase())
+                                     .unwrap_or_else(|| SubtitleFormat::Unknown.extension().to_string())
+    }
+
+    /// Temporary file, removed when dropped
+    struct TempFile {
+        path: PathBuf
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            if let Err(e) = fs::remove_file(&self.path) {
+                if e.kind() != ErrorKind::NotFound {
+                    error!("unable to remove temporary file '{}': {}", self.path.display(), e);
+                }
+            }
+        }
+    }
+
+    fn write_temp_file(path: &Path, content: &[u8]) -> OperationResult<TempFile> {
+        let filename = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let temp_file = TempFile { path: path.with_file_name(format!(".{}.{}.tmp", filename, process::id())) };
+
+        let mut file = File::create(&temp_file.path)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+
+        Ok(temp_file)
+    }
+
+    /// Atomically create `path` with temp file content, `false` if `path` exists
+    fn link_no_clobber(temp_path: &Path, path: &Path) -> OperationResult<bool> {
+        match fs::hard_link(temp_path, path) {
+            Ok(_) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => {
+                // FAT, exFAT and SMB shares have no hard links
+                debug!("unable to link '{}': {}, copy instead", path.display(), e);
+                copy_no_clobber(temp_path, path)
+            }
+        }
+    }
+
+    /// Create `path` with temp file content unless it exists, partially written file is removed
+    fn copy_no_clobber(temp_path: &Path, path: &Path) -> OperationResult<bool> {
+        let mut file = match OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => return Ok(false),
+            Err(e) => {
+                error!("unable to create file '{}': {}", path.display(), e);
+                return Err(OperationError::IOError(e));
+            }
+        };
+
+        let result = fs::read(temp_path).and_then(|content| {
+            file.write_all(&content)?;
+            file.sync_all()
+        });
+
+        if let Err(e) = result {
+            error!("unable to write file '{}': {}", path.display(), e);
+            drop(file);
+            let _ = fs::remove_file(path);
+            return Err(OperationError::IOError(e));
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod save_tests {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use crate::domain::domain::SubtitleFile;
+    use crate::error::error::OperationError;
+    use crate::format::format::detect_file_format;
+    use crate::language::language::LanguageCodeStyle;
+    use crate::save::save::{CollisionPolicy, get_subtitle_path, save_subtitle, SaveOptions, SaveStatus, SubtitleTag};
+
+    const SRT_CONTENT: &str = "1\n00:00:01,000 --> 00:00:02,000\nHello\n";
+
+    #[test]
+    fn path_should_use_video_stem_language_and_tags() {
+        let video_path = Path::new("/media/Movie (2005)/Movie.2005.1080p.mkv");
+
+        let path = get_subtitle_path(video_path, "rus", "srt", &SaveOptions::default()).unwrap();
+        assert_eq!(Path::new("/media/Movie (2005)/Movie.2005.1080p.ru.srt"), path);
+
+        let options = SaveOptions {
+            language_code_style: LanguageCodeStyle::Iso639_2,
+            tags: vec![SubtitleTag::Forced, SubtitleTag::Sdh],
+            ..SaveOptions::default()
+        };
+
+        let path = get_subtitle_path(video_path, "fr", "ass", &options).unwrap();
+        assert_eq!(Path::new("/media/Movie (2005)/Movie.2005.1080p.fre.forced.sdh.ass"), path);
+    }
+
+    #[test]
+    fn chinese_vietnamese_and_site_language_codes_should_be_supported() {
+        let video_path = Path::new("movie.mkv");
+
+        for (language, expected) in [("zho", "movie.zh.srt"), ("vie", "movie.vi.srt"),
+                                     ("pob", "movie.pb.srt"), ("zht", "movie.zt.srt")] {
+            let path = get_subtitle_path(video_path, language, "srt", &SaveOptions::default()).unwrap();
+            assert_eq!(Path::new(expected), path);
+        }
+    }
+
+    #[test]
+    fn unknown_language_should_be_rejected() {
+        match get_subtitle_path(Path::new("movie.mkv"), "xx", "srt", &SaveOptions::default()) {
+            Err(OperationError::InvalidArgument(_)) => {}
+            _ => panic!("error expected")
+        }
+    }
+
+    #[test]
+    fn subtitle_should_be_written_with_detected_format_extension() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("movie.mkv");
+
+        let result = save_subtitle(&video_path, &get_subtitle("movie.txt"), "eng", &SaveOptions::default()).unwrap();
+
+        assert_eq!(SaveStatus::Written, result.status);
+        assert_eq!(directory.path().join("movie.en.srt"), result.path);
+        assert_eq!(SRT_CONTENT, fs::read_to_string(&result.path).unwrap());
+        assert_eq!(1, fs::read_dir(directory.path()).unwrap().count());
+    }
+
+    #[test]
+    fn existing_file_should_be_kept_by_default() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("movie.mkv");
+        let existing_path = directory.path().join("movie.en.srt");
+        fs::write(&existing_path, "user file").unwrap();
+
+        let result = save_subtitle(&video_path, &get_subtitle("movie.srt"), "en", &SaveOptions::default()).unwrap();
+
+        assert_eq!(SaveStatus::Skipped, result.status);
+        assert_eq!("user file", fs::read_to_string(&existing_path).unwrap());
+        assert_eq!(1, fs::read_dir(directory.path()).unwrap().count());
+    }
+
+    #[test]
+    fn existing_file_should_be_overwritten_when_told_to() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("movie.mkv");
+        let existing_path = directory.path().join("movie.en.srt");
+        fs::write(&existing_path, "user file").unwrap();
+
+        let options = SaveOptions { collision_policy: CollisionPolicy::Overwrite, ..SaveOptions::default() };
+
+        let result = save_subtitle(&video_path, &get_subtitle("movie.srt"), "en", &options).unwrap();
+
+        assert_eq!(SaveStatus::Overwritten, result.status);
+        assert_eq!(SRT_CONTENT, fs::read_to_string(&existing_path).unwrap());
+    }
+
+    #[test]
+    fn numbered_file_should_be_written_on_collision() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("movie.mkv");
+        fs::write(directory.path().join("movie.en.srt"), "user file").unwrap();
+        fs::write(directory.path().join("movie.en.1.srt"), "user file").unwrap();
+
+        let options = SaveOptions { collision_policy: CollisionPolicy::Number, ..SaveOptions::default() };
+
+        let result = save_subtitle(&video_path, &get_subtitle("movie.srt"), "en", &options).unwrap();
+
+        assert_eq!(SaveStatus::Written, result.status);
+        assert_eq!(directory.path().join("movie.en.2.srt"), result.path);
+        assert_eq!(3, fs::read_dir(directory.path()).unwrap().count());
+    }
+
+    #[test]
+    fn temp_file_should_be_removed_on_error() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("movie.mkv");
+
+        // directory in place of subtitle file makes rename fail
+        let blocking_path = directory.path().join("movie.en.srt");
+        fs::create_dir(&blocking_path).unwrap();
+        fs::write(blocking_path.join("file"), "user file").unwrap();
+
+        let options = SaveOptions { collision_policy: CollisionPolicy::Overwrite, ..SaveOptions::default() };
+
+        assert!(save_subtitle(&video_path, &get_subtitle("movie.srt"), "en", &options).is_err());
+        assert_eq!(1, fs::read_dir(directory.path()).unwrap().count());
+    }
+
+    fn get_subtitle(filename: &str) -> SubtitleFile {
+        SubtitleFile {
+            filename: filename.to_string(),
+            content: SRT_CONTENT.as_bytes().to_vec(),
+            format: detect_file_format(filename, SRT_CONTENT.as_bytes())
+        }
+    }
+}
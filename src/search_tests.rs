@@ -1,26 +1,41 @@
 #[cfg(test)]
 mod search_tests {
+    use std::fs;
+    use std::time::Duration;
+
     use reqwest::Client;
+    use tempfile::TempDir;
 
-    use crate::{BASE_URL, get_download_url_from_page, search_by_mask, search_serial_episode, search_serial_season};
+    use crate::{find_best_subtitles, get_download_url_from_page, search_by_mask, search_serial_episode, search_serial_season};
+    use crate::error::error::OperationError;
+    use crate::mock_server::mock_server::{MockResponse, MockServer};
+    use crate::ranking::ranking::{CandidateSource, RankingOptions};
 
     const SEARCH_MASK: &str = "Midnight Gospel";
 
+    const SEARCH_RESULTS_PAGE: &str = "series-search-results.html";
+    const EPISODE_PAGE: &str = "episode-page.html";
+    const SUBTITLE_PAGE: &str = "season-page.html";
+
     #[tokio::test]
     async fn search_movie_with_multi_results() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search/sublanguageid-rus,eng/moviename-adventure\+time$",
+                    MockResponse::fixture(SEARCH_RESULTS_PAGE));
+
         let client = get_client();
 
         match search_by_mask(
-            &client, BASE_URL,
-            "tideland", "rus,eng"
+            &client, &server.url(),
+            "adventure time", "rus,eng"
         ).await {
             Ok(results) => {
                 println!("{:?}", results);
                 assert!(results.len() > 1);
 
-                let first_movie = results.get(0).unwrap();
+                let first_movie = results.first().unwrap();
 
-                assert_eq!("Tideland (2005)", first_movie.title);
+                assert_eq!("\"Adventure Time\" Bonnibel Bubblegum (2017)", first_movie.title);
             },
             Err(_) => panic!("search results expected")
         }
@@ -28,24 +43,33 @@ mod search_tests {
 
     #[tokio::test]
     async fn search_serial_episode_with_one_result() {
+        let server = get_series_server();
         let client = get_client();
 
         match search_serial_episode(
-            &client, BASE_URL,
-            SEARCH_MASK, "rus", 1, 2
+            &client, &server.url(),
+            SEARCH_MASK, "rus", 1, 1
         ).await {
-            Ok(results) => assert_eq!(1, results.len()),
+            Ok(results) => {
+                assert_eq!(1, results.len());
+
+                let item = results.first().unwrap();
+
+                assert_eq!(Some("The.Midnight.Gospel.S01E01.1080p.NF.WEBRip.DDP5.1.x264-NTb"),
+                           item.release_name.as_deref());
+            }
             Err(_) => panic!("search results expected")
         }
     }
 
     #[tokio::test]
     async fn search_serial_episode_with_multi_results() {
+        let server = get_series_server();
         let client = get_client();
 
         match search_serial_episode(
-            &client, BASE_URL,
-            SEARCH_MASK, "rus,eng", 1, 2
+            &client, &server.url(),
+            SEARCH_MASK, "rus,eng", 1, 1
         ).await {
             Ok(results) => {
                 println!("{:?}", results);
@@ -57,24 +81,28 @@ mod search_tests {
 
     #[tokio::test]
     async fn result_should_contain_relative_url() {
+        let server = MockServer::start();
+        server.mock(r"^/en/subtitles/7863206/", MockResponse::fixture(SUBTITLE_PAGE));
+
         let client = get_client();
 
-        let url = format!("{}/en/subtitles/8314554/midnight-sun-ko", BASE_URL);
+        let url = format!("{}/en/subtitles/7863206/adventure-time-bonnibel-bubblegum-ru", server.url());
 
         match get_download_url_from_page(&client, &url).await {
-            Ok(url) => assert_eq!("/en/subtitleserve/sub/8314554", url.unwrap()),
+            Ok(url) => assert_eq!("/en/subtitleserve/sub/7863206", url.unwrap()),
             Err(_) => panic!("search results expected")
         }
     }
 
     #[tokio::test]
     async fn result_should_contain_series_episode_search_results() {
+        let server = get_series_server();
         let client = get_client();
 
-        match search_serial_episode(&client, BASE_URL, SEARCH_MASK,
-                                    "rus", 1, 2).await {
+        match search_serial_episode(&client, &server.url(), SEARCH_MASK,
+                                    "rus", 1, 1).await {
             Ok(search_results) => {
-                assert!(search_results.len() > 0);
+                assert!(!search_results.is_empty());
                 println!("{:?}", &search_results);
             }
             Err(_) => panic!("search results expected")
@@ -83,18 +111,146 @@ mod search_tests {
 
     #[tokio::test]
     async fn result_should_contain_series_season_search_results() {
+        let server = get_series_server();
         let client = get_client();
 
-        match search_serial_season(&client, BASE_URL,
+        match search_serial_season(&client, &server.url(),
                                    SEARCH_MASK, "rus", 1).await {
             Ok(search_results) => {
-                assert!(search_results.len() > 0);
+                assert!(!search_results.is_empty());
                 println!("{:?}", &search_results);
             }
             Err(_) => panic!("search results expected")
         }
     }
 
+    #[tokio::test]
+    async fn search_should_request_expected_url() {
+        let server = get_series_server();
+        let client = get_client();
+
+        search_serial_season(&client, &server.url(), SEARCH_MASK, "rus", 1).await.unwrap();
+
+        let requests = server.requests();
+
+        assert_eq!(1, requests.len());
+        assert_eq!("GET", requests[0].method);
+        assert_eq!("/en/search/sublanguageid-rus/moviename-Midnight+Gospel/season-1/SearchOnlyTVSeries-on",
+                   requests[0].path);
+        assert_eq!(Some("Google Chrome"), requests[0].header("User-Agent"));
+    }
+
+    #[tokio::test]
+    async fn unexpected_status_should_return_error() {
+        for status in [403_u16, 404, 429, 500, 503].iter() {
+            let server = MockServer::start();
+            server.mock(r"^/en/search/", MockResponse::status(*status));
+
+            let client = get_client();
+
+            match search_by_mask(&client, &server.url(), SEARCH_MASK, "rus").await {
+                Err(OperationError::Error) => {}
+                _ => panic!("error expected for status {}", status)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_url_should_return_error() {
+        let server = MockServer::start();
+        let client = get_client();
+
+        assert!(search_by_mask(&client, &server.url(), SEARCH_MASK, "rus").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn redirect_should_be_followed() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search/", MockResponse::redirect("/en/subtitles/8179335/the-midnight-gospel-taste-of-the-king-en"))
+              .mock(r"^/en/subtitles/8179335/", MockResponse::fixture(EPISODE_PAGE));
+
+        let client = get_client();
+
+        match search_serial_episode(&client, &server.url(), SEARCH_MASK, "eng", 1, 1).await {
+            Ok(results) => assert_eq!(1, results.len()),
+            Err(_) => panic!("search results expected")
+        }
+
+        let paths: Vec<String> = server.requests().into_iter().map(|request| request.path).collect();
+
+        assert_eq!(2, paths.len());
+        assert!(paths[1].starts_with("/en/subtitles/8179335/"));
+    }
+
+    #[tokio::test]
+    async fn captcha_page_should_return_parse_error() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search/", MockResponse::captcha());
+
+        let client = get_client();
+
+        match search_by_mask(&client, &server.url(), SEARCH_MASK, "rus").await {
+            Err(OperationError::HtmlParseError) => {}
+            _ => panic!("parse error expected")
+        }
+    }
+
+    #[tokio::test]
+    async fn slow_response_should_return_error_on_timeout() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search/",
+                    MockResponse::fixture(SEARCH_RESULTS_PAGE).with_delay(Duration::from_secs(2)));
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        match search_by_mask(&client, &server.url(), SEARCH_MASK, "rus").await {
+            Err(OperationError::Error) => {}
+            _ => panic!("timeout error expected")
+        }
+    }
+
+    #[tokio::test]
+    async fn slow_response_should_be_parsed_within_timeout() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search/",
+                    MockResponse::fixture(SEARCH_RESULTS_PAGE).with_delay(Duration::from_millis(100)));
+
+        let client = get_client();
+
+        assert!(!search_by_mask(&client, &server.url(), SEARCH_MASK, "rus").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn video_too_small_for_hash_should_be_searched_by_file_name() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("The.Midnight.Gospel.S01E01.mkv");
+        fs::write(&video_path, "sample").unwrap();
+
+        let server = get_series_server();
+
+        let ranked = find_best_subtitles(&get_client(), &server.url(), "en", &video_path, "rus", &RankingOptions::default())
+            .await.unwrap();
+
+        assert!(!ranked.is_empty());
+        assert_eq!(CandidateSource::Filename, ranked[0].source);
+        assert!(server.requests().iter().all(|request| !request.path.contains("moviehash")));
+    }
+
+    /// Episode search returns single subtitle page (results table for several languages),
+    /// season search returns results table
+    fn get_series_server() -> MockServer {
+        let server = MockServer::start();
+
+        server.mock(r"^/en/search/sublanguageid-[a-z]+,", MockResponse::fixture(SEARCH_RESULTS_PAGE))
+              .mock(r"/season-\d+/episode-\d+/SearchOnlyTVSeries-on$", MockResponse::fixture(EPISODE_PAGE))
+              .mock(r"/season-\d+/SearchOnlyTVSeries-on$", MockResponse::fixture(SEARCH_RESULTS_PAGE));
+
+        server
+    }
+
     fn get_client() -> Client {
         reqwest::Client::builder()
             .user_agent("Google Chrome")
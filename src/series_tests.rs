@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod series_tests {
+    use reqwest::Client;
+
+    use crate::{get_series_by_imdb_id, get_series_by_mask};
+    use crate::domain::domain::Series;
+    use crate::error::error::OperationError;
+    use crate::mock_server::mock_server::{MockResponse, MockServer};
+
+    const SERIES_PAGE: &str = "series-page.html";
+    const SERIES_SEARCH_PAGE: &str = "series-search-page.html";
+
+    #[tokio::test]
+    async fn series_should_be_found_by_mask() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search2/sublanguageid-rus/moviename-adventure\+time/SearchOnlyTVSeries-on$",
+                    MockResponse::fixture(SERIES_SEARCH_PAGE));
+        server.mock(r"^/en/ssearch/sublanguageid-rus/idmovie-101356$", MockResponse::fixture(SERIES_PAGE));
+
+        let series = get_series_by_mask(&Client::new(), &server.url(), "en", "adventure time", "rus")
+            .await.unwrap().expect("series expected");
+
+        assert_eq!("Adventure Time", series.title);
+        assert_eq!(Some("tt1305826"), series.imdb_id.as_deref());
+        assert_eq!(vec![1, 2], series.seasons.iter().map(|season| season.number).collect::<Vec<u8>>());
+        assert_eq!(3, series.seasons[0].episodes.len());
+    }
+
+    #[tokio::test]
+    async fn series_search_without_shows_should_return_none() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search2/", MockResponse::html(
+            "<html><body><table id=\"search_results\"><tbody></tbody></table></body></html>"
+        ));
+
+        let result = get_series_by_mask(&Client::new(), &server.url(), "en", "nothing", "rus").await;
+
+        assert!(result.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn subtitle_counts_should_be_collected_per_language() {
+        let server = MockServer::start();
+        server.mock(r"^/ru/ssearch/sublanguageid-rus/imdbid-1305826$", MockResponse::fixture(SERIES_PAGE));
+        server.mock(r"^/ru/ssearch/sublanguageid-eng/imdbid-1305826$", MockResponse::html(
+            &get_english_series_page()
+        ));
+
+        let series = get_series_by_imdb_id(&Client::new(), &server.url(), "ru", "tt1305826", "rus,eng")
+            .await.unwrap();
+
+        assert_eq!(vec![(1, 1, 3, 7), (1, 2, 1, 0), (1, 3, 0, 0), (1, 4, 0, 2), (2, 1, 2, 0), (2, 2, 0, 0)],
+                   get_counts(&series));
+
+        assert_eq!(2, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn series_page_errors_should_be_returned() {
+        let server = MockServer::start();
+        server.mock(r"^/en/ssearch/sublanguageid-rus/", MockResponse::fixture(SERIES_PAGE));
+        server.mock(r"^/en/ssearch/sublanguageid-eng/", MockResponse::status(503));
+
+        match get_series_by_imdb_id(&Client::new(), &server.url(), "en", "1305826", "rus,eng").await {
+            Err(OperationError::Error) => {}
+            _ => panic!("error expected")
+        }
+
+        match get_series_by_imdb_id(&Client::new(), &server.url(), "en", "1305826", "").await {
+            Err(OperationError::InvalidArgument(_)) => {}
+            _ => panic!("invalid argument error expected")
+        }
+    }
+
+    /// Only two episodes have english subtitles, the last one is missing on russian page
+    fn get_english_series_page() -> String {
+        let episode_row = |episode: u16, count: u32| format!(
+            "<tr itemprop=\"episode\" itemscope=\"\" itemtype=\"http://schema.org/TVEpisode\">\
+             <td><span itemprop=\"episodeNumber\">{}</span>. <a itemprop=\"url\" href=\"/en/search/sublanguageid-eng/imdbid-{}\">\
+             <span itemprop=\"name\">Episode {}</span></a></td><td>{}</td><td></td></tr>",
+            episode, 1512315 + episode as u32, episode, count
+        );
+
+        format!(
+            "<html lang=\"en\"><body><h1><span itemprop=\"name\">&quot;Adventure Time&quot; subtitles</span></h1>\
+             <table id=\"search_results\"><tbody><tr><td colspan=\"3\"><span id=\"season-1\"><b>Season 1</b></span></td></tr>\
+             {}{}</tbody></table></body></html>",
+            episode_row(1, 7), episode_row(4, 2)
+        )
+    }
+
+    /// (season, episode, rus, eng)
+    fn get_counts(series: &Series) -> Vec<(u8, u16, u32, u32)> {
+        series.seasons.iter()
+              .flat_map(|season| season.episodes.iter())
+              .map(|episode| (
+                  episode.season, episode.episode,
+                  episode.subtitle_counts["rus"], episode.subtitle_counts["eng"]
+              ))
+              .collect()
+    }
+}
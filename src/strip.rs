@@ -1,30 +1,50 @@
 pub mod strip {
+    use std::sync::OnceLock;
+
     use regex::Regex;
 
     const OPEN_TAG_PATTERN: &str = "(<\\s*\\w[^>]*>)";
     const CLOSE_TAG_PATTERN: &str = "</[\\w+]+>";
-    const SPACE_CHARACTER_CODE: &str = "&nbsp;";
-    const SPACE_CHARACTER_UNICODE: &str = "\u{a0}";
+    const LINE_BREAK_TAG_PATTERN: &str = "(?i)<\\s*br\\s*/?\\s*>";
     const BLANK_VALUE: &str = "";
     const SPACE_CHARACTER_VALUE: &str = " ";
+    const LINE_BREAK_VALUE: &str = "\n";
+
+    // Used for every table cell, compiled once
+    static OPEN_TAG_REGEX: OnceLock<Regex> = OnceLock::new();
+    static CLOSE_TAG_REGEX: OnceLock<Regex> = OnceLock::new();
+    static LINE_BREAK_TAG_REGEX: OnceLock<Regex> = OnceLock::new();
+
+    /**
+    Plain text of html fragment: tags are removed, entities are decoded (`&quot;`, `&#39;`, `&#x27;`),
+    whitespace runs (including `&nbsp;` and source line breaks) are collapsed to a single space.
 
+    `<br>` tags become line breaks.
+    */
     pub fn strip_html_tags(text: &str) -> String {
-        let open_tag_regex = Regex::new(OPEN_TAG_PATTERN).unwrap();
-        let close_tag_regex = Regex::new(CLOSE_TAG_PATTERN).unwrap();
+        let line_break_tag_regex = LINE_BREAK_TAG_REGEX.get_or_init(|| Regex::new(LINE_BREAK_TAG_PATTERN).unwrap());
+        let open_tag_regex = OPEN_TAG_REGEX.get_or_init(|| Regex::new(OPEN_TAG_PATTERN).unwrap());
+        let close_tag_regex = CLOSE_TAG_REGEX.get_or_init(|| Regex::new(CLOSE_TAG_PATTERN).unwrap());
 
-        let removed_open_tags = open_tag_regex.replace_all(text, BLANK_VALUE).to_string();
-        let removed_close_tags = close_tag_regex.replace_all(
-            &removed_open_tags, BLANK_VALUE
-        ).to_string();
+        let lines: Vec<String> = line_break_tag_regex.split(text).map(|line| {
+            let removed_open_tags = open_tag_regex.replace_all(line, BLANK_VALUE).to_string();
+            let removed_close_tags = close_tag_regex.replace_all(
+                &removed_open_tags, BLANK_VALUE
+            ).to_string();
 
-        let removed_space_character_codes = removed_close_tags.replace(
-            SPACE_CHARACTER_CODE, SPACE_CHARACTER_VALUE
-        ).to_string();
+            collapse_whitespace(&decode_html_entities(&removed_close_tags))
+        }).collect();
 
-        let removed_space_character_unicodes = removed_space_character_codes.replace(
-            SPACE_CHARACTER_UNICODE, SPACE_CHARACTER_VALUE
-        ).to_string();
+        lines.join(LINE_BREAK_VALUE).trim().to_string()
+    }
+
+    /// All HTML5 named and numeric entities: `&quot;Adventure Time&quot;` -> `"Adventure Time"`
+    pub fn decode_html_entities(text: &str) -> String {
+        html_escape::decode_html_entities(text).to_string()
+    }
 
-        String::from(removed_space_character_unicodes)
+    /// `Adventure  Time\n(2017)` -> `Adventure Time (2017)`, non-breaking spaces are whitespace too
+    pub fn collapse_whitespace(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<&str>>().join(SPACE_CHARACTER_VALUE)
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod strip_tests {
-    use crate::strip::strip::strip_html_tags;
+    use crate::strip::strip::{collapse_whitespace, decode_html_entities, strip_html_tags};
 
     #[test]
     fn html_tags_should_be_remove_from_string() {
@@ -10,7 +10,7 @@ mod strip_tests {
         );
         assert_eq!(
             strip_html_tags("Hi<br>How about multiple tags?<span>*</span>"),
-            "HiHow about multiple tags?*"
+            "Hi\nHow about multiple tags?*"
         );
         assert_eq!(
             strip_html_tags("How <span style=\"font-size: 12px\">about</span> html-attributes?"),
@@ -34,4 +34,27 @@ mod strip_tests {
             "Терминатор: Хроники Сары Коннор / Битва за будущее / Terminator: The Sarah Connor Chronicles / Сезон: 2 / Серии: 1-22 (22) [2008, США, фантастика, боевик, драма, BDRemux 1080p] MVO (DD 5.1 LostFilm)"
         );
     }
+
+    #[test]
+    fn html_entities_should_be_decoded() {
+        assert_eq!(
+            strip_html_tags("&quot;Adventure Time&quot; Tom &amp; Jerry&#39;s &#x27;Quest&#x27; &lt;b&gt; &eacute;t&eacute; &hellip;"),
+            "\"Adventure Time\" Tom & Jerry's 'Quest' <b> été …"
+        );
+        assert_eq!(decode_html_entities("&#1055;&#x440;&#1080;&#1074;&#1077;&#1090;"), "Привет");
+        assert_eq!(decode_html_entities("&unknown; & text"), "&unknown; & text");
+    }
+
+    #[test]
+    fn whitespace_should_be_collapsed_and_br_should_be_line_break() {
+        assert_eq!(
+            strip_html_tags("  \"Adventure Time\"   Bonnibel\n\t Bubblegum\n(2017)  "),
+            "\"Adventure Time\" Bonnibel Bubblegum (2017)"
+        );
+        assert_eq!(
+            strip_html_tags("Season 1 <BR/> Episode&nbsp;2<br />\n  Russian"),
+            "Season 1\nEpisode 2\nRussian"
+        );
+        assert_eq!(collapse_whitespace("a\u{a0}\u{a0}b \n c"), "a b c");
+    }
 }
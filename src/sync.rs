@@ -0,0 +1,254 @@
+pub mod sync {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::cue::cue::Cue;
+    use crate::error::error::OperationError;
+    use crate::timing::timing::apply_time_mapping;
+    use crate::types::types::OperationResult;
+
+    /// Drift candidates: same speed and common frame rate conversions
+    const DRIFT_CANDIDATES: [f64; 9] = [
+        1.0,
+        25.0 / 23.976, 23.976 / 25.0,
+        25.0 / 24.0, 24.0 / 25.0,
+        24.0 / 23.976, 23.976 / 24.0,
+        30.0 / 29.97, 29.97 / 30.0
+    ];
+
+    /// Offset candidates checked per drift
+    const OFFSET_CANDIDATES_PER_DRIFT: usize = 3;
+
+    /// Cues with durations that differ more are not considered as a pair
+    const MIN_DURATION_SIMILARITY: f64 = 0.5;
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub struct SyncOptions {
+        /// Max distance (ms) between cue starts to treat cues as matched
+        pub tolerance: u64,
+
+        /// Max offset (ms) to search for
+        pub max_offset: u64
+    }
+
+    impl Default for SyncOptions {
+        fn default() -> Self {
+            SyncOptions {
+                tolerance: 400,
+                max_offset: 10 * 60 * 1000
+            }
+        }
+    }
+
+    /// Estimated retiming: `correct_time = time * drift + offset`
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub struct SyncResult {
+        /// Offset in milliseconds
+        pub offset: f64,
+
+        pub drift: f64,
+
+        /// Target cues matched to reference cues after retiming
+        pub matched: usize,
+
+        /// Fit quality from 0.0 (no fit) to 1.0 (every cue matched exactly)
+        pub score: f32
+    }
+
+    /**
+    Estimate offset and drift of `target` cues against well-timed `reference` cues.
+
+    Tracks may be in different languages: only cue patterns (start times, durations, gaps)
+    are compared.
+    */
+    pub fn estimate_sync(reference: &[Cue], target: &[Cue], options: &SyncOptions) -> OperationResult<SyncResult> {
+        info!("estimate sync, reference cues: {}, target cues: {}", reference.len(), target.len());
+
+        if reference.is_empty() || target.is_empty() {
+            return Err(OperationError::InvalidArgument(
+                String::from("reference and target cues are required")
+            ))
+        }
+
+        let mut reference_starts: Vec<u64> = reference.iter().map(|cue| cue.start).collect();
+        reference_starts.sort_unstable();
+
+        let mut best: Option<SyncResult> = None;
+
+        for drift in DRIFT_CANDIDATES.iter() {
+            for offset in get_offset_candidates(reference, target, *drift, options) {
+                let candidate = evaluate(&reference_starts, reference.len(), target,
+                                         *drift, offset, options);
+
+                if is_better(&candidate, &best) {
+                    best = Some(candidate);
+                }
+            }
+        }
+
+        let mut result = best.unwrap_or(SyncResult { offset: 0.0, drift: 1.0, matched: 0, score: 0.0 });
+
+        if let Some((drift, offset)) = refine(&reference_starts, target, &result, options) {
+            let refined = evaluate(&reference_starts, reference.len(), target, drift, offset, options);
+
+            if refined.matched >= result.matched {
+                result = refined;
+            }
+        }
+
+        info!("sync result: {:?}", result);
+
+        Ok(result)
+    }
+
+    /// Estimate sync and retime `target` cues, returns estimation
+    pub fn auto_sync(reference: &[Cue], target: &mut [Cue], options: &SyncOptions) -> OperationResult<SyncResult> {
+        let result = estimate_sync(reference, target, options)?;
+
+        apply_time_mapping(target, |time| {
+            (time as f64 * result.drift + result.offset).round().max(0.0) as u64
+        });
+
+        Ok(result)
+    }
+
+    /// Vote for offsets between cue pairs with similar durations
+    fn get_offset_candidates(reference: &[Cue], target: &[Cue], drift: f64,
+                             options: &SyncOptions) -> Vec<f64> {
+        let bin_size = (options.tolerance / 2).max(1) as f64;
+        let mut votes: HashMap<i64, f64> = HashMap::new();
+
+        for target_cue in target {
+            let target_start = target_cue.start as f64 * drift;
+            let target_duration = target_cue.duration() as f64 * drift;
+
+            for reference_cue in reference {
+                let offset = reference_cue.start as f64 - target_start;
+
+                if offset.abs() > options.max_offset as f64 {
+                    continue;
+                }
+
+                let similarity = get_similarity(reference_cue.duration() as f64, target_duration);
+
+                if similarity >= MIN_DURATION_SIMILARITY {
+                    *votes.entry((offset / bin_size).round() as i64).or_insert(0.0) += similarity;
+                }
+            }
+        }
+
+        let mut bins: Vec<(i64, f64)> = votes.into_iter().collect();
+        bins.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then(a.0.cmp(&b.0)));
+
+        bins.into_iter()
+            .take(OFFSET_CANDIDATES_PER_DRIFT)
+            .map(|(bin, _)| bin as f64 * bin_size)
+            .collect()
+    }
+
+    fn evaluate(reference_starts: &[u64], reference_count: usize, target: &[Cue],
+                drift: f64, offset: f64, options: &SyncOptions) -> SyncResult {
+        let mut matched = 0;
+        let mut total_distance = 0.0;
+
+        for cue in target {
+            let start = cue.start as f64 * drift + offset;
+
+            if let Some(distance) = get_nearest_distance(reference_starts, start) {
+                if distance <= options.tolerance as f64 {
+                    matched += 1;
+                    total_distance += distance;
+                }
+            }
+        }
+
+        let score = if matched > 0 {
+            let match_ratio = matched as f64 / reference_count.min(target.len()) as f64;
+            let mean_distance = total_distance / matched as f64;
+
+            (match_ratio * (1.0 - 0.5 * mean_distance / options.tolerance.max(1) as f64)).min(1.0)
+
+        } else {
+            0.0
+        };
+
+        SyncResult { offset, drift, matched, score: score as f32 }
+    }
+
+    /// Least squares fit on matched cue pairs
+    fn refine(reference_starts: &[u64], target: &[Cue], result: &SyncResult,
+              options: &SyncOptions) -> Option<(f64, f64)> {
+        let pairs: Vec<(f64, f64)> = target.iter().filter_map(|cue| {
+            let start = cue.start as f64 * result.drift + result.offset;
+            let nearest = get_nearest(reference_starts, start)? as f64;
+
+            if (nearest - start).abs() <= options.tolerance as f64 {
+                Some((cue.start as f64, nearest))
+            } else {
+                None
+            }
+        }).collect();
+
+        if pairs.len() < 2 {
+            return None;
+        }
+
+        let count = pairs.len() as f64;
+        let mean_x = pairs.iter().map(|pair| pair.0).sum::<f64>() / count;
+        let mean_y = pairs.iter().map(|pair| pair.1).sum::<f64>() / count;
+
+        let covariance: f64 = pairs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let variance: f64 = pairs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+        if variance == 0.0 {
+            return None;
+        }
+
+        let drift = covariance / variance;
+
+        if drift <= 0.0 {
+            return None;
+        }
+
+        Some((drift, mean_y - drift * mean_x))
+    }
+
+    fn is_better(candidate: &SyncResult, best: &Option<SyncResult>) -> bool {
+        match best {
+            Some(best) => candidate.score > best.score ||
+                (candidate.score == best.score && (candidate.drift - 1.0).abs() < (best.drift - 1.0).abs()),
+            None => true
+        }
+    }
+
+    fn get_nearest(sorted_values: &[u64], value: f64) -> Option<u64> {
+        let position = sorted_values.partition_point(|item| (*item as f64) < value);
+
+        let before = position.checked_sub(1).and_then(|index| sorted_values.get(index));
+        let after = sorted_values.get(position);
+
+        match (before, after) {
+            (Some(before), Some(after)) => {
+                if value - *before as f64 <= *after as f64 - value { Some(*before) } else { Some(*after) }
+            }
+            (Some(before), None) => Some(*before),
+            (None, Some(after)) => Some(*after),
+            (None, None) => None
+        }
+    }
+
+    fn get_nearest_distance(sorted_values: &[u64], value: f64) -> Option<f64> {
+        get_nearest(sorted_values, value).map(|nearest| (nearest as f64 - value).abs())
+    }
+
+    fn get_similarity(first: f64, second: f64) -> f64 {
+        let max = first.max(second);
+
+        if max == 0.0 {
+            1.0
+        } else {
+            first.min(second) / max
+        }
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod sync_tests {
+    use crate::cue::cue::Cue;
+    use crate::sync::sync::{auto_sync, estimate_sync, SyncOptions};
+
+    #[test]
+    fn offset_should_be_estimated() {
+        let reference = get_reference_cues(120);
+        let target = get_mistimed_cues(&reference, -2500.0, 1.0);
+
+        let result = estimate_sync(&reference, &target, &SyncOptions::default()).unwrap();
+
+        assert!((result.offset + 2500.0).abs() < 50.0, "{:?}", result);
+        assert!((result.drift - 1.0).abs() < 0.001, "{:?}", result);
+        assert!(result.score > 0.8, "{:?}", result);
+    }
+
+    #[test]
+    fn offset_and_drift_should_be_estimated() {
+        let reference = get_reference_cues(150);
+        let target = get_mistimed_cues(&reference, 7000.0, 23.976 / 25.0);
+
+        let result = estimate_sync(&reference, &target, &SyncOptions::default()).unwrap();
+
+        assert!((result.drift - 25.0 / 23.976).abs() < 0.001, "{:?}", result);
+        assert!(result.score > 0.8, "{:?}", result);
+    }
+
+    #[test]
+    fn target_cues_should_be_retimed() {
+        let reference = get_reference_cues(100);
+        let mut target = get_mistimed_cues(&reference, 1200.0, 24.0 / 25.0);
+
+        auto_sync(&reference, &mut target, &SyncOptions::default()).unwrap();
+
+        let matched = target.iter()
+            .filter(|cue| reference.iter().any(|item| (item.start as i64 - cue.start as i64).abs() < 100))
+            .count();
+
+        assert!(matched as f64 > target.len() as f64 * 0.9);
+    }
+
+    #[test]
+    fn unrelated_tracks_should_have_low_score() {
+        let reference = get_reference_cues(100);
+
+        let target: Vec<Cue> = (0..100)
+            .map(|index| Cue::new(index + 1, index as u64 * 3000, index as u64 * 3000 + 2000, "x"))
+            .collect();
+
+        let result = estimate_sync(&reference, &target, &SyncOptions::default()).unwrap();
+
+        assert!(result.score < 0.5, "{:?}", result);
+    }
+
+    #[test]
+    fn empty_tracks_should_be_rejected() {
+        assert!(estimate_sync(&[], &get_reference_cues(5), &SyncOptions::default()).is_err());
+    }
+
+    /// Irregular but deterministic cue pattern
+    fn get_reference_cues(count: u32) -> Vec<Cue> {
+        let mut seed: u64 = 42;
+        let mut time: u64 = 5000;
+
+        (0..count).map(|index| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let duration = 800 + (seed >> 33) % 3200;
+
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let gap = 200 + (seed >> 33) % 6000;
+
+            let cue = Cue::new(index + 1, time, time + duration, "reference");
+            time += duration + gap;
+
+            cue
+        }).collect()
+    }
+
+    /// Cues with `reference = target * drift + offset` timing, every 10th cue dropped, small jitter added
+    fn get_mistimed_cues(reference: &[Cue], offset: f64, speed: f64) -> Vec<Cue> {
+        let drift = 1.0 / speed;
+
+        reference.iter()
+            .enumerate()
+            .filter(|(position, _)| position % 10 != 7)
+            .map(|(position, cue)| {
+                let jitter = (position % 5) as f64 * 15.0 - 30.0;
+                let start = ((cue.start as f64 - offset) / drift + jitter).max(0.0) as u64;
+                let end = ((cue.end as f64 - offset) / drift).max(0.0) as u64;
+
+                Cue::new(cue.index, start, end.max(start), "target")
+            })
+            .collect()
+    }
+}
@@ -2,6 +2,7 @@
 pub mod test_utils {
     use std::fs::File;
     use std::io::Read;
+    use std::path::PathBuf;
 
     use encoding::{DecoderTrap, Encoding};
     use encoding::all::WINDOWS_1251;
@@ -24,7 +25,7 @@ pub mod test_utils {
                 Root::builder()
                     .appender("file")
                     .build(level)
-            ).expect(&format!("unable to create log file 'parser.log'"))
+            ).expect("unable to create logging config")
     }
 
     fn get_file_appender_definition(level: LevelFilter) -> Appender {
@@ -37,10 +38,15 @@ pub mod test_utils {
     fn get_file_appender() -> FileAppender {
         FileAppender::builder()
             .encoder(get_encoder())
-            .build("parser.log")
+            .build(get_log_file_path())
             .unwrap()
     }
 
+    /// Test log is written outside of the source tree
+    fn get_log_file_path() -> PathBuf {
+        std::env::temp_dir().join("opensubs-rs").join("parser.log")
+    }
+
     fn get_encoder() -> Box<PatternEncoder> {
         Box::new(PatternEncoder::new("{d(%Y-%m-%d %H:%M:%S)} - {l} - [{M}] - {m}{n}"))
     }
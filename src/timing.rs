@@ -0,0 +1,103 @@
+pub mod timing {
+    use crate::cue::cue::Cue;
+    use crate::error::error::OperationError;
+    use crate::types::types::OperationResult;
+
+    /// Correct time for a cue: time in the subtitle (ms) and the time it should be shown at (ms)
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct SyncPoint {
+        pub original: u64,
+        pub correct: u64
+    }
+
+    /// Shift all cues by `offset` milliseconds (negative - earlier)
+    pub fn shift(cues: &mut [Cue], offset: i64) {
+        info!("shift cues by {} ms", offset);
+        apply_time_mapping(cues, |time| shift_time(time, offset));
+    }
+
+    /// Shift only cues starting inside `from..to` range (milliseconds)
+    pub fn shift_range(cues: &mut [Cue], from: u64, to: u64, offset: i64) {
+        info!("shift cues in range {}..{} ms by {} ms", from, to, offset);
+
+        for cue in cues.iter_mut().filter(|cue| cue.start >= from && cue.start < to) {
+            map_cue_times(cue, |time| shift_time(time, offset));
+        }
+    }
+
+    /// Retime cues made for `from_fps` video to `to_fps` video (e.g. 23.976 -> 25)
+    pub fn convert_fps(cues: &mut [Cue], from_fps: f64, to_fps: f64) -> OperationResult<()> {
+        info!("convert cues from {} to {} fps", from_fps, to_fps);
+
+        if !(from_fps > 0.0 && to_fps > 0.0) {
+            return Err(OperationError::InvalidArgument(
+                format!("fps values must be positive: {} -> {}", from_fps, to_fps)
+            ))
+        }
+
+        let ratio = from_fps / to_fps;
+        apply_time_mapping(cues, |time| scale_time(time, ratio, 0.0));
+
+        Ok(())
+    }
+
+    /**
+    Linear resync by two points.
+
+    User supplies correct times for one early and one late cue, offset and drift
+    are calculated from them and applied to all cues.
+    */
+    pub fn linear_resync(cues: &mut [Cue], first: SyncPoint, second: SyncPoint) -> OperationResult<()> {
+        info!("linear resync by {:?} and {:?}", first, second);
+
+        if first.original == second.original {
+            return Err(OperationError::InvalidArgument(
+                String::from("sync points must have different original times")
+            ))
+        }
+
+        let ratio = (second.correct as f64 - first.correct as f64) /
+                    (second.original as f64 - first.original as f64);
+
+        if ratio <= 0.0 {
+            return Err(OperationError::InvalidArgument(
+                String::from("sync points must keep cue order")
+            ))
+        }
+
+        let offset = first.correct as f64 - first.original as f64 * ratio;
+        debug!("ratio {}, offset {} ms", ratio, offset);
+
+        apply_time_mapping(cues, |time| scale_time(time, ratio, offset));
+
+        Ok(())
+    }
+
+    /// Map start and end times of every cue, cue is never allowed to end before it starts
+    pub fn apply_time_mapping(cues: &mut [Cue], mapping: impl Fn(u64) -> u64) {
+        for cue in cues.iter_mut() {
+            map_cue_times(cue, &mapping);
+        }
+    }
+
+    fn map_cue_times(cue: &mut Cue, mapping: impl Fn(u64) -> u64) {
+        let start = mapping(cue.start);
+        let end = mapping(cue.end);
+
+        cue.start = start;
+        cue.end = end.max(start);
+    }
+
+    fn shift_time(time: u64, offset: i64) -> u64 {
+        if offset < 0 {
+            time.saturating_sub(offset.unsigned_abs())
+
+        } else {
+            time.saturating_add(offset as u64)
+        }
+    }
+
+    fn scale_time(time: u64, ratio: f64, offset: f64) -> u64 {
+        (time as f64 * ratio + offset).round().max(0.0) as u64
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod timing_tests {
+    use crate::cue::cue::Cue;
+    use crate::timing::timing::{convert_fps, linear_resync, shift, shift_range, SyncPoint};
+
+    #[test]
+    fn cues_should_be_shifted_forward_and_backward() {
+        let mut cues = get_cues();
+
+        shift(&mut cues, 1500);
+        assert_eq!((2500, 3500), (cues[0].start, cues[0].end));
+
+        shift(&mut cues, -2000);
+        assert_eq!((500, 1500), (cues[0].start, cues[0].end));
+        assert_eq!((9500, 11500), (cues[1].start, cues[1].end));
+    }
+
+    #[test]
+    fn negative_shift_should_not_go_below_zero() {
+        let mut cues = get_cues();
+
+        shift(&mut cues, -1500);
+
+        assert_eq!((0, 500), (cues[0].start, cues[0].end));
+        assert_eq!((8500, 10500), (cues[1].start, cues[1].end));
+
+        shift(&mut cues, -5000);
+        assert_eq!((0, 0), (cues[0].start, cues[0].end));
+    }
+
+    #[test]
+    fn only_cues_from_range_should_be_shifted() {
+        let mut cues = get_cues();
+
+        shift_range(&mut cues, 5000, 20000, -500);
+
+        assert_eq!((1000, 2000), (cues[0].start, cues[0].end));
+        assert_eq!((9500, 11500), (cues[1].start, cues[1].end));
+        assert_eq!((60000, 61000), (cues[2].start, cues[2].end));
+    }
+
+    #[test]
+    fn fps_conversion_should_scale_times() {
+        let mut cues = get_cues();
+
+        convert_fps(&mut cues, 25.0, 23.976).unwrap();
+
+        assert_eq!((62563, 63605), (cues[2].start, cues[2].end));
+        assert!(convert_fps(&mut cues, 0.0, 25.0).is_err());
+    }
+
+    #[test]
+    fn two_point_resync_should_fix_offset_and_drift() {
+        let mut cues = get_cues();
+
+        linear_resync(
+            &mut cues,
+            SyncPoint { original: 1000, correct: 3000 },
+            SyncPoint { original: 60000, correct: 120000 }
+        ).unwrap();
+
+        assert_eq!((3000, 4983), (cues[0].start, cues[0].end));
+        assert_eq!((20847, 24814), (cues[1].start, cues[1].end));
+        assert_eq!((120000, 121983), (cues[2].start, cues[2].end));
+    }
+
+    #[test]
+    fn resync_with_invalid_points_should_be_rejected() {
+        let mut cues = get_cues();
+
+        let first = SyncPoint { original: 1000, correct: 3000 };
+
+        assert!(linear_resync(&mut cues, first, SyncPoint { original: 1000, correct: 5000 }).is_err());
+        assert!(linear_resync(&mut cues, first, SyncPoint { original: 9000, correct: 1000 }).is_err());
+        assert_eq!(get_cues(), cues);
+    }
+
+    fn get_cues() -> Vec<Cue> {
+        vec![
+            Cue::new(1, 1000, 2000, "One"),
+            Cue::new(2, 10000, 12000, "Two"),
+            Cue::new(3, 60000, 61000, "Three")
+        ]
+    }
+}
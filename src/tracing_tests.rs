@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tracing_tests {
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+    use tracing_subscriber::registry::Registry;
+
+    use crate::{download_subtitles, search_by_mask};
+    use crate::mock_server::mock_server::{MockResponse, MockServer};
+
+    type SpanFields = HashMap<String, String>;
+
+    /// Keeps names and fields of all spans
+    #[derive(Clone, Default)]
+    struct SpanCollector {
+        spans: Arc<Mutex<Vec<(String, SpanFields)>>>,
+        indexes: Arc<Mutex<HashMap<u64, usize>>>
+    }
+
+    impl SpanCollector {
+        fn get_spans(&self, name: &str) -> Vec<SpanFields> {
+            self.spans.lock().unwrap().iter()
+                .filter(|(span_name, _)| span_name == name)
+                .map(|(_, fields)| fields.clone())
+                .collect()
+        }
+    }
+
+    struct FieldVisitor<'a>(&'a mut SpanFields);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.insert(field.name().to_string(), format!("{:?}", value).trim_matches('"').to_string());
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for SpanCollector {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _: Context<'_, S>) {
+            let mut fields = SpanFields::new();
+            attrs.record(&mut FieldVisitor(&mut fields));
+
+            let mut spans = self.spans.lock().unwrap();
+            self.indexes.lock().unwrap().insert(id.into_u64(), spans.len());
+            spans.push((attrs.metadata().name().to_string(), fields));
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, _: Context<'_, S>) {
+            if let Some(index) = self.indexes.lock().unwrap().get(&id.into_u64()) {
+                values.record(&mut FieldVisitor(&mut self.spans.lock().unwrap()[*index].1));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn search_should_have_search_fetch_and_parse_spans() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search/", MockResponse::fixture("series-search-results.html"));
+
+        let collector = SpanCollector::default();
+        let _guard = tracing::subscriber::set_default(Registry::default().with(collector.clone()));
+
+        let results = search_by_mask(&reqwest::Client::new(), &server.url(), "adventure time", "rus").await.unwrap();
+
+        let search_spans = collector.get_spans("search");
+        assert_eq!(1, search_spans.len());
+        assert_eq!("mask", search_spans[0]["kind"]);
+        assert_eq!("adventure time", search_spans[0]["mask"]);
+        assert_eq!("rus", search_spans[0]["sub_langs"]);
+        assert_eq!(results.len().to_string(), search_spans[0]["result_count"]);
+
+        let fetch_spans = collector.get_spans("fetch");
+        assert_eq!(1, fetch_spans.len());
+        assert_eq!(format!("{}/en/search/sublanguageid-rus/moviename-adventure+time", server.url()),
+                   fetch_spans[0]["url"]);
+        assert_eq!("200", fetch_spans[0]["status"]);
+        assert!(fetch_spans[0].contains_key("elapsed_ms"));
+
+        let parse_spans = collector.get_spans("parse");
+        assert_eq!(1, parse_spans.len());
+        assert_eq!("MultipleOptions", parse_spans[0]["page_type"]);
+    }
+
+    #[tokio::test]
+    async fn failed_fetch_should_have_status() {
+        let server = MockServer::start();
+        server.mock(r"^/en/search/", MockResponse::status(503));
+
+        let collector = SpanCollector::default();
+        let _guard = tracing::subscriber::set_default(Registry::default().with(collector.clone()));
+
+        assert!(search_by_mask(&reqwest::Client::new(), &server.url(), "adventure time", "rus").await.is_err());
+
+        assert_eq!("503", collector.get_spans("fetch")[0]["status"]);
+        assert!(!collector.get_spans("search")[0].contains_key("result_count"));
+        assert!(collector.get_spans("parse").is_empty());
+    }
+
+    #[tokio::test]
+    async fn download_should_have_span() {
+        let server = MockServer::start();
+        server.mock(r"^/en/subtitleserve/sub/7863206$",
+                    MockResponse::bytes(b"1\n00:00:01,000 --> 00:00:02,000\nHello\n", "text/plain"));
+
+        let collector = SpanCollector::default();
+        let _guard = tracing::subscriber::set_default(Registry::default().with(collector.clone()));
+
+        download_subtitles(&reqwest::Client::new(), &server.url(), "/en/subtitleserve/sub/7863206").await.unwrap();
+
+        let download_spans = collector.get_spans("download");
+        assert_eq!(1, download_spans.len());
+        assert_eq!("/en/subtitleserve/sub/7863206", download_spans[0]["url"]);
+        assert_eq!("200", download_spans[0]["status"]);
+        assert_eq!("1", download_spans[0]["file_count"]);
+        assert!(download_spans[0].contains_key("elapsed_ms"));
+    }
+}
@@ -0,0 +1,151 @@
+pub mod transport {
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use async_trait::async_trait;
+    use encoding::DecoderTrap;
+    use encoding::label::encoding_from_whatwg_label;
+
+    use crate::error::error::OperationError;
+    use crate::types::types::OperationResult;
+
+    /// Response as returned by transport, redirects are followed by transport
+    #[derive(Clone, Debug, PartialEq, Default)]
+    pub struct HttpResponse {
+        pub status: u16,
+        pub headers: Vec<(String, String)>,
+        pub body: Vec<u8>
+    }
+
+    impl HttpResponse {
+        /// Header value, name is case insensitive
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers.iter()
+                        .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+                        .map(|(_, value)| value.as_str())
+        }
+
+        /// Body decoded with `Content-Type` charset, UTF-8 by default
+        pub fn text(&self) -> String {
+            let charset = self.header("Content-Type")
+                              .and_then(|content_type| {
+                                  content_type.split(';')
+                                              .map(|part| part.trim())
+                                              .find_map(|part| part.strip_prefix("charset="))
+                              })
+                              .map(|charset| charset.trim_matches('"').to_string());
+
+            match charset.as_deref().and_then(encoding_from_whatwg_label) {
+                Some(encoding) => encoding.decode(&self.body, DecoderTrap::Replace)
+                                          .unwrap_or_else(|_| String::from_utf8_lossy(&self.body).to_string()),
+                None => String::from_utf8_lossy(&self.body).to_string()
+            }
+        }
+    }
+
+    /**
+    HTTP stack used by library functions, `reqwest::Client` is the default implementation.
+
+    Implement it for a proxy-aware client, a caching layer or a test double
+    and pass it wherever `&reqwest::Client` is accepted.
+    */
+    #[async_trait]
+    pub trait HttpTransport: Send + Sync {
+        async fn get(&self, url: &str) -> OperationResult<HttpResponse>;
+
+        async fn post(&self, url: &str, content_type: &str, body: Vec<u8>) -> OperationResult<HttpResponse>;
+    }
+
+    #[async_trait]
+    impl HttpTransport for reqwest::Client {
+        async fn get(&self, url: &str) -> OperationResult<HttpResponse> {
+            get_response(reqwest::Client::get(self, url)).await
+        }
+
+        async fn post(&self, url: &str, content_type: &str, body: Vec<u8>) -> OperationResult<HttpResponse> {
+            let request = reqwest::Client::post(self, url)
+                .header(reqwest::header::CONTENT_TYPE, content_type)
+                .body(body);
+
+            get_response(request).await
+        }
+    }
+
+    /// Transport keeping min interval between requests, every request counts
+    pub struct RateLimitedTransport {
+        inner: Arc<dyn HttpTransport>,
+        interval: Duration,
+        last_request: Mutex<Option<Instant>>
+    }
+
+    impl RateLimitedTransport {
+        /// `requests_per_second` must be positive and give an interval `Duration` can hold
+        pub fn new(inner: Arc<dyn HttpTransport>, requests_per_second: f64) -> OperationResult<RateLimitedTransport> {
+            let interval = if requests_per_second > 0.0 {
+                Duration::try_from_secs_f64(1.0 / requests_per_second).ok()
+            } else {
+                None
+            };
+
+            let interval = interval.ok_or_else(|| {
+                error!("invalid requests per second value: {}", requests_per_second);
+                OperationError::InvalidArgument(format!("invalid requests_per_second: {}", requests_per_second))
+            })?;
+
+            Ok(RateLimitedTransport { inner, interval, last_request: Mutex::new(None) })
+        }
+
+        async fn wait(&self) {
+            let delay = {
+                let mut last_request = self.last_request.lock().unwrap();
+                let now = Instant::now();
+
+                let next_request = match *last_request {
+                    Some(previous) => (previous + self.interval).max(now),
+                    None => now
+                };
+
+                *last_request = Some(next_request);
+                next_request - now
+            };
+
+            if !delay.is_zero() {
+                debug!("rate limit, wait {:?}", delay);
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HttpTransport for RateLimitedTransport {
+        async fn get(&self, url: &str) -> OperationResult<HttpResponse> {
+            self.wait().await;
+            self.inner.get(url).await
+        }
+
+        async fn post(&self, url: &str, content_type: &str, body: Vec<u8>) -> OperationResult<HttpResponse> {
+            self.wait().await;
+            self.inner.post(url, content_type, body).await
+        }
+    }
+
+    async fn get_response(request: reqwest::RequestBuilder) -> OperationResult<HttpResponse> {
+        let resp = request.send().await.map_err(|e| {
+            error!("unable to get data from url: {}", e);
+            OperationError::Error
+        })?;
+
+        let status = resp.status().as_u16();
+
+        let headers = resp.headers().iter()
+                          .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+                          .collect();
+
+        let body = resp.bytes().await.map_err(|e| {
+            error!("unable to get response content: {}", e);
+            OperationError::Error
+        })?;
+
+        Ok(HttpResponse { status, headers, body: body.to_vec() })
+    }
+}
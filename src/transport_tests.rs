@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod transport_tests {
+    use std::fs;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use async_trait::async_trait;
+
+    use crate::{download_subtitles, search_by_mask};
+    use crate::client::client::Client;
+    use crate::config::config::Config;
+    use crate::error::error::OperationError;
+    use crate::mock_server::mock_server::{MockResponse, MockServer};
+    use crate::transport::transport::{HttpResponse, HttpTransport, RateLimitedTransport};
+    use crate::types::types::OperationResult;
+
+    const SEARCH_RESULTS_PAGE: &str = "tests/series-search-results.html";
+
+    /// Same response for every request, requested urls are kept
+    struct StaticTransport {
+        response: HttpResponse,
+        urls: Mutex<Vec<String>>
+    }
+
+    impl StaticTransport {
+        fn new(response: HttpResponse) -> StaticTransport {
+            StaticTransport { response, urls: Mutex::new(Vec::new()) }
+        }
+    }
+
+    #[async_trait]
+    impl HttpTransport for StaticTransport {
+        async fn get(&self, url: &str) -> OperationResult<HttpResponse> {
+            self.urls.lock().unwrap().push(url.to_string());
+            Ok(self.response.clone())
+        }
+
+        async fn post(&self, url: &str, _: &str, _: Vec<u8>) -> OperationResult<HttpResponse> {
+            self.get(url).await
+        }
+    }
+
+    #[tokio::test]
+    async fn search_should_use_transport() {
+        let transport = StaticTransport::new(HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: fs::read(SEARCH_RESULTS_PAGE).unwrap()
+        });
+
+        let results = search_by_mask(&transport, "http://site", "adventure time", "rus").await.unwrap();
+
+        assert_eq!("\"Adventure Time\" Bonnibel Bubblegum (2017)", results[0].title);
+        assert_eq!(vec!["http://site/en/search/sublanguageid-rus/moviename-adventure+time"],
+                   *transport.urls.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn unexpected_status_should_return_error() {
+        let transport = StaticTransport::new(HttpResponse { status: 503, ..HttpResponse::default() });
+
+        match search_by_mask(&transport, "http://site", "adventure time", "rus").await {
+            Err(OperationError::Error) => {}
+            _ => panic!("error expected")
+        }
+    }
+
+    #[tokio::test]
+    async fn download_should_use_attachment_filename() {
+        let transport = StaticTransport::new(HttpResponse {
+            status: 200,
+            headers: vec![(String::from("content-disposition"), String::from("attachment; filename=\"movie.srt\""))],
+            body: b"1\n00:00:01,000 --> 00:00:02,000\nHello\n".to_vec()
+        });
+
+        let files = download_subtitles(&transport, "http://site", "/en/subtitleserve/sub/7863206").await.unwrap();
+
+        assert_eq!(1, files.len());
+        assert_eq!("movie.srt", files[0].filename);
+        assert_eq!(vec!["http://site/en/subtitleserve/sub/7863206"], *transport.urls.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn attachment_filename_should_be_reduced_to_file_name() {
+        let transport = StaticTransport::new(HttpResponse {
+            status: 200,
+            headers: vec![(String::from("content-disposition"), String::from("attachment; filename=\"../../movie.srt\""))],
+            body: b"1\n00:00:01,000 --> 00:00:02,000\nHello\n".to_vec()
+        });
+
+        let files = download_subtitles(&transport, "http://site", "/en/subtitleserve/sub/7863206").await.unwrap();
+
+        assert_eq!("movie.srt", files[0].filename);
+    }
+
+    #[tokio::test]
+    async fn client_should_use_transport() {
+        let transport = StaticTransport::new(HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: fs::read(SEARCH_RESULTS_PAGE).unwrap()
+        });
+
+        let client = Client::builder().base_url("http://site").languages(&["rus"]).transport(transport).build().unwrap();
+
+        assert!(!client.search_by_mask("adventure time").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rate_limit_should_apply_to_every_transport_request() {
+        let config = Config::parse("requests_per_second = 20.0").unwrap();
+
+        let client = Client::builder().config(config).transport(StaticTransport::new(HttpResponse::default()))
+                                      .build().unwrap();
+
+        let started = Instant::now();
+
+        for _ in 0..3 {
+            client.transport().get("http://site/page").await.unwrap();
+        }
+
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn unrepresentable_rate_should_be_rejected() {
+        let inner: Arc<dyn HttpTransport> = Arc::new(StaticTransport::new(HttpResponse::default()));
+
+        assert!(RateLimitedTransport::new(inner.clone(), 1e-300).is_err());
+        assert!(RateLimitedTransport::new(inner.clone(), f64::NAN).is_err());
+        assert!(RateLimitedTransport::new(inner, 2.0).is_ok());
+    }
+
+    #[test]
+    fn text_should_be_decoded_with_charset() {
+        let response = HttpResponse {
+            status: 200,
+            headers: vec![(String::from("Content-Type"), String::from("text/html; charset=windows-1251"))],
+            body: vec![0xcf, 0xf0, 0xe8, 0xe2, 0xe5, 0xf2]
+        };
+
+        assert_eq!("Привет", response.text());
+
+        let response = HttpResponse { body: "Привет".as_bytes().to_vec(), ..HttpResponse::default() };
+
+        assert_eq!("Привет", response.text());
+    }
+
+    #[tokio::test]
+    async fn reqwest_transport_should_return_status_headers_and_body() {
+        let server = MockServer::start();
+        server.mock(r"^/page$", MockResponse::html("<html></html>").with_status(201).with_header("X-Test", "1"));
+
+        let client = reqwest::Client::new();
+
+        let response = HttpTransport::get(&client, &format!("{}/page", server.url())).await.unwrap();
+
+        assert_eq!(201, response.status);
+        assert_eq!(Some("1"), response.header("x-test"));
+        assert_eq!(b"<html></html>".to_vec(), response.body);
+    }
+
+    #[tokio::test]
+    async fn reqwest_transport_should_post_body() {
+        let server = MockServer::start();
+        server.mock(r"^/form$", MockResponse::html("ok"));
+
+        let client = reqwest::Client::new();
+
+        let response = HttpTransport::post(
+            &client, &format!("{}/form", server.url()), "application/x-www-form-urlencoded", b"a=1&b=2".to_vec()
+        ).await.unwrap();
+
+        assert_eq!("ok", response.text());
+
+        let request = &server.requests()[0];
+
+        assert_eq!("POST", request.method);
+        assert_eq!(Some("application/x-www-form-urlencoded"), request.header("Content-Type"));
+        assert_eq!(b"a=1&b=2".to_vec(), request.body);
+    }
+}
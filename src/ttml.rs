@@ -0,0 +1,460 @@
+pub mod ttml {
+    use std::collections::HashMap;
+    use std::sync::OnceLock;
+
+    use quick_xml::events::{BytesStart, Event};
+    use quick_xml::Reader;
+    use regex::Regex;
+
+    use crate::cue::cue::{Cue, Cues};
+    use crate::error::error::OperationError;
+    use crate::types::types::OperationResult;
+
+    const TTML_NAMESPACE: &str = "http://www.w3.org/ns/ttml";
+    const TTML_STYLING_NAMESPACE: &str = "http://www.w3.org/ns/ttml#styling";
+
+    const CLOCK_TIME_PATTERN: &str = "^(\\d{2,}):(\\d{2}):(\\d{2})(?:\\.(\\d+)|:(\\d{2,})(?:\\.\\d+)?)?$";
+    const OFFSET_TIME_PATTERN: &str = "^(\\d+(?:\\.\\d+)?)(h|ms|m|s|f|t)$";
+    const MARKUP_TAG_PATTERN: &str = "<(/?)([a-zA-Z]+)[^>]*>";
+    const WHITESPACE_PATTERN: &str = "\\s+";
+
+    // Used for every cue, compiled once
+    static CLOCK_TIME_REGEX: OnceLock<Regex> = OnceLock::new();
+    static OFFSET_TIME_REGEX: OnceLock<Regex> = OnceLock::new();
+    static MARKUP_TAG_REGEX: OnceLock<Regex> = OnceLock::new();
+    static WHITESPACE_REGEX: OnceLock<Regex> = OnceLock::new();
+
+    const DEFAULT_FRAME_RATE: f64 = 30.0;
+    const DEFAULT_TICK_RATE: f64 = 1.0;
+
+    /// Frame and tick rates declared on the `<tt>` element
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct TimeBase {
+        pub frame_rate: f64,
+        pub tick_rate: f64
+    }
+
+    impl Default for TimeBase {
+        fn default() -> Self {
+            TimeBase {
+                frame_rate: DEFAULT_FRAME_RATE,
+                tick_rate: DEFAULT_TICK_RATE
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, Default)]
+    struct TextStyle {
+        italic: bool,
+        bold: bool,
+        underline: bool
+    }
+
+    /// Paragraph (`<p>`) being read
+    struct Paragraph {
+        begin: u64,
+        end: Option<u64>,
+        text: String
+    }
+
+    /// Element opened by the reader: its time offset and markup tags it has opened
+    struct ElementFrame {
+        offset: u64,
+        tags: Vec<&'static str>
+    }
+
+    /**
+    Parse TTML/DFXP document into cues.
+
+    Supports clock-time (`00:00:01.500`, `00:00:01:12`), offset-time (`1.5s`, `1500ms`, `36f`)
+    and tick (`15000000t`) expressions, `<br/>` line breaks and italic, bold and underline
+    styling (inline or referenced through `<style>`).
+    */
+    pub fn parse_ttml(xml: &str) -> OperationResult<Cues> {
+        info!("parse ttml document");
+
+        let mut reader = Reader::from_str(xml);
+
+        let mut time_base = TimeBase::default();
+        let mut styles: HashMap<String, TextStyle> = HashMap::new();
+
+        let mut frames: Vec<ElementFrame> = Vec::new();
+        let mut cues: Cues = Vec::new();
+
+        let mut root_found = false;
+        let mut paragraph: Option<Paragraph> = None;
+
+        loop {
+            let event = reader.read_event().map_err(|e| {
+                error!("unable to read ttml document: {}", e);
+                OperationError::SubtitleParseError
+            })?;
+
+            match event {
+                Event::Start(ref element) | Event::Empty(ref element) => {
+                    let is_empty = matches!(event, Event::Empty(_));
+                    let name = get_local_name(element);
+                    let parent_offset = frames.last().map(|frame| frame.offset).unwrap_or(0);
+
+                    let mut frame = ElementFrame { offset: parent_offset, tags: Vec::new() };
+
+                    match name.as_str() {
+                        "tt" => {
+                            root_found = true;
+                            time_base = get_time_base(element);
+                            debug!("time base: {:?}", time_base);
+                        }
+                        "style" => {
+                            if let Some(id) = get_attribute(element, "id") {
+                                let style = get_text_style(element, &styles);
+                                styles.insert(id, style);
+                            }
+                        }
+                        "body" | "div" => {
+                            if let Some(begin) = get_attribute(element, "begin") {
+                                frame.offset = parent_offset +
+                                    parse_time_expression(&begin, &time_base).unwrap_or(0);
+                            }
+                        }
+                        "p" => {
+                            let begin = get_attribute(element, "begin")
+                                .and_then(|value| parse_time_expression(&value, &time_base))
+                                .unwrap_or(0) + parent_offset;
+
+                            let end = match get_attribute(element, "end") {
+                                Some(value) => parse_time_expression(&value, &time_base)
+                                                                .map(|end| end + parent_offset),
+                                None => get_attribute(element, "dur")
+                                    .and_then(|value| parse_time_expression(&value, &time_base))
+                                    .map(|duration| begin + duration)
+                            };
+
+                            frame.offset = begin;
+
+                            let mut text = String::new();
+                            frame.tags = open_style_tags(element, &styles, &mut text);
+
+                            paragraph = Some(Paragraph { begin, end, text });
+                        }
+                        "span" => {
+                            if let Some(paragraph) = paragraph.as_mut() {
+                                frame.tags = open_style_tags(element, &styles, &mut paragraph.text);
+                            }
+                        }
+                        "br" => {
+                            if let Some(paragraph) = paragraph.as_mut() {
+                                paragraph.text.push('\n');
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    if is_empty {
+                        close_style_tags(&frame, &mut paragraph);
+                    } else {
+                        frames.push(frame);
+                    }
+                }
+                Event::Text(text_element) => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        match text_element.unescape() {
+                            Ok(value) => paragraph.text.push_str(&collapse_whitespace(&value)),
+                            Err(e) => warn!("unable to unescape ttml text: {}", e)
+                        }
+                    }
+                }
+                Event::CData(data) => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.text.push_str(&collapse_whitespace(&String::from_utf8_lossy(&data)));
+                    }
+                }
+                Event::End(element) => {
+                    if let Some(frame) = frames.pop() {
+                        close_style_tags(&frame, &mut paragraph);
+                    }
+
+                    if element.local_name().as_ref() == b"p" {
+                        if let Some(Paragraph { begin, end, text }) = paragraph.take() {
+                            match end {
+                                Some(end) if end >= begin => {
+                                    let index = cues.len() as u32 + 1;
+                                    cues.push(Cue::new(index, begin, end, &clean_text(&text)));
+                                }
+                                _ => warn!("skip paragraph without valid end time: '{}'", text)
+                            }
+                        }
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if root_found {
+            info!("cues parsed: {}", cues.len());
+            Ok(cues)
+
+        } else {
+            error!("unable to find <tt> root element");
+            Err(OperationError::SubtitleParseError)
+        }
+    }
+
+    /// Parse TTML time expression into milliseconds
+    pub fn parse_time_expression(value: &str, time_base: &TimeBase) -> Option<u64> {
+        let value = value.trim();
+
+        let clock_time_pattern = CLOCK_TIME_REGEX.get_or_init(|| Regex::new(CLOCK_TIME_PATTERN).unwrap());
+        let offset_time_pattern = OFFSET_TIME_REGEX.get_or_init(|| Regex::new(OFFSET_TIME_PATTERN).unwrap());
+
+        if let Some(groups) = clock_time_pattern.captures(value) {
+            let hours: u64 = groups[1].parse().ok()?;
+            let minutes: u64 = groups[2].parse().ok()?;
+            let seconds: u64 = groups[3].parse().ok()?;
+
+            let mut millis = ((hours * 60 + minutes) * 60 + seconds) * 1000;
+
+            if let Some(fraction) = groups.get(4) {
+                let fraction: f64 = format!("0.{}", fraction.as_str()).parse().ok()?;
+                millis += (fraction * 1000.0).round() as u64;
+
+            } else if let Some(frames) = groups.get(5) {
+                let frames: f64 = frames.as_str().parse().ok()?;
+                millis += (frames * 1000.0 / time_base.frame_rate).round() as u64;
+            }
+
+            Some(millis)
+
+        } else if let Some(groups) = offset_time_pattern.captures(value) {
+            let count: f64 = groups[1].parse().ok()?;
+
+            let seconds = match &groups[2] {
+                "h" => count * 3600.0,
+                "m" => count * 60.0,
+                "s" => count,
+                "ms" => count / 1000.0,
+                "f" => count / time_base.frame_rate,
+                "t" => count / time_base.tick_rate,
+                _ => return None
+            };
+
+            Some((seconds * 1000.0).round() as u64)
+
+        } else {
+            warn!("unsupported time expression '{}'", value);
+            None
+        }
+    }
+
+    /**
+    Write cues as a minimal TTML document.
+
+    Line breaks become `<br/>`, `<i>`, `<b>` and `<u>` tags become styled `<span>` elements.
+    */
+    pub fn write_ttml(cues: &[Cue], lang: &str) -> String {
+        let mut result = String::new();
+
+        result.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        result.push_str(&format!(
+            "<tt xmlns=\"{}\" xmlns:tts=\"{}\" xml:lang=\"{}\">\n",
+            TTML_NAMESPACE, TTML_STYLING_NAMESPACE, escape_xml(lang)
+        ));
+        result.push_str("  <body>\n    <div>\n");
+
+        for cue in cues {
+            result.push_str(&format!(
+                "      <p begin=\"{}\" end=\"{}\">{}</p>\n",
+                format_clock_time(cue.start), format_clock_time(cue.end),
+                get_ttml_text(&cue.text)
+            ));
+        }
+
+        result.push_str("    </div>\n  </body>\n</tt>\n");
+
+        result
+    }
+
+    /// Format milliseconds as TTML clock-time `HH:MM:SS.mmm`
+    pub fn format_clock_time(millis: u64) -> String {
+        format!(
+            "{:02}:{:02}:{:02}.{:03}",
+            millis / 3_600_000, (millis / 60_000) % 60, (millis / 1000) % 60, millis % 1000
+        )
+    }
+
+    fn get_ttml_text(text: &str) -> String {
+        let markup_tag_pattern = MARKUP_TAG_REGEX.get_or_init(|| Regex::new(MARKUP_TAG_PATTERN).unwrap());
+
+        let mut result = String::new();
+        let mut open_spans = 0;
+        let mut last_position = 0;
+
+        for tag in markup_tag_pattern.captures_iter(text) {
+            let tag_match = tag.get(0).unwrap();
+            result.push_str(&escape_text(&text[last_position..tag_match.start()]));
+            last_position = tag_match.end();
+
+            let is_closing = !tag[1].is_empty();
+
+            let attribute = match tag[2].to_lowercase().as_str() {
+                "i" => "tts:fontStyle=\"italic\"",
+                "b" => "tts:fontWeight=\"bold\"",
+                "u" => "tts:textDecoration=\"underline\"",
+                _ => continue
+            };
+
+            if is_closing {
+                if open_spans > 0 {
+                    result.push_str("</span>");
+                    open_spans -= 1;
+                }
+
+            } else {
+                result.push_str(&format!("<span {}>", attribute));
+                open_spans += 1;
+            }
+        }
+
+        result.push_str(&escape_text(&text[last_position..]));
+
+        for _ in 0..open_spans {
+            result.push_str("</span>");
+        }
+
+        result
+    }
+
+    fn escape_text(text: &str) -> String {
+        escape_xml(text).replace('\n', "<br/>")
+    }
+
+    fn escape_xml(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn get_local_name(element: &BytesStart) -> String {
+        String::from_utf8_lossy(element.local_name().as_ref()).to_string()
+    }
+
+    fn get_attribute(element: &BytesStart, name: &str) -> Option<String> {
+        element.attributes().flatten()
+            .find(|attribute| attribute.key.local_name().as_ref() == name.as_bytes())
+            .and_then(|attribute| attribute.unescape_value().ok())
+            .map(|value| value.to_string())
+    }
+
+    fn get_time_base(element: &BytesStart) -> TimeBase {
+        let mut frame_rate = get_attribute(element, "frameRate")
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .filter(|value| *value > 0.0);
+
+        if let (Some(rate), Some(multiplier)) = (frame_rate, get_attribute(element, "frameRateMultiplier")) {
+            let parts: Vec<f64> = multiplier.split_whitespace()
+                                            .filter_map(|part| part.parse().ok()).collect();
+
+            if parts.len() == 2 && parts[1] > 0.0 {
+                frame_rate = Some(rate * parts[0] / parts[1]);
+            }
+        }
+
+        let sub_frame_rate = get_attribute(element, "subFrameRate")
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .unwrap_or(1.0);
+
+        let tick_rate = get_attribute(element, "tickRate")
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .filter(|value| *value > 0.0)
+            .unwrap_or(match frame_rate {
+                Some(rate) => rate * sub_frame_rate,
+                None => DEFAULT_TICK_RATE
+            });
+
+        TimeBase {
+            frame_rate: frame_rate.unwrap_or(DEFAULT_FRAME_RATE),
+            tick_rate
+        }
+    }
+
+    fn get_text_style(element: &BytesStart, styles: &HashMap<String, TextStyle>) -> TextStyle {
+        let mut style = TextStyle::default();
+
+        if let Some(references) = get_attribute(element, "style") {
+            for reference in references.split_whitespace() {
+                if let Some(referenced_style) = styles.get(reference) {
+                    style.italic |= referenced_style.italic;
+                    style.bold |= referenced_style.bold;
+                    style.underline |= referenced_style.underline;
+                }
+            }
+        }
+
+        if let Some(value) = get_attribute(element, "fontStyle") {
+            style.italic = value == "italic" || value == "oblique";
+        }
+
+        if let Some(value) = get_attribute(element, "fontWeight") {
+            style.bold = value == "bold";
+        }
+
+        if let Some(value) = get_attribute(element, "textDecoration") {
+            style.underline = value.split_whitespace().any(|part| part == "underline");
+        }
+
+        style
+    }
+
+    fn open_style_tags(element: &BytesStart, styles: &HashMap<String, TextStyle>,
+                       text: &mut String) -> Vec<&'static str> {
+        let style = get_text_style(element, styles);
+        let mut tags = Vec::new();
+
+        if style.italic {
+            tags.push("i");
+        }
+
+        if style.bold {
+            tags.push("b");
+        }
+
+        if style.underline {
+            tags.push("u");
+        }
+
+        for tag in &tags {
+            text.push_str(&format!("<{}>", tag));
+        }
+
+        tags
+    }
+
+    fn close_style_tags(frame: &ElementFrame, paragraph: &mut Option<Paragraph>) {
+        if let Some(paragraph) = paragraph.as_mut() {
+            for tag in frame.tags.iter().rev() {
+                paragraph.text.push_str(&format!("</{}>", tag));
+            }
+        }
+    }
+
+    fn collapse_whitespace(text: &str) -> String {
+        let whitespace_pattern = WHITESPACE_REGEX.get_or_init(|| Regex::new(WHITESPACE_PATTERN).unwrap());
+        whitespace_pattern.replace_all(text, " ").to_string()
+    }
+
+    fn clean_text(text: &str) -> String {
+        let mut result = text.to_string();
+
+        for tag in &["i", "b", "u"] {
+            result = result.replace(&format!("<{}></{}>", tag, tag), "");
+        }
+
+        result.split('\n')
+              .map(|line| line.trim())
+              .collect::<Vec<&str>>()
+              .join("\n")
+              .trim_matches('\n')
+              .to_string()
+    }
+}
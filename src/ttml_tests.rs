@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod ttml_tests {
+    use crate::cue::cue::Cue;
+    use crate::ttml::ttml::{format_clock_time, parse_time_expression, parse_ttml, TimeBase, write_ttml};
+
+    const DFXP_SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<tt xmlns="http://www.w3.org/2006/10/ttaf1" xmlns:tts="http://www.w3.org/2006/10/ttaf1#styling"
+    xmlns:ttp="http://www.w3.org/2006/10/ttaf1#parameter" ttp:tickRate="10000000" xml:lang="en">
+  <head>
+    <styling>
+      <style xml:id="s1" tts:fontStyle="italic"/>
+    </styling>
+  </head>
+  <body>
+    <div>
+      <p begin="00:00:01.500" end="00:00:03.000">First line<br/>second line</p>
+      <p begin="4s" dur="1500ms"><span style="s1">Whispering</span> &amp; talking</p>
+      <p begin="60000000t" end="75000000t">
+        <span tts:fontWeight="bold">Ticks</span>
+      </p>
+    </div>
+  </body>
+</tt>"#;
+
+    #[test]
+    fn cues_should_be_parsed_from_dfxp() {
+        let cues = parse_ttml(DFXP_SAMPLE).expect("cues expected");
+
+        assert_eq!(3, cues.len());
+
+        assert_eq!(Cue::new(1, 1500, 3000, "First line\nsecond line"), cues[0]);
+        assert_eq!(Cue::new(2, 4000, 5500, "<i>Whispering</i> & talking"), cues[1]);
+        assert_eq!(Cue::new(3, 6000, 7500, "<b>Ticks</b>"), cues[2]);
+    }
+
+    #[test]
+    fn div_begin_should_offset_paragraph_times() {
+        let xml = r#"<tt xmlns="http://www.w3.org/ns/ttml"><body><div begin="10s">
+            <p begin="1s" end="2s">Hi</p></div></body></tt>"#;
+
+        let cues = parse_ttml(xml).expect("cues expected");
+
+        assert_eq!(11000, cues[0].start);
+        assert_eq!(12000, cues[0].end);
+    }
+
+    #[test]
+    fn document_without_tt_root_should_be_rejected() {
+        assert!(parse_ttml("<html><body><p>Hi</p></body></html>").is_err());
+    }
+
+    #[test]
+    fn time_expressions_should_be_parsed() {
+        let time_base = TimeBase { frame_rate: 25.0, tick_rate: 10_000_000.0 };
+
+        assert_eq!(Some(3_723_450), parse_time_expression("01:02:03.45", &time_base));
+        assert_eq!(Some(3_723_480), parse_time_expression("01:02:03:12", &time_base));
+        assert_eq!(Some(5400000), parse_time_expression("1.5h", &time_base));
+        assert_eq!(Some(90000), parse_time_expression("1.5m", &time_base));
+        assert_eq!(Some(2500), parse_time_expression("2.5s", &time_base));
+        assert_eq!(Some(120), parse_time_expression("120ms", &time_base));
+        assert_eq!(Some(2000), parse_time_expression("50f", &time_base));
+        assert_eq!(Some(1500), parse_time_expression("15000000t", &time_base));
+        assert_eq!(None, parse_time_expression("soon", &time_base));
+    }
+
+    #[test]
+    fn written_document_should_be_parsed_back() {
+        let cues = vec![
+            Cue::new(1, 1000, 2500, "<i>Hello</i> & welcome\nto <b>Ooo</b>"),
+            Cue::new(2, 3_600_000, 3_601_000, "One hour later")
+        ];
+
+        let xml = write_ttml(&cues, "en");
+
+        assert!(xml.contains("<p begin=\"00:00:01.000\" end=\"00:00:02.500\">"));
+        assert!(xml.contains("<span tts:fontStyle=\"italic\">Hello</span> &amp; welcome<br/>"));
+
+        assert_eq!(cues, parse_ttml(&xml).expect("cues expected"));
+    }
+
+    #[test]
+    fn clock_time_should_be_formatted_with_milliseconds() {
+        assert_eq!("00:00:00.000", format_clock_time(0));
+        assert_eq!("01:02:03.045", format_clock_time(3_723_045));
+    }
+}
@@ -0,0 +1,367 @@
+pub mod upload {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+    use regex::Regex;
+    use scraper::{Html, Selector};
+    use serde::{Deserialize, Serialize};
+    use tracing::Span;
+
+    use crate::config::config::Credentials;
+    use crate::error::error::OperationError;
+    use crate::hash::hash::MovieHash;
+    use crate::language::language::{get_language_code, LanguageCodeStyle};
+    use crate::strip::strip::strip_html_tags;
+    use crate::transport::transport::{HttpResponse, HttpTransport};
+    use crate::types::types::OperationResult;
+
+    const OK_STATUS: u16 = 200;
+
+    const FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
+
+    /// Link shown to logged in users only
+    const LOGOUT_LINK_SELECTOR: &str = "a[href*=\"/logout\"]";
+
+    /// Upload form, hidden inputs are sent back with the upload
+    const UPLOAD_FORM_SELECTOR: &str = "form[enctype=\"multipart/form-data\"]";
+
+    /// Existing subtitles on hash check page and new subtitles on upload result page
+    const SUBTITLE_LINK_SELECTOR: &str = "a[href*=\"/subtitles/\"]";
+    const SUBTITLE_ID_PATTERN: &str = "/subtitles/(\\d+)";
+
+    /// Hash check result box, existing subtitles are linked inside
+    const CHECK_RESULT_SELECTOR: &str = "div.msg";
+
+    const UPLOAD_OK_SELECTOR: &str = "div.msg.ok";
+    const UPLOAD_ERROR_SELECTOR: &str = "div.msg.error";
+
+    /// Subtitle file to upload
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct UploadFile {
+        pub filename: String,
+        pub content: Vec<u8>
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct UploadRequest {
+        /// Several files for multi-CD releases
+        pub files: Vec<UploadFile>,
+
+        /// IMDb id: `tt7342228` or `7342228`
+        pub imdb_id: String,
+
+        /// Hash and size of the video file subtitles were made for, see `hash::get_movie_hash`
+        pub movie_hash: MovieHash,
+
+        /// Subtitle language: `rus`, `en`, ...
+        pub language: String,
+
+        /// Release name (video file name) subtitles were made for
+        pub release_name: Option<String>,
+
+        /// Video frame rate subtitles were made for
+        pub fps: Option<f32>,
+
+        pub hearing_impaired: bool,
+
+        /// Subtitles for foreign parts only
+        pub forced: bool,
+
+        /// Translator name
+        pub translator: Option<String>,
+
+        /// Machine translated subtitles
+        pub machine_translated: bool
+    }
+
+    /**
+    Log in with site credentials, session cookie is kept by transport
+    (default `client::Client` transport has cookie store enabled).
+    */
+    #[instrument(skip_all, fields(login = %credentials.login))]
+    pub async fn login(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                       credentials: &Credentials) -> OperationResult<()> {
+        let request_url = format!("{}/{}/login", base_url, locale);
+
+        let body = get_form_urlencoded(&[
+            ("a", "login"),
+            ("redirect", &format!("/{}/upload", locale)),
+            ("user", &credentials.login),
+            ("password", &credentials.password),
+            ("remember", "on")
+        ]);
+
+        let resp = get_ok_response(client.post(&request_url, FORM_URLENCODED, body.into_bytes()).await?)?;
+
+        if has_element(&resp.text(), LOGOUT_LINK_SELECTOR) {
+            info!("logged in as '{}'", credentials.login);
+            Ok(())
+
+        } else {
+            error!("login failed for '{}'", credentials.login);
+            Err(OperationError::Authentication)
+        }
+    }
+
+    /**
+    Upload subtitles the way the site upload form does it and return new subtitle id.
+
+    Flow: log in, get upload form, check movie hash for existing subtitles in the same language,
+    post the form with files. Errors:
+    - `InvalidArgument` - invalid request (no files, bad IMDb id or language)
+    - `Authentication` - invalid credentials or session is lost
+    - `DuplicateSubtitles` - the site already has subtitles for this hash and language
+    - `UploadRejected` - the site rejected the upload, message is from the site
+    */
+    #[instrument(skip_all, fields(imdb_id = %request.imdb_id, hash = %request.movie_hash.hash, language = %request.language, subtitle_id))]
+    pub async fn upload_subtitles(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                                  credentials: &Credentials, request: &UploadRequest) -> OperationResult<String> {
+        let site_language = validate_request(request)?;
+
+        login(client, base_url, locale, credentials).await?;
+
+        let form_url = format!("{}/{}/upload", base_url, locale);
+        let form_page = get_ok_response(client.get(&form_url).await?)?.text();
+
+        let (action, hidden_fields) = parse_upload_form(&form_page)?;
+
+        let check_url = format!(
+            "{}/{}/upload/check/moviehash-{}/moviebytesize-{}/sublanguageid-{}",
+            base_url, locale, request.movie_hash.hash, request.movie_hash.size, site_language
+        );
+
+        let check_page = get_ok_response(client.get(&check_url).await?)?.text();
+
+        if let Some(subtitle_id) = get_subtitle_id(&check_page, Some(CHECK_RESULT_SELECTOR)) {
+            warn!("subtitles {} already uploaded for hash {}", subtitle_id, request.movie_hash.hash);
+            return Err(OperationError::DuplicateSubtitles(subtitle_id));
+        }
+
+        let mut fields = hidden_fields;
+        fields.extend(get_request_fields(request, site_language));
+
+        let boundary = get_boundary();
+        let body = get_multipart_body(&boundary, &fields, &request.files)?;
+
+        let upload_url = get_absolute_url(base_url, &form_url, &action);
+        info!("upload {} files to '{}'", request.files.len(), upload_url);
+
+        let result_page = get_ok_response(client.post(
+            &upload_url, &format!("multipart/form-data; boundary={}", boundary), body
+        ).await?)?.text();
+
+        let subtitle_id = parse_upload_result(&result_page)?;
+        Span::current().record("subtitle_id", subtitle_id.as_str());
+
+        info!("subtitles uploaded, id {}", subtitle_id);
+
+        Ok(subtitle_id)
+    }
+
+    /// Site language code (ISO 639-2) for valid request
+    fn validate_request(request: &UploadRequest) -> OperationResult<&'static str> {
+        if request.files.is_empty() {
+            return Err(OperationError::InvalidArgument(String::from("no subtitle files")));
+        }
+
+        if let Some(file) = request.files.iter().find(|file| file.content.is_empty()) {
+            return Err(OperationError::InvalidArgument(format!("empty subtitle file '{}'", file.filename)));
+        }
+
+        let text_values = request.files.iter().map(|file| file.filename.as_str())
+                                 .chain(request.release_name.as_deref())
+                                 .chain(request.translator.as_deref());
+
+        for value in text_values {
+            if has_line_break(value) {
+                return Err(OperationError::InvalidArgument(format!("line break in '{}'", value.escape_debug())));
+            }
+        }
+
+        let imdb_id = request.imdb_id.trim_start_matches("tt");
+
+        if imdb_id.is_empty() || !imdb_id.chars().all(|character| character.is_ascii_digit()) {
+            return Err(OperationError::InvalidArgument(format!("invalid IMDb id '{}'", request.imdb_id)));
+        }
+
+        if request.movie_hash.hash.len() != 16 || !request.movie_hash.hash.chars().all(|character| character.is_ascii_hexdigit()) {
+            return Err(OperationError::InvalidArgument(format!("invalid movie hash '{}'", request.movie_hash.hash)));
+        }
+
+        get_language_code(&request.language, LanguageCodeStyle::Iso639_2).ok_or_else(|| {
+            OperationError::InvalidArgument(format!("unsupported language '{}'", request.language))
+        })
+    }
+
+    /// Form action and hidden inputs, page without the form means session is lost
+    fn parse_upload_form(html: &str) -> OperationResult<(String, Vec<(String, String)>)> {
+        let document = Html::parse_document(html);
+
+        let form_selector = Selector::parse(UPLOAD_FORM_SELECTOR).unwrap();
+        let hidden_input_selector = Selector::parse("input[type=\"hidden\"][name]").unwrap();
+
+        let form = document.select(&form_selector).next().ok_or_else(|| {
+            error!("upload form not found, not logged in");
+            OperationError::Authentication
+        })?;
+
+        let action = form.value().attr("action").unwrap_or("").to_string();
+
+        let hidden_fields = form.select(&hidden_input_selector)
+                                .filter_map(|input| {
+                                    let name = input.value().attr("name")?;
+                                    Some((name.to_string(), input.value().attr("value").unwrap_or("").to_string()))
+                                })
+                                .collect();
+
+        Ok((action, hidden_fields))
+    }
+
+    /// New subtitle id from the result page or the site error message
+    fn parse_upload_result(html: &str) -> OperationResult<String> {
+        let document = Html::parse_document(html);
+
+        let error_selector = Selector::parse(UPLOAD_ERROR_SELECTOR).unwrap();
+
+        if let Some(message) = document.select(&error_selector).next() {
+            let message = strip_html_tags(&message.inner_html());
+            error!("upload rejected: {}", message);
+            return Err(OperationError::UploadRejected(message));
+        }
+
+        get_subtitle_id(html, Some(UPLOAD_OK_SELECTOR)).ok_or_else(|| {
+            error!("unable to get subtitle id from upload result page");
+            OperationError::HtmlParseError
+        })
+    }
+
+    /// Id of the first subtitle link, inside `container_selector` element if set
+    fn get_subtitle_id(html: &str, container_selector: Option<&str>) -> Option<String> {
+        let document = Html::parse_document(html);
+
+        let link_selector = match container_selector {
+            Some(container) => Selector::parse(&format!("{} {}", container, SUBTITLE_LINK_SELECTOR)).unwrap(),
+            None => Selector::parse(SUBTITLE_LINK_SELECTOR).unwrap()
+        };
+
+        let subtitle_id_pattern = Regex::new(SUBTITLE_ID_PATTERN).unwrap();
+
+        document.select(&link_selector)
+                .filter_map(|a_element| a_element.value().attr("href"))
+                .find_map(|href| subtitle_id_pattern.captures(href))
+                .map(|groups| groups[1].to_string())
+    }
+
+    fn get_request_fields(request: &UploadRequest, site_language: &str) -> Vec<(String, String)> {
+        let flag = |value: bool| String::from(if value { "1" } else { "0" });
+
+        vec![
+            (String::from("IDMovieImdb"), request.imdb_id.trim_start_matches("tt").to_string()),
+            (String::from("MovieHash"), request.movie_hash.hash.to_lowercase()),
+            (String::from("MovieByteSize"), request.movie_hash.size.to_string()),
+            (String::from("SubLanguageID"), site_language.to_string()),
+            (String::from("MovieReleaseName"), request.release_name.clone().unwrap_or_default()),
+            (String::from("MovieFPS"), request.fps.map(|fps| format!("{:.3}", fps)).unwrap_or_default()),
+            (String::from("HearingImpaired"), flag(request.hearing_impaired)),
+            (String::from("ForeignPartsOnly"), flag(request.forced)),
+            (String::from("SubTranslator"), request.translator.clone().unwrap_or_default()),
+            (String::from("MachineTranslated"), flag(request.machine_translated))
+        ]
+    }
+
+    /// Field names, values and file names with line breaks or the boundary are rejected
+    fn get_multipart_body(boundary: &str, fields: &[(String, String)],
+                          files: &[UploadFile]) -> OperationResult<Vec<u8>> {
+        let delimiter = format!("--{}", boundary);
+
+        let values = fields.iter()
+                           .flat_map(|(name, value)| [name.as_str(), value.as_str()])
+                           .chain(files.iter().map(|file| file.filename.as_str()));
+
+        for value in values {
+            if has_line_break(value) || value.contains(&delimiter) {
+                error!("invalid form value '{}'", value.escape_debug());
+                return Err(OperationError::InvalidArgument(format!("invalid form value '{}'", value.escape_debug())));
+            }
+        }
+
+        if files.iter().any(|file| file.content.windows(delimiter.len()).any(|window| window == delimiter.as_bytes())) {
+            error!("boundary found in subtitle file content");
+            return Err(OperationError::InvalidArgument(String::from("boundary found in subtitle file content")));
+        }
+
+        let mut body: Vec<u8> = Vec::new();
+
+        for (name, value) in fields {
+            body.extend(format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                boundary, name, value
+            ).into_bytes());
+        }
+
+        for file in files {
+            body.extend(format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"subfile[]\"; filename=\"{}\"\r\n\
+                 Content-Type: application/octet-stream\r\n\r\n",
+                boundary, file.filename.replace('"', "")
+            ).into_bytes());
+
+            body.extend(&file.content);
+            body.extend(b"\r\n");
+        }
+
+        body.extend(format!("--{}--\r\n", boundary).into_bytes());
+
+        Ok(body)
+    }
+
+    fn has_line_break(value: &str) -> bool {
+        value.contains(['\r', '\n'])
+    }
+
+    fn get_boundary() -> String {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_nanos()).unwrap_or(0);
+        format!("----opensubs{:x}", nanos)
+    }
+
+    fn get_form_urlencoded(fields: &[(&str, &str)]) -> String {
+        fields.iter()
+              .map(|(name, value)| format!("{}={}", name, utf8_percent_encode(value, NON_ALPHANUMERIC)))
+              .collect::<Vec<String>>()
+              .join("&")
+    }
+
+    /// Form action relative to form page, empty action is the form page itself
+    fn get_absolute_url(base_url: &str, page_url: &str, action: &str) -> String {
+        if action.is_empty() {
+            page_url.to_string()
+
+        } else if action.starts_with("http://") || action.starts_with("https://") {
+            action.to_string()
+
+        } else if action.starts_with('/') {
+            format!("{}{}", base_url.trim_end_matches('/'), action)
+
+        } else {
+            let page_directory = page_url.rsplit_once('/').map(|(directory, _)| directory).unwrap_or(page_url);
+            format!("{}/{}", page_directory, action)
+        }
+    }
+
+    fn has_element(html: &str, selector: &str) -> bool {
+        let document = Html::parse_document(html);
+        let selector = Selector::parse(selector).unwrap();
+
+        document.select(&selector).next().is_some()
+    }
+
+    fn get_ok_response(resp: HttpResponse) -> OperationResult<HttpResponse> {
+        if resp.status == OK_STATUS {
+            Ok(resp)
+
+        } else {
+            error!("unexpected server status code: {}", resp.status);
+            Err(OperationError::Error)
+        }
+    }
+}
@@ -0,0 +1,246 @@
+#[cfg(test)]
+mod upload_tests {
+    use crate::client::client::Client;
+    use crate::config::config::{Config, Credentials};
+    use crate::error::error::OperationError;
+    use crate::hash::hash::MovieHash;
+    use crate::mock_server::mock_server::{MockRequest, MockResponse, MockServer};
+    use crate::upload::upload::{login, upload_subtitles, UploadFile, UploadRequest};
+
+    const SESSION_COOKIE: &str = "PHPSESSID=5e55i0n";
+    const FORM_TOKEN: &str = "t0k3n";
+
+    const DUPLICATE_HASH: &str = "00000000000000ff";
+
+    #[tokio::test]
+    async fn upload_should_return_new_subtitle_id() {
+        let server = start_form_server();
+
+        let subtitle_id = upload_subtitles(&get_http_client(), &server.url(), "en", &get_credentials(), &get_request())
+            .await.unwrap();
+
+        assert_eq!("9000001", subtitle_id);
+
+        let requests = server.requests();
+        let paths: Vec<&str> = requests.iter().map(|request| request.path.as_str()).collect();
+
+        assert_eq!(vec![
+            "/en/login",
+            "/en/upload",
+            "/en/upload/check/moviehash-8e245d9679d31e12/moviebytesize-12909756/sublanguageid-rus",
+            "/en/upload/submit"
+        ], paths);
+
+        let body = String::from_utf8_lossy(&requests[3].body).to_string();
+
+        for field in [
+            get_form_field("token", FORM_TOKEN),
+            get_form_field("IDMovieImdb", "1305826"),
+            get_form_field("MovieHash", "8e245d9679d31e12"),
+            get_form_field("MovieByteSize", "12909756"),
+            get_form_field("SubLanguageID", "rus"),
+            get_form_field("MovieReleaseName", "Adventure.Time.S01E01.720p"),
+            get_form_field("MovieFPS", "23.976"),
+            get_form_field("HearingImpaired", "1"),
+            get_form_field("ForeignPartsOnly", "0"),
+            get_form_field("SubTranslator", "Finn"),
+            get_form_field("MachineTranslated", "0")
+        ] {
+            assert!(body.contains(&field), "field expected: {}", field);
+        }
+
+        assert!(body.contains("name=\"subfile[]\"; filename=\"adventure.time.s01e01.srt\"\r\n"));
+        assert!(body.contains("00:00:01,000 --> 00:00:02,000\nПривет\n"));
+    }
+
+    #[tokio::test]
+    async fn invalid_credentials_should_return_authentication_error() {
+        let server = start_form_server();
+
+        let credentials = Credentials { login: String::from("user"), password: String::from("wrong") };
+
+        match login(&get_http_client(), &server.url(), "en", &credentials).await {
+            Err(OperationError::Authentication) => {}
+            _ => panic!("authentication error expected")
+        }
+
+        match upload_subtitles(&get_http_client(), &server.url(), "en", &credentials, &get_request()).await {
+            Err(OperationError::Authentication) => {}
+            _ => panic!("authentication error expected")
+        }
+    }
+
+    #[tokio::test]
+    async fn lost_session_should_return_authentication_error() {
+        let server = start_form_server();
+
+        match upload_subtitles(&reqwest::Client::new(), &server.url(), "en", &get_credentials(), &get_request()).await {
+            Err(OperationError::Authentication) => {}
+            _ => panic!("authentication error expected")
+        }
+    }
+
+    #[tokio::test]
+    async fn duplicate_should_return_existing_subtitle_id() {
+        let server = start_form_server();
+
+        let mut request = get_request();
+        request.movie_hash.hash = String::from(DUPLICATE_HASH);
+
+        match upload_subtitles(&get_http_client(), &server.url(), "en", &get_credentials(), &request).await {
+            Err(OperationError::DuplicateSubtitles(subtitle_id)) => assert_eq!("1234567", subtitle_id),
+            _ => panic!("duplicate error expected")
+        }
+
+        assert!(server.requests().iter().all(|request| request.path != "/en/upload/submit"));
+    }
+
+    #[tokio::test]
+    async fn rejected_upload_should_return_site_message() {
+        let server = start_form_server();
+
+        let mut request = get_request();
+        request.release_name = Some(String::from("rejected"));
+
+        match upload_subtitles(&get_http_client(), &server.url(), "en", &get_credentials(), &request).await {
+            Err(OperationError::UploadRejected(message)) => assert_eq!("Subtitle file is not valid", message),
+            _ => panic!("rejected error expected")
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_request_should_not_be_sent() {
+        let server = start_form_server();
+
+        let invalid_requests = vec![
+            UploadRequest { files: Vec::new(), ..get_request() },
+            UploadRequest { imdb_id: String::from("tt13x"), ..get_request() },
+            UploadRequest { language: String::from("xx"), ..get_request() },
+            UploadRequest { movie_hash: MovieHash { hash: String::from("abc"), size: 1 }, ..get_request() },
+            UploadRequest { release_name: Some(String::from("Adventure.Time\r\nX-Injected: 1")), ..get_request() },
+            UploadRequest { translator: Some(String::from("Finn\n")), ..get_request() },
+            UploadRequest {
+                files: vec![UploadFile { filename: String::from("a.srt\r\n\r\nx"), content: b"1".to_vec() }],
+                ..get_request()
+            }
+        ];
+
+        for request in invalid_requests {
+            match upload_subtitles(&get_http_client(), &server.url(), "en", &get_credentials(), &request).await {
+                Err(OperationError::InvalidArgument(_)) => {}
+                _ => panic!("invalid argument error expected")
+            }
+        }
+
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn client_should_upload_with_config_credentials() {
+        let server = start_form_server();
+
+        let config = Config::parse(r#"
+            [credentials]
+            login = "user"
+            password = "secret"
+        "#).unwrap();
+
+        let client = Client::builder().config(config).base_url(&server.url()).build().unwrap();
+
+        assert_eq!("9000001", client.upload_subtitles(&get_request()).await.unwrap());
+
+        let client = Client::builder().base_url(&server.url()).build().unwrap();
+
+        match client.upload_subtitles(&get_request()).await {
+            Err(OperationError::Authentication) => {}
+            _ => panic!("authentication error expected")
+        }
+    }
+
+    /// Stand-in for the site login, upload form, hash check and upload result pages
+    fn start_form_server() -> MockServer {
+        MockServer::start_with_handler(|request| {
+            let body = String::from_utf8_lossy(&request.body).to_string();
+
+            match (request.method.as_str(), request.path.as_str()) {
+                ("POST", "/en/login") => {
+                    if body.contains("user=user") && body.contains("password=secret") {
+                        MockResponse::html("<html><body><a href=\"/en/logout\">Logout</a></body></html>")
+                            .with_header("Set-Cookie", &format!("{}; Path=/", SESSION_COOKIE))
+                    } else {
+                        get_login_page()
+                    }
+                }
+                (_, _) if !has_session(request) => get_login_page(),
+                ("GET", "/en/upload") => MockResponse::html(&format!(
+                    "<html><body><form name=\"uploadform\" action=\"/en/upload/submit\" method=\"post\" \
+                     enctype=\"multipart/form-data\"><input type=\"hidden\" name=\"token\" value=\"{}\">\
+                     <input type=\"file\" name=\"subfile[]\"></form></body></html>",
+                    FORM_TOKEN
+                )),
+                ("GET", path) if path.starts_with("/en/upload/check/") => {
+                    if path.contains(DUPLICATE_HASH) {
+                        MockResponse::html("<html><body><a href=\"/en/subtitles/1/latest\">latest</a>\
+                                            <div class=\"msg hint\">Already uploaded: \
+                                            <a href=\"/en/subtitles/1234567/adventure-time-ru\">subtitles</a></div></body></html>")
+                    } else {
+                        MockResponse::html("<html><body><a href=\"/en/subtitles/1/latest\">latest</a>\
+                                            <div class=\"msg\">No subtitles for this hash</div></body></html>")
+                    }
+                }
+                ("POST", "/en/upload/submit") => {
+                    if !body.contains(&get_form_field("token", FORM_TOKEN)) {
+                        MockResponse::status(403)
+                    } else if body.contains(&get_form_field("MovieReleaseName", "rejected")) {
+                        MockResponse::html("<html><body><div class=\"msg error\">Subtitle file is \
+                                            <b>not valid</b></div></body></html>")
+                    } else {
+                        MockResponse::html("<html><body><a href=\"/en/subtitles/1/other\">other</a>\
+                                            <div class=\"msg ok\">Uploaded: \
+                                            <a href=\"/en/subtitles/9000001/adventure-time-ru\">subtitles</a></div></body></html>")
+                    }
+                }
+                _ => MockResponse::status(404)
+            }
+        })
+    }
+
+    fn has_session(request: &MockRequest) -> bool {
+        request.header("Cookie").is_some_and(|cookie| cookie.contains(SESSION_COOKIE))
+    }
+
+    fn get_login_page() -> MockResponse {
+        MockResponse::html("<html><body><form action=\"/en/login\" method=\"post\">\
+                            <input name=\"user\"><input name=\"password\" type=\"password\"></form></body></html>")
+    }
+
+    fn get_form_field(name: &str, value: &str) -> String {
+        format!("Content-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n", name, value)
+    }
+
+    fn get_http_client() -> reqwest::Client {
+        reqwest::Client::builder().cookie_store(true).build().unwrap()
+    }
+
+    fn get_credentials() -> Credentials {
+        Credentials { login: String::from("user"), password: String::from("secret") }
+    }
+
+    fn get_request() -> UploadRequest {
+        UploadRequest {
+            files: vec![UploadFile {
+                filename: String::from("adventure.time.s01e01.srt"),
+                content: "1\n00:00:01,000 --> 00:00:02,000\nПривет\n".as_bytes().to_vec()
+            }],
+            imdb_id: String::from("tt1305826"),
+            movie_hash: MovieHash { hash: String::from("8e245d9679d31e12"), size: 12909756 },
+            language: String::from("ru"),
+            release_name: Some(String::from("Adventure.Time.S01E01.720p")),
+            fps: Some(23.976),
+            hearing_impaired: true,
+            forced: false,
+            translator: Some(String::from("Finn")),
+            machine_translated: false
+        }
+    }
+}
@@ -0,0 +1,389 @@
+pub mod watch {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::mpsc::{channel, Sender, TryRecvError};
+    use std::thread;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use inotify::{EventMask, Inotify, WatchDescriptor, WatchMask};
+    use serde::{Deserialize, Serialize};
+
+    use crate::batch::batch::{BatchOptions, BatchStatus, fetch_video_subtitles, find_video_files,
+                              get_missing_languages, VIDEO_EXTENSIONS};
+    use crate::error::error::OperationError;
+    use crate::transport::transport::HttpTransport;
+    use crate::types::types::OperationResult;
+
+    const EVENT_BUFFER_SIZE: usize = 4096;
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct WatchOptions {
+        pub directories: Vec<PathBuf>,
+
+        /// Queue state file, survives restarts
+        pub queue_path: PathBuf,
+
+        /// File size must stay the same for this time (seconds) before processing
+        pub settle_delay: u64,
+
+        /// First retry delay (seconds) for videos without subtitles, doubled on every attempt
+        pub retry_base_delay: u64,
+
+        /// Max retry delay, seconds
+        pub retry_max_delay: u64,
+
+        /// Video is dropped from the queue after this number of attempts
+        pub max_attempts: u32,
+
+        /// Queue check interval, seconds
+        pub poll_interval: u64,
+
+        pub batch: BatchOptions
+    }
+
+    impl Default for WatchOptions {
+        fn default() -> Self {
+            WatchOptions {
+                directories: Vec::new(),
+                queue_path: PathBuf::from("opensubs-queue.json"),
+                settle_delay: 10,
+                retry_base_delay: 1800,
+                retry_max_delay: 86400,
+                max_attempts: 30,
+                poll_interval: 1,
+                batch: BatchOptions::default()
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    pub enum QueueItemState {
+        /// File may still be copied, waiting for the size to stop changing
+        Settling { size: u64 },
+
+        /// Waiting for subtitles acquisition (first or next attempt)
+        Ready
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct QueueItem {
+        pub video: PathBuf,
+        pub state: QueueItemState,
+
+        /// Failed subtitle acquisition attempts
+        pub attempts: u32,
+
+        /// Unix time, seconds
+        pub next_check_at: u64
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+    pub struct WatchQueue {
+        pub items: Vec<QueueItem>
+    }
+
+    impl WatchQueue {
+        /// Load queue state, empty queue if file doesn't exist
+        pub fn load(path: &Path) -> OperationResult<WatchQueue> {
+            if !path.exists() {
+                info!("queue file '{}' not found, start with empty queue", path.display());
+                return Ok(WatchQueue::default());
+            }
+
+            let content = fs::read_to_string(path)?;
+
+            serde_json::from_str(&content).map_err(|e| {
+                error!("unable to parse queue file '{}': {}", path.display(), e);
+                OperationError::Error
+            })
+        }
+
+        /// Write queue state atomically
+        pub fn save(&self, path: &Path) -> OperationResult<()> {
+            let content = serde_json::to_string_pretty(self).map_err(|e| {
+                error!("unable to serialize queue: {}", e);
+                OperationError::Error
+            })?;
+
+            let temp_path = path.with_extension("tmp");
+            fs::write(&temp_path, content)?;
+            fs::rename(&temp_path, path)?;
+
+            Ok(())
+        }
+
+        /// Add new video, `false` if it's queued already or doesn't exist
+        pub fn add(&mut self, video: &Path, now: u64, options: &WatchOptions) -> bool {
+            if self.items.iter().any(|item| item.video == video) {
+                return false;
+            }
+
+            match fs::metadata(video) {
+                Ok(metadata) => {
+                    info!("queue video '{}'", video.display());
+
+                    self.items.push(QueueItem {
+                        video: video.to_path_buf(),
+                        state: QueueItemState::Settling { size: metadata.len() },
+                        attempts: 0,
+                        next_check_at: now + options.settle_delay
+                    });
+
+                    true
+                }
+                Err(e) => {
+                    warn!("unable to get video file '{}' size: {}", video.display(), e);
+                    false
+                }
+            }
+        }
+
+        /**
+        Check settling items due at `now`: item with unchanged size becomes `Ready`,
+        changed size restarts the wait, removed files are dropped.
+        */
+        pub fn settle(&mut self, now: u64, options: &WatchOptions) {
+            self.items.retain_mut(|item| {
+                let size = match item.state {
+                    QueueItemState::Settling { size } if item.next_check_at <= now => size,
+                    _ => return true
+                };
+
+                match fs::metadata(&item.video) {
+                    Ok(metadata) if metadata.len() == size => {
+                        debug!("video '{}' size is stable", item.video.display());
+                        item.state = QueueItemState::Ready;
+                        item.next_check_at = now;
+                        true
+                    }
+                    Ok(metadata) => {
+                        item.state = QueueItemState::Settling { size: metadata.len() };
+                        item.next_check_at = now + options.settle_delay;
+                        true
+                    }
+                    Err(_) => {
+                        info!("video '{}' is gone, drop it", item.video.display());
+                        false
+                    }
+                }
+            });
+        }
+
+        /// Ready videos due at `now`
+        pub fn get_ready(&self, now: u64) -> Vec<PathBuf> {
+            self.items.iter()
+                      .filter(|item| item.state == QueueItemState::Ready && item.next_check_at <= now)
+                      .map(|item| item.video.clone())
+                      .collect()
+        }
+
+        /// Schedule next attempt with backoff, `false` if video is dropped after `max_attempts`
+        pub fn reschedule(&mut self, video: &Path, now: u64, options: &WatchOptions) -> bool {
+            let position = match self.items.iter().position(|item| item.video == video) {
+                Some(position) => position,
+                None => return false
+            };
+
+            let item = &mut self.items[position];
+            item.attempts += 1;
+
+            if item.attempts >= options.max_attempts {
+                warn!("no subtitles for '{}' after {} attempts, drop it", video.display(), item.attempts);
+                self.items.remove(position);
+                return false;
+            }
+
+            item.next_check_at = now + get_retry_delay(item.attempts, options);
+            debug!("next check for '{}' at {}", video.display(), item.next_check_at);
+
+            true
+        }
+
+        pub fn remove(&mut self, video: &Path) {
+            self.items.retain(|item| item.video != video);
+        }
+    }
+
+    /// Exponential backoff: `retry_base_delay * 2^(attempts - 1)`, limited by `retry_max_delay`
+    pub fn get_retry_delay(attempts: u32, options: &WatchOptions) -> u64 {
+        let multiplier = 2_u64.saturating_pow(attempts.saturating_sub(1));
+        options.retry_base_delay.saturating_mul(multiplier).min(options.retry_max_delay)
+    }
+
+    /**
+    Watch directories (recursively) for new video files and fetch missing subtitles.
+
+    New files wait until their size stops changing. Videos without subtitles in all
+    wanted languages are re-queued with backoff. Queue state is saved to `options.queue_path`
+    after every change. Runs until an error occurs.
+    */
+    pub async fn watch(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str, options: &WatchOptions) -> OperationResult<()> {
+        info!("watch directories: {:?}", options.directories);
+
+        let mut queue = WatchQueue::load(&options.queue_path)?;
+        info!("queued videos: {}", queue.items.len());
+
+        let (sender, receiver) = channel::<PathBuf>();
+
+        let mut inotify = Inotify::init()?;
+        let mut watched_directories: HashMap<WatchDescriptor, PathBuf> = HashMap::new();
+
+        for directory in &options.directories {
+            add_watches(&mut inotify, directory, &mut watched_directories)?;
+        }
+
+        thread::spawn(move || read_events(inotify, watched_directories, sender));
+
+        loop {
+            let now = get_now();
+            let mut changed = false;
+
+            loop {
+                match receiver.try_recv() {
+                    Ok(video) => changed |= queue.add(&video, now, options),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        error!("file watcher has stopped");
+                        return Err(OperationError::Error);
+                    }
+                }
+            }
+
+            let before_settle = queue.clone();
+            queue.settle(now, options);
+            changed |= queue != before_settle;
+
+            for video in queue.get_ready(now) {
+                let found_all = fetch_subtitles(client, base_url, locale, &video, &options.batch).await;
+
+                if found_all {
+                    info!("all subtitles found for '{}'", video.display());
+                    queue.remove(&video);
+
+                } else {
+                    queue.reschedule(&video, now, options);
+                }
+
+                changed = true;
+            }
+
+            if changed {
+                queue.save(&options.queue_path)?;
+            }
+
+            tokio::time::sleep(Duration::from_secs(options.poll_interval.max(1))).await;
+        }
+    }
+
+    /// `true` if video has subtitles in all wanted languages
+    async fn fetch_subtitles(client: &(impl HttpTransport + ?Sized), base_url: &str, locale: &str,
+                             video: &Path, options: &BatchOptions) -> bool {
+        let missing_languages = match get_missing_languages(video, &options.languages) {
+            Ok(languages) => languages,
+            Err(e) => {
+                error!("unable to check subtitles for '{}': {}", video.display(), e);
+                return false;
+            }
+        };
+
+        let mut found_all = true;
+
+        for language in &missing_languages {
+            match fetch_video_subtitles(client, base_url, locale, video, language, options).await {
+                Ok(BatchStatus::Downloaded { path }) => info!("subtitles saved: '{}'", path.display()),
+                Ok(BatchStatus::Skipped { path }) => info!("subtitle file '{}' exists, kept", path.display()),
+                Ok(BatchStatus::WouldDownload { details_url, .. }) => {
+                    // nothing saved, video stays queued
+                    info!("dry run, would download '{}'", details_url);
+                    found_all = false;
+                }
+                Ok(status) => {
+                    info!("no subtitles for '{}', language '{}': {:?}", video.display(), language, status);
+                    found_all = false;
+                }
+                Err(e) => {
+                    error!("unable to fetch subtitles for '{}': {}", video.display(), e);
+                    found_all = false;
+                }
+            }
+        }
+
+        found_all
+    }
+
+    fn read_events(mut inotify: Inotify, mut watched_directories: HashMap<WatchDescriptor, PathBuf>,
+                   sender: Sender<PathBuf>) {
+        let mut buffer = [0_u8; EVENT_BUFFER_SIZE];
+
+        loop {
+            let events = match inotify.read_events_blocking(&mut buffer) {
+                Ok(events) => events,
+                Err(e) => {
+                    error!("unable to read file events: {}", e);
+                    return;
+                }
+            };
+
+            let mut paths: Vec<(PathBuf, bool)> = Vec::new();
+
+            for event in events {
+                if let (Some(directory), Some(name)) = (watched_directories.get(&event.wd), event.name) {
+                    paths.push((directory.join(name), event.mask.contains(EventMask::ISDIR)));
+                }
+            }
+
+            for (path, is_directory) in paths {
+                let videos = if is_directory {
+                    if let Err(e) = add_watches(&mut inotify, &path, &mut watched_directories) {
+                        error!("unable to watch directory '{}': {}", path.display(), e);
+                    }
+
+                    find_video_files(&path).unwrap_or_default()
+
+                } else if is_video_file(&path) {
+                    vec![path]
+
+                } else {
+                    Vec::new()
+                };
+
+                for video in videos {
+                    debug!("new video file '{}'", video.display());
+
+                    if sender.send(video).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_watches(inotify: &mut Inotify, directory: &Path,
+                   watched_directories: &mut HashMap<WatchDescriptor, PathBuf>) -> OperationResult<()> {
+        let mask = WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::CREATE;
+
+        let descriptor = inotify.watches().add(directory, mask)?;
+        watched_directories.insert(descriptor, directory.to_path_buf());
+
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+
+            if path.is_dir() {
+                add_watches(inotify, &path, watched_directories)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_video_file(path: &Path) -> bool {
+        path.extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .is_some_and(|extension| VIDEO_EXTENSIONS.contains(&extension.as_str()))
+    }
+
+    fn get_now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0)
+    }
+}
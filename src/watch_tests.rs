@@ -0,0 +1,98 @@
+#[cfg(all(test, target_os = "linux"))]
+mod watch_tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use crate::watch::watch::{get_retry_delay, QueueItemState, WatchOptions, WatchQueue};
+
+    #[test]
+    fn video_should_become_ready_when_size_is_stable() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("show.s01e01.mkv");
+        fs::write(&video_path, "part").unwrap();
+
+        let options = WatchOptions { settle_delay: 10, ..WatchOptions::default() };
+
+        let mut queue = WatchQueue::default();
+        assert!(queue.add(&video_path, 100, &options));
+        assert!(!queue.add(&video_path, 100, &options));
+
+        // not due yet
+        queue.settle(105, &options);
+        assert!(queue.get_ready(105).is_empty());
+
+        fs::write(&video_path, "part, more data").unwrap();
+
+        queue.settle(110, &options);
+        assert_eq!(QueueItemState::Settling { size: 15 }, queue.items[0].state);
+        assert_eq!(120, queue.items[0].next_check_at);
+
+        queue.settle(120, &options);
+        assert_eq!(vec![video_path], queue.get_ready(120));
+    }
+
+    #[test]
+    fn removed_video_should_be_dropped() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("movie.mkv");
+        fs::write(&video_path, "data").unwrap();
+
+        let options = WatchOptions::default();
+
+        let mut queue = WatchQueue::default();
+        queue.add(&video_path, 0, &options);
+        fs::remove_file(&video_path).unwrap();
+
+        queue.settle(100, &options);
+
+        assert!(queue.items.is_empty());
+    }
+
+    #[test]
+    fn retry_delay_should_grow_up_to_limit() {
+        let options = WatchOptions { retry_base_delay: 60, retry_max_delay: 1000, ..WatchOptions::default() };
+
+        assert_eq!(60, get_retry_delay(1, &options));
+        assert_eq!(120, get_retry_delay(2, &options));
+        assert_eq!(480, get_retry_delay(4, &options));
+        assert_eq!(960, get_retry_delay(5, &options));
+        assert_eq!(1000, get_retry_delay(6, &options));
+        assert_eq!(1000, get_retry_delay(100, &options));
+    }
+
+    #[test]
+    fn video_should_be_rescheduled_then_dropped() {
+        let directory = TempDir::new().unwrap();
+        let video_path = directory.path().join("movie.mkv");
+        fs::write(&video_path, "data").unwrap();
+
+        let options = WatchOptions { retry_base_delay: 60, max_attempts: 2, ..WatchOptions::default() };
+
+        let mut queue = WatchQueue::default();
+        queue.add(&video_path, 0, &options);
+
+        assert!(queue.reschedule(&video_path, 1000, &options));
+        assert_eq!(1060, queue.items[0].next_check_at);
+        assert_eq!(1, queue.items[0].attempts);
+
+        assert!(!queue.reschedule(&video_path, 2000, &options));
+        assert!(queue.items.is_empty());
+    }
+
+    #[test]
+    fn queue_should_survive_restart() {
+        let directory = TempDir::new().unwrap();
+        let queue_path = directory.path().join("queue.json");
+        let video_path = directory.path().join("movie.mkv");
+        fs::write(&video_path, "data").unwrap();
+
+        assert!(WatchQueue::load(&queue_path).unwrap().items.is_empty());
+
+        let mut queue = WatchQueue::default();
+        queue.add(&video_path, 50, &WatchOptions::default());
+        queue.save(&queue_path).unwrap();
+
+        assert_eq!(queue, WatchQueue::load(&queue_path).unwrap());
+    }
+}
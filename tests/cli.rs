@@ -0,0 +1,29 @@
+use std::fs;
+use std::process::Command;
+
+use tempfile::tempdir;
+
+use opensubs_rs::mock_server::mock_server::{MockResponse, MockServer};
+
+const EXIT_NO_RESULTS: i32 = 3;
+
+/// Search page without results table
+const NO_RESULTS_PAGE: &str = "<html><body><div class=\"msg\">No results found</div></body></html>";
+
+#[test]
+fn search_without_results_should_exit_with_no_results_code() {
+    let server = MockServer::start();
+    server.mock(r"^/en/search/", MockResponse::html(NO_RESULTS_PAGE));
+
+    let directory = tempdir().unwrap();
+    let config_path = directory.path().join("config.toml");
+    fs::write(&config_path, "").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_opensubs"))
+        .args(["--config", config_path.to_str().unwrap(), "--base-url", &server.url()])
+        .args(["search", "tideland", "--langs", "rus"])
+        .output().unwrap();
+
+    assert_eq!(Some(EXIT_NO_RESULTS), output.status.code());
+    assert_eq!(1, server.requests().len());
+}